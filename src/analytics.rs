@@ -0,0 +1,622 @@
+use crate::zones::{draw_zones, Zone};
+use crate::{iou, Detection};
+use image::{Rgb, RgbImage};
+use imageproc::drawing::{draw_filled_circle_mut, draw_line_segment_mut};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write as _;
+
+// 상수 정의
+const LINE_COLOR: Rgb<u8> = Rgb([255, 200, 0]); // 노란색
+const TRACK_COLOR: Rgb<u8> = Rgb([0, 200, 255]); // 하늘색
+const DEFAULT_TRACK_IOU_THRESHOLD: f32 = 0.3;
+const DEFAULT_MAX_MISSED_FRAMES: u32 = 15;
+
+/// 추적 ID가 부여된 검출 결과
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackedDetection {
+    pub track_id: u64,
+    pub detection: Detection,
+}
+
+/// 검출 박스 중심점
+fn bbox_center(bbox: &[f32; 4]) -> [f32; 2] {
+    [(bbox[0] + bbox[2]) / 2.0, (bbox[1] + bbox[3]) / 2.0]
+}
+
+#[derive(Debug, Clone)]
+struct Track {
+    id: u64,
+    detection: Detection,
+    missed: u32,
+}
+
+/// IoU 기반 단순 다중 객체 추적기
+///
+/// 같은 클래스의 이전 박스와 IoU가 가장 높은 검출을 탐욕적으로 매칭합니다.
+#[derive(Debug, Clone)]
+pub struct IouTracker {
+    pub iou_threshold: f32,
+    pub max_missed_frames: u32,
+    tracks: Vec<Track>,
+    next_id: u64,
+}
+
+impl Default for IouTracker {
+    fn default() -> Self {
+        Self {
+            iou_threshold: DEFAULT_TRACK_IOU_THRESHOLD,
+            max_missed_frames: DEFAULT_MAX_MISSED_FRAMES,
+            tracks: Vec::new(),
+            next_id: 1,
+        }
+    }
+}
+
+impl IouTracker {
+    /// 새 프레임의 검출로 트랙 갱신
+    pub fn update(&mut self, detections: &[Detection]) -> Vec<TrackedDetection> {
+        // 모든 (트랙, 검출) 쌍을 IoU 내림차순으로 매칭
+        let mut pairs = Vec::new();
+        for (t, track) in self.tracks.iter().enumerate() {
+            for (d, det) in detections.iter().enumerate() {
+                if track.detection.class_id != det.class_id {
+                    continue;
+                }
+                let overlap = iou(&track.detection.bbox, &det.bbox);
+                if overlap >= self.iou_threshold {
+                    pairs.push((overlap, t, d));
+                }
+            }
+        }
+        pairs.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let mut track_matched = vec![false; self.tracks.len()];
+        let mut det_track: Vec<Option<u64>> = vec![None; detections.len()];
+        for (_, t, d) in pairs {
+            if track_matched[t] || det_track[d].is_some() {
+                continue;
+            }
+            track_matched[t] = true;
+            det_track[d] = Some(self.tracks[t].id);
+            self.tracks[t].detection = detections[d].clone();
+            self.tracks[t].missed = 0;
+        }
+
+        // 매칭되지 않은 트랙은 누락 횟수 증가 후 오래된 트랙 제거
+        for (track, matched) in self.tracks.iter_mut().zip(track_matched) {
+            if !matched {
+                track.missed += 1;
+            }
+        }
+        let max_missed = self.max_missed_frames;
+        self.tracks.retain(|t| t.missed <= max_missed);
+
+        // 매칭되지 않은 검출은 새 트랙 생성
+        detections
+            .iter()
+            .zip(det_track)
+            .map(|(det, track_id)| {
+                let track_id = track_id.unwrap_or_else(|| {
+                    let id = self.next_id;
+                    self.next_id += 1;
+                    self.tracks.push(Track {
+                        id,
+                        detection: det.clone(),
+                        missed: 0,
+                    });
+                    id
+                });
+                TrackedDetection {
+                    track_id,
+                    detection: det.clone(),
+                }
+            })
+            .collect()
+    }
+
+    /// 현재 유지 중인 트랙 ID (잠시 누락된 트랙 포함)
+    pub fn track_ids(&self) -> impl Iterator<Item = u64> + '_ {
+        self.tracks.iter().map(|t| t.id)
+    }
+}
+
+/// 통과 횟수를 셀 선분 (정규화 좌표 0-1)
+///
+/// 선분 진행 방향 기준 오른쪽에서 왼쪽으로 넘어가면 `forward`,
+/// 반대 방향이면 `backward`로 집계합니다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CountingLine {
+    pub name: String,
+    pub start: [f32; 2],
+    pub end: [f32; 2],
+}
+
+impl CountingLine {
+    /// 점이 선분의 어느 쪽에 있는지 (외적 부호)
+    fn side(&self, p: [f32; 2]) -> f32 {
+        (self.end[0] - self.start[0]) * (p[1] - self.start[1])
+            - (self.end[1] - self.start[1]) * (p[0] - self.start[0])
+    }
+
+    /// 점이 선 위에 있는지 (`f32::signum`은 +0.0을 양수로 보므로 0을 따로 판정)
+    fn is_on_line(&self, p: [f32; 2]) -> bool {
+        self.side(p) == 0.0
+    }
+
+    /// 이동 경로가 선분을 통과했는지 확인하고 방향 반환 (true = forward)
+    ///
+    /// 양 끝 중 하나라도 선 위에 있으면 통과로 보지 않으므로, 호출 측은 선 위의
+    /// 위치 대신 선 밖의 마지막 위치를 기준점으로 유지해야 합니다.
+    fn crossing(&self, from: [f32; 2], to: [f32; 2]) -> Option<bool> {
+        let s_from = self.side(from);
+        let s_to = self.side(to);
+        if s_from == 0.0 || s_to == 0.0 || (s_from > 0.0) == (s_to > 0.0) {
+            return None;
+        }
+
+        // 이동 경로 양 끝이 선분을 기준으로도 반대편에 있어야 실제 교차 (끝점 접촉 포함)
+        let d = [to[0] - from[0], to[1] - from[1]];
+        let path_side = |p: [f32; 2]| d[0] * (p[1] - from[1]) - d[1] * (p[0] - from[0]);
+        if path_side(self.start) * path_side(self.end) > 0.0 {
+            return None;
+        }
+
+        Some(s_from > 0.0)
+    }
+}
+
+/// 선분별 방향/클래스별 통과 횟수
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LineCounts {
+    pub line: String,
+    pub forward: BTreeMap<String, u32>,
+    pub backward: BTreeMap<String, u32>,
+}
+
+impl LineCounts {
+    pub fn total_forward(&self) -> u32 {
+        self.forward.values().sum()
+    }
+
+    pub fn total_backward(&self) -> u32 {
+        self.backward.values().sum()
+    }
+}
+
+/// 영역 체류 기록
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DwellRecord {
+    pub zone: String,
+    pub track_id: u64,
+    pub class_name: String,
+    pub entered_at_s: f64,
+    pub duration_s: f64,
+    /// 아직 영역 안에 머물고 있는지 여부
+    pub active: bool,
+}
+
+/// 특정 시점의 영역별 클래스 점유 수
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OccupancySample {
+    pub timestamp_s: f64,
+    pub zone: String,
+    pub counts: BTreeMap<String, u32>,
+}
+
+/// 내보내기용 분석 결과
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnalyticsReport {
+    pub line_counts: Vec<LineCounts>,
+    pub dwell: Vec<DwellRecord>,
+    pub occupancy: Vec<OccupancySample>,
+}
+
+/// CSV 필드 이스케이프
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl AnalyticsReport {
+    /// JSON 문자열로 변환
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 선분 통과 횟수 CSV (line,direction,class,count)
+    pub fn line_counts_csv(&self) -> String {
+        let mut out = String::from("line,direction,class,count\n");
+        for counts in &self.line_counts {
+            for (direction, map) in [("forward", &counts.forward), ("backward", &counts.backward)] {
+                for (class_name, count) in map {
+                    let _ = writeln!(
+                        out,
+                        "{},{},{},{}",
+                        csv_field(&counts.line),
+                        direction,
+                        csv_field(class_name),
+                        count
+                    );
+                }
+            }
+        }
+        out
+    }
+
+    /// 체류 시간 CSV
+    pub fn dwell_csv(&self) -> String {
+        let mut out = String::from("zone,track_id,class,entered_at_s,duration_s,active\n");
+        for record in &self.dwell {
+            let _ = writeln!(
+                out,
+                "{},{},{},{:.3},{:.3},{}",
+                csv_field(&record.zone),
+                record.track_id,
+                csv_field(&record.class_name),
+                record.entered_at_s,
+                record.duration_s,
+                record.active
+            );
+        }
+        out
+    }
+
+    /// 점유 시계열 CSV (timestamp_s,zone,class,count)
+    pub fn occupancy_csv(&self) -> String {
+        let mut out = String::from("timestamp_s,zone,class,count\n");
+        for sample in &self.occupancy {
+            for (class_name, count) in &sample.counts {
+                let _ = writeln!(
+                    out,
+                    "{:.3},{},{},{}",
+                    sample.timestamp_s,
+                    csv_field(&sample.zone),
+                    csv_field(class_name),
+                    count
+                );
+            }
+        }
+        out
+    }
+
+    /// 디렉터리에 JSON 및 CSV 파일 저장
+    pub fn write_to_dir(&self, dir: impl AsRef<std::path::Path>) -> anyhow::Result<()> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join("analytics.json"), self.to_json()?)?;
+        std::fs::write(dir.join("line_counts.csv"), self.line_counts_csv())?;
+        std::fs::write(dir.join("dwell.csv"), self.dwell_csv())?;
+        std::fs::write(dir.join("occupancy.csv"), self.occupancy_csv())?;
+        Ok(())
+    }
+}
+
+/// 프레임 단위 검출 결과를 누적하는 시계열 분석기
+#[derive(Debug, Clone, Default)]
+pub struct Analytics {
+    pub tracker: IouTracker,
+    pub lines: Vec<CountingLine>,
+    pub zones: Vec<Zone>,
+    line_counts: Vec<LineCounts>,
+    // (선분 인덱스, 트랙 ID) -> 해당 선 밖의 마지막 중심점
+    last_centers: HashMap<(usize, u64), [f32; 2]>,
+    dwell: Vec<DwellRecord>,
+    // (영역 인덱스, 트랙 ID) -> dwell 인덱스
+    open_dwell: HashMap<(usize, u64), usize>,
+    occupancy: Vec<OccupancySample>,
+    last_tracked: Vec<TrackedDetection>,
+}
+
+impl Analytics {
+    /// 선분과 영역으로 분석기 생성
+    pub fn new(lines: Vec<CountingLine>, zones: Vec<Zone>) -> Self {
+        let line_counts = lines
+            .iter()
+            .map(|line| LineCounts {
+                line: line.name.clone(),
+                ..Default::default()
+            })
+            .collect();
+        Self {
+            lines,
+            zones,
+            line_counts,
+            ..Default::default()
+        }
+    }
+
+    /// 한 프레임의 검출 결과 반영 (timestamp_s: 프레임 시각, 초)
+    pub fn update(&mut self, timestamp_s: f64, detections: &[Detection]) -> Vec<TrackedDetection> {
+        let tracked = self.tracker.update(detections);
+
+        // 선분 통과 집계 (선 위에 놓인 중심점은 기준점으로 쓰지 않아 중복 집계 방지)
+        for t in &tracked {
+            let center = bbox_center(&t.detection.bbox);
+            for (l, (line, counts)) in self
+                .lines
+                .iter()
+                .zip(self.line_counts.iter_mut())
+                .enumerate()
+            {
+                if line.is_on_line(center) {
+                    continue;
+                }
+                let key = (l, t.track_id);
+                if let Some(&prev) = self.last_centers.get(&key) {
+                    if let Some(forward) = line.crossing(prev, center) {
+                        let map = if forward {
+                            &mut counts.forward
+                        } else {
+                            &mut counts.backward
+                        };
+                        *map.entry(t.detection.class_name.clone()).or_insert(0) += 1;
+                    }
+                }
+                self.last_centers.insert(key, center);
+            }
+        }
+        // 잠시 누락된 트랙의 마지막 위치는 유지하고, 추적기에서 제거된 트랙만 정리
+        let active: HashSet<u64> = self.tracker.track_ids().collect();
+        self.last_centers.retain(|(_, id), _| active.contains(id));
+
+        // 영역 체류 및 점유 집계
+        let mut still_inside = Vec::new();
+        for (z, zone) in self.zones.iter().enumerate() {
+            let mut counts = BTreeMap::new();
+            for t in &tracked {
+                let [cx, cy] = bbox_center(&t.detection.bbox);
+                if !zone.contains_point(cx, cy) {
+                    continue;
+                }
+                *counts.entry(t.detection.class_name.clone()).or_insert(0) += 1;
+
+                let key = (z, t.track_id);
+                match self.open_dwell.get(&key) {
+                    // 누락 프레임 동안에도 머문 것으로 보고 진입 시각부터 계산
+                    Some(&idx) => {
+                        let record = &mut self.dwell[idx];
+                        record.duration_s = timestamp_s - record.entered_at_s;
+                    }
+                    None => {
+                        self.dwell.push(DwellRecord {
+                            zone: zone.name.clone(),
+                            track_id: t.track_id,
+                            class_name: t.detection.class_name.clone(),
+                            entered_at_s: timestamp_s,
+                            duration_s: 0.0,
+                            active: true,
+                        });
+                        self.open_dwell.insert(key, self.dwell.len() - 1);
+                    }
+                }
+                still_inside.push(key);
+            }
+            self.occupancy.push(OccupancySample {
+                timestamp_s,
+                zone: zone.name.clone(),
+                counts,
+            });
+        }
+
+        // 영역 밖에서 검출되었거나 추적기에서 제거된 트랙의 체류 종료
+        // (이번 프레임에만 누락된 트랙은 다시 나타날 때까지 유지)
+        let seen: HashSet<u64> = tracked.iter().map(|t| t.track_id).collect();
+        let dwell = &mut self.dwell;
+        self.open_dwell.retain(|key, idx| {
+            let (_, id) = *key;
+            let inside =
+                still_inside.contains(key) || (!seen.contains(&id) && active.contains(&id));
+            if !inside {
+                dwell[*idx].active = false;
+            }
+            inside
+        });
+
+        self.last_tracked = tracked.clone();
+        tracked
+    }
+
+    /// 현재까지의 선분 통과 횟수
+    pub fn line_counts(&self) -> &[LineCounts] {
+        &self.line_counts
+    }
+
+    /// 현재까지의 분석 결과
+    pub fn report(&self) -> AnalyticsReport {
+        AnalyticsReport {
+            line_counts: self.line_counts.clone(),
+            dwell: self.dwell.clone(),
+            occupancy: self.occupancy.clone(),
+        }
+    }
+
+    /// 영역, 카운팅 선분, 추적 중인 객체 중심을 프레임에 그리기
+    pub fn draw(&self, image: &mut RgbImage) {
+        let (width, height) = (image.width() as f32, image.height() as f32);
+
+        draw_zones(image, &self.zones);
+
+        for line in &self.lines {
+            let start = (line.start[0] * width, line.start[1] * height);
+            let end = (line.end[0] * width, line.end[1] * height);
+            draw_line_segment_mut(image, start, end, LINE_COLOR);
+            // 선분 시작점 표시 (방향 확인용)
+            draw_filled_circle_mut(image, (start.0 as i32, start.1 as i32), 4, LINE_COLOR);
+        }
+
+        for t in &self.last_tracked {
+            let [cx, cy] = bbox_center(&t.detection.bbox);
+            draw_filled_circle_mut(
+                image,
+                ((cx * width) as i32, (cy * height) as i32),
+                3,
+                TRACK_COLOR,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zones::ZoneMode;
+
+    fn detection_at(cx: f32, cy: f32) -> Detection {
+        Detection {
+            bbox: [cx - 0.125, cy - 0.125, cx + 0.125, cy + 0.125],
+            confidence: 0.9,
+            class_id: 1,
+            class_name: "person".to_string(),
+            mask: None,
+            keypoints: None,
+            obb: None,
+            secondary: Vec::new(),
+            original_class_id: None,
+        }
+    }
+
+    /// 화면 가운데를 가로지르는 수평선 (아래 → 위 이동이 forward)
+    fn horizontal_line() -> CountingLine {
+        CountingLine {
+            name: "gate".to_string(),
+            start: [0.0, 0.5],
+            end: [1.0, 0.5],
+        }
+    }
+
+    /// 한 트랙을 주어진 세로 위치들로 순서대로 이동시킨 뒤 (forward, backward) 반환
+    fn run_track(ys: &[f32]) -> (u32, u32) {
+        let mut analytics = Analytics::new(vec![horizontal_line()], Vec::new());
+        for (i, &y) in ys.iter().enumerate() {
+            analytics.update(i as f64, &[detection_at(0.5, y)]);
+        }
+        let counts = &analytics.line_counts()[0];
+        (counts.total_forward(), counts.total_backward())
+    }
+
+    #[test]
+    fn crossing_direction() {
+        let line = horizontal_line();
+        assert_eq!(line.crossing([0.5, 0.8], [0.5, 0.2]), Some(true));
+        assert_eq!(line.crossing([0.5, 0.2], [0.5, 0.8]), Some(false));
+        assert_eq!(line.crossing([0.5, 0.2], [0.5, 0.4]), None);
+        // 선분 끝을 벗어난 곳에서의 이동은 통과가 아님
+        let short = CountingLine {
+            end: [0.3, 0.5],
+            ..horizontal_line()
+        };
+        assert_eq!(short.crossing([0.5, 0.8], [0.5, 0.2]), None);
+    }
+
+    #[test]
+    fn counts_each_crossing_once() {
+        assert_eq!(run_track(&[0.48, 0.46, 0.44]), (0, 0));
+        assert_eq!(run_track(&[0.6, 0.55, 0.45, 0.4]), (1, 0));
+        assert_eq!(run_track(&[0.45, 0.55, 0.45]), (1, 1));
+    }
+
+    #[test]
+    fn center_on_line_is_not_double_counted() {
+        // 선에 닿았다가 돌아가기를 반복해도 통과가 아님
+        assert_eq!(run_track(&[0.55, 0.5, 0.55, 0.5, 0.55]), (0, 0));
+        // 선 위에 머문 뒤 넘어가면 한 번만 집계
+        assert_eq!(run_track(&[0.55, 0.5, 0.5, 0.45]), (1, 0));
+    }
+
+    #[test]
+    fn expired_tracks_are_pruned() {
+        let mut analytics = Analytics::new(vec![horizontal_line()], Vec::new());
+        analytics.tracker.max_missed_frames = 2;
+        analytics.update(0.0, &[detection_at(0.5, 0.7)]);
+        assert_eq!(analytics.last_centers.len(), 1);
+
+        // 허용 누락 프레임 동안은 마지막 위치 유지
+        analytics.update(1.0, &[]);
+        analytics.update(2.0, &[]);
+        assert_eq!(analytics.last_centers.len(), 1);
+
+        analytics.update(3.0, &[]);
+        assert!(analytics.last_centers.is_empty());
+        assert_eq!(analytics.tracker.track_ids().count(), 0);
+    }
+
+    #[test]
+    fn tracker_keeps_id_for_overlapping_boxes() {
+        let mut tracker = IouTracker::default();
+        let first = tracker.update(&[detection_at(0.3, 0.3)]);
+        let second = tracker.update(&[detection_at(0.31, 0.3), detection_at(0.8, 0.8)]);
+        assert_eq!(second[0].track_id, first[0].track_id);
+        assert_ne!(second[1].track_id, first[0].track_id);
+    }
+
+    #[test]
+    fn dwell_accumulates_while_inside_zone() {
+        let zone = Zone {
+            name: "door".to_string(),
+            polygon: vec![[0.0, 0.0], [0.5, 0.0], [0.5, 0.5], [0.0, 0.5]],
+            mode: ZoneMode::Include,
+        };
+        let mut analytics = Analytics::new(Vec::new(), vec![zone]);
+        // 트랙이 바로 제거되도록 설정
+        analytics.tracker.max_missed_frames = 0;
+        analytics.update(0.0, &[detection_at(0.2, 0.2)]);
+        analytics.update(1.5, &[detection_at(0.21, 0.2)]);
+        analytics.update(2.0, &[detection_at(0.8, 0.8)]);
+
+        let report = analytics.report();
+        assert_eq!(report.dwell.len(), 1);
+        assert!((report.dwell[0].duration_s - 1.5).abs() < 1e-9);
+        assert!(!report.dwell[0].active);
+        assert_eq!(report.occupancy.len(), 3);
+    }
+
+    fn door_zone() -> Zone {
+        Zone {
+            name: "door".to_string(),
+            polygon: vec![[0.0, 0.0], [0.5, 0.0], [0.5, 0.5], [0.0, 0.5]],
+            mode: ZoneMode::Include,
+        }
+    }
+
+    #[test]
+    fn dwell_survives_missed_frames() {
+        let mut analytics = Analytics::new(Vec::new(), vec![door_zone()]);
+        analytics.tracker.max_missed_frames = 2;
+        analytics.update(0.0, &[detection_at(0.2, 0.2)]);
+        analytics.update(1.0, &[]);
+        assert!(analytics.report().dwell[0].active);
+        analytics.update(2.0, &[detection_at(0.21, 0.2)]);
+
+        let report = analytics.report();
+        assert_eq!(report.dwell.len(), 1);
+        assert!(report.dwell[0].active);
+        assert!((report.dwell[0].duration_s - 2.0).abs() < 1e-9);
+
+        // 허용 누락 프레임을 넘기면 종료
+        for t in 3..6 {
+            analytics.update(t as f64, &[]);
+        }
+        let report = analytics.report();
+        assert_eq!(report.dwell.len(), 1);
+        assert!(!report.dwell[0].active);
+        assert!((report.dwell[0].duration_s - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn dwell_closes_when_tracked_object_leaves_zone() {
+        let mut analytics = Analytics::new(Vec::new(), vec![door_zone()]);
+        analytics.update(0.0, &[detection_at(0.35, 0.2)]);
+        analytics.update(1.0, &[detection_at(0.45, 0.2)]);
+        let tracked = analytics.update(2.0, &[detection_at(0.55, 0.2)]);
+
+        let report = analytics.report();
+        assert_eq!(report.dwell.len(), 1);
+        assert_eq!(report.dwell[0].track_id, tracked[0].track_id);
+        assert!(!report.dwell[0].active);
+        assert!((report.dwell[0].duration_s - 1.0).abs() < 1e-9);
+    }
+}
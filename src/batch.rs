@@ -0,0 +1,477 @@
+use crate::{
+    decode_image_with_orientation, detect_objects_with_orientation, draw_detections_with_skeleton,
+    ClassRemap, Detection, DrawStyle, ModelCache, ModelSource, OrientationMode, SessionOptions,
+    Skeleton, TtaConfig,
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+
+// 상수 정의
+pub(crate) const DEFAULT_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "webp"];
+
+/// 배치 처리 설정
+#[derive(Debug, Clone)]
+pub struct BatchConfig {
+    pub input_dir: PathBuf,
+    pub output_dir: PathBuf,
+    /// 처리할 확장자 (소문자, 점 제외)
+    pub extensions: Vec<String>,
+    /// 워커 스레드 수 (워커마다 별도 세션 사용)
+    pub workers: usize,
+    /// 출력이 이미 있어도 다시 처리할지 여부
+    pub overwrite: bool,
+    /// 저장 전에 적용할 클래스 재매핑
+    pub remap: Option<ClassRemap>,
+    /// EXIF 방향 처리 방식 (기본: 적용)
+    pub orientation: OrientationMode,
+    /// 키포인트 스켈레톤 (None이면 키포인트 수에 맞는 기본값)
+    pub skeleton: Option<Skeleton>,
+}
+
+impl BatchConfig {
+    pub fn new(input_dir: impl Into<PathBuf>, output_dir: impl Into<PathBuf>) -> Self {
+        // 코어를 워커끼리 나눠 쓰므로(`worker_session_options`) 워커 수는 보수적으로 설정
+        let workers = std::thread::available_parallelism()
+            .map(|n| (n.get() / 4).max(1))
+            .unwrap_or(1);
+        Self {
+            input_dir: input_dir.into(),
+            output_dir: output_dir.into(),
+            extensions: DEFAULT_EXTENSIONS.iter().map(|e| e.to_string()).collect(),
+            workers,
+            overwrite: false,
+            remap: None,
+            orientation: OrientationMode::Apply,
+            skeleton: None,
+        }
+    }
+}
+
+/// 이미지별 JSON 출력 형식
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageDetections {
+    pub image: String,
+    pub width: u32,
+    pub height: u32,
+    pub inference_time_ms: f64,
+    pub detections: Vec<Detection>,
+}
+
+/// 진행 상황 이벤트
+#[derive(Debug, Clone)]
+pub enum BatchProgress {
+    Processed {
+        path: PathBuf,
+        done: usize,
+        total: usize,
+    },
+    Skipped {
+        path: PathBuf,
+        done: usize,
+        total: usize,
+    },
+    Failed {
+        path: PathBuf,
+        error: String,
+        done: usize,
+        total: usize,
+    },
+}
+
+/// 배치 처리 결과 요약
+#[derive(Debug, Clone, Default)]
+pub struct BatchReport {
+    pub total: usize,
+    pub processed: usize,
+    pub skipped: usize,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// 입력 디렉터리를 재귀적으로 탐색하여 대상 이미지 수집 (정렬된 순서)
+///
+/// 디렉터리 심볼릭 링크는 따라가지 않습니다 (순환 링크 방지).
+pub fn collect_images(dir: &Path, extensions: &[String]) -> anyhow::Result<Vec<PathBuf>> {
+    collect_images_excluding(dir, extensions, None)
+}
+
+/// `exclude` 디렉터리(보통 입력 폴더 안에 둔 출력 폴더)는 건너뛰고 이미지 수집
+pub fn collect_images_excluding(
+    dir: &Path,
+    extensions: &[String],
+    exclude: Option<&Path>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    // 아직 없는 출력 폴더는 탐색 중에 만날 일이 없음
+    let exclude = exclude.and_then(|path| std::fs::canonicalize(path).ok());
+    let mut images = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+
+    while let Some(current) = stack.pop() {
+        for entry in std::fs::read_dir(&current)? {
+            let entry = entry?;
+            let path = entry.path();
+            // `DirEntry::file_type`은 심볼릭 링크를 따라가지 않음
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                let excluded = exclude.is_some()
+                    && std::fs::canonicalize(&path).ok().as_deref() == exclude.as_deref();
+                if !excluded {
+                    stack.push(path);
+                }
+            } else if file_type.is_symlink() && path.is_dir() {
+                continue;
+            } else if has_extension(&path, extensions) {
+                images.push(path);
+            }
+        }
+    }
+
+    images.sort();
+    Ok(images)
+}
+
+/// 확장자 필터 (대소문자 무시)
+fn has_extension(path: &Path, extensions: &[String]) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| {
+            extensions
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(e))
+        })
+        .unwrap_or(false)
+}
+
+/// 폴더의 이미지를 모델 세션 하나로 순서대로 처리 (크롭 저장, 가림 처리 등 폴더 단위 명령 공용)
+///
+/// 입력 폴더 안에 둔 출력 폴더는 다시 처리하지 않습니다. `process`는 이미지마다 호출되어
+/// 결과 수(저장한 크롭, 가린 영역 등)를 반환하며, 그 합계를 요약과 함께 반환합니다
+/// (`BatchReport::processed`는 처리한 이미지 수).
+pub fn run_folder(
+    input_dir: &Path,
+    output_dir: &Path,
+    extensions: &[String],
+    mut on_progress: impl FnMut(&BatchProgress),
+    mut process: impl FnMut(&Path, &mut ModelCache) -> anyhow::Result<usize>,
+) -> anyhow::Result<(BatchReport, usize)> {
+    let images = collect_images_excluding(input_dir, extensions, Some(output_dir))?;
+    let total = images.len();
+    let mut report = BatchReport {
+        total,
+        ..Default::default()
+    };
+    let mut count = 0;
+    let mut cache = ModelCache::new()?;
+
+    for (i, path) in images.into_iter().enumerate() {
+        let done = i + 1;
+        match process(path.as_path(), &mut cache) {
+            Ok(n) => {
+                count += n;
+                report.processed += 1;
+                on_progress(&BatchProgress::Processed { path, done, total });
+            }
+            Err(e) => {
+                let error = e.to_string();
+                report.failed.push((path.clone(), error.clone()));
+                on_progress(&BatchProgress::Failed {
+                    path,
+                    error,
+                    done,
+                    total,
+                });
+            }
+        }
+    }
+
+    Ok((report, count))
+}
+
+/// 입력 경로에 대응하는 (주석 이미지, JSON) 출력 경로
+///
+/// 같은 폴더의 `a.jpg`와 `a.png`가 겹치지 않도록 JSON 이름에 원본 확장자를 유지합니다 (`a.jpg.json`).
+pub fn output_paths(config: &BatchConfig, input: &Path) -> (PathBuf, PathBuf) {
+    let relative = input.strip_prefix(&config.input_dir).unwrap_or(input);
+    let annotated = config.output_dir.join(relative);
+    let mut json_name = annotated.file_name().unwrap_or_default().to_os_string();
+    json_name.push(".json");
+    let json = annotated.with_file_name(json_name);
+    (annotated, json)
+}
+
+/// 단일 이미지 처리 후 결과 저장
+fn process_one(config: &BatchConfig, input: &Path, cache: &mut ModelCache) -> anyhow::Result<()> {
+    let (annotated_path, json_path) = output_paths(config, input);
+    let image_data = std::fs::read(input)?;
+    let tta = TtaConfig {
+        skeleton: config.skeleton.clone(),
+        ..TtaConfig::default()
+    };
+    let mut result = detect_objects_with_orientation(&image_data, cache, &tta, config.orientation)?;
+    if let Some(remap) = &config.remap {
+        remap.apply(&mut result.detections);
+        // 제외된 클래스의 박스가 남지 않도록 원본에 다시 그림
+        result.result_image = decode_image_with_orientation(&image_data, config.orientation)?;
+        draw_detections_with_skeleton(
+            &mut result.result_image,
+            &result.detections,
+            &DrawStyle::default(),
+            config.skeleton.as_ref(),
+        );
+    }
+
+    if let Some(parent) = annotated_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    result.result_image.save(&annotated_path)?;
+
+    let record = ImageDetections {
+        image: input.to_string_lossy().into_owned(),
+        width: result.result_image.width(),
+        height: result.result_image.height(),
+        inference_time_ms: result.inference_time_ms,
+        detections: result.detections,
+    };
+    // JSON은 마지막에 기록하여 재개 시 완료 여부 판단에 사용
+    std::fs::write(&json_path, serde_json::to_string_pretty(&record)?)?;
+    Ok(())
+}
+
+/// 배치 워커 하나의 세션 옵션
+///
+/// 기본 `SessionOptions`(intra 16개 등)를 워커마다 쓰면 코어 수보다 훨씬 많은 스레드가 생기므로,
+/// 사용 가능한 코어를 워커 수로 나눠 intra 스레드를 정하고 연산자 간 병렬은 쓰지 않습니다.
+fn worker_session_options(workers: usize) -> SessionOptions {
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    SessionOptions {
+        intra_threads: (cores / workers.max(1)).max(1),
+        inter_threads: 1,
+    }
+}
+
+/// 스레드 패닉 메시지 (문자열이 아니면 대체 문구)
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "알 수 없는 패닉".to_string())
+}
+
+/// 디렉터리 배치 처리 (워커 풀, 재개 가능)
+///
+/// 각 워커는 자체 `ModelCache`를 가지며, 출력 JSON이 이미 존재하는 입력은 건너뜁니다.
+/// 워커 스레드가 패닉으로 중단되어 결과를 보내지 못한 입력은 실패로 보고합니다.
+pub fn run_batch(
+    config: &BatchConfig,
+    mut on_progress: impl FnMut(&BatchProgress),
+) -> anyhow::Result<BatchReport> {
+    let images = collect_images_excluding(
+        &config.input_dir,
+        &config.extensions,
+        Some(&config.output_dir),
+    )?;
+    let total = images.len();
+    let mut report = BatchReport {
+        total,
+        ..Default::default()
+    };
+
+    // 이미 처리된 입력 제외
+    let mut pending = Vec::new();
+    let mut done = 0;
+    for path in images {
+        let (_, json_path) = output_paths(config, &path);
+        if !config.overwrite && json_path.exists() {
+            done += 1;
+            report.skipped += 1;
+            on_progress(&BatchProgress::Skipped { path, done, total });
+        } else {
+            pending.push(path);
+        }
+    }
+
+    // 모두 처리된 경우에는 세션을 만들지 않음
+    if pending.is_empty() {
+        return Ok(report);
+    }
+
+    let workers = config.workers.max(1).min(pending.len());
+    let caches = (0..workers)
+        .map(|_| ModelCache::with_options(ModelSource::Embedded, worker_session_options(workers)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let queue = Arc::new(pending);
+    let next = Arc::new(AtomicUsize::new(0));
+    let (tx, rx) = mpsc::channel::<(usize, anyhow::Result<()>)>();
+
+    let mut handles = Vec::with_capacity(workers);
+    for mut cache in caches {
+        let queue = Arc::clone(&queue);
+        let next = Arc::clone(&next);
+        let tx = tx.clone();
+        let config = config.clone();
+        handles.push(std::thread::spawn(move || loop {
+            let index = next.fetch_add(1, Ordering::SeqCst);
+            let Some(path) = queue.get(index) else {
+                break;
+            };
+            let result = process_one(&config, path, &mut cache);
+            if tx.send((index, result)).is_err() {
+                break;
+            }
+        }));
+    }
+    drop(tx);
+
+    let mut reported = vec![false; queue.len()];
+    let mut record = |path: PathBuf, result: anyhow::Result<()>, report: &mut BatchReport| {
+        done += 1;
+        match result {
+            Ok(()) => {
+                report.processed += 1;
+                on_progress(&BatchProgress::Processed { path, done, total });
+            }
+            Err(e) => {
+                let error = e.to_string();
+                report.failed.push((path.clone(), error.clone()));
+                on_progress(&BatchProgress::Failed {
+                    path,
+                    error,
+                    done,
+                    total,
+                });
+            }
+        }
+    };
+    for (index, result) in rx {
+        reported[index] = true;
+        record(queue[index].clone(), result, &mut report);
+    }
+
+    let panics: Vec<String> = handles
+        .into_iter()
+        .filter_map(|handle| handle.join().err())
+        .map(|payload| panic_message(payload.as_ref()))
+        .collect();
+    if !panics.is_empty() {
+        // 패닉한 워커가 처리 중이던 입력 (모든 워커가 중단되었으면 남은 대기열 전체)
+        let error = format!("워커 스레드가 중단되었습니다: {}", panics.join("; "));
+        for (index, path) in queue.iter().enumerate() {
+            if !reported[index] {
+                record(path.clone(), Err(anyhow::anyhow!("{}", error)), &mut report);
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 테스트마다 비어 있는 임시 폴더
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("rf_detr_batch_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn touch(path: &Path) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, b"").unwrap();
+    }
+
+    fn extensions() -> Vec<String> {
+        DEFAULT_EXTENSIONS.iter().map(|e| e.to_string()).collect()
+    }
+
+    #[test]
+    fn collects_images_recursively_and_skips_the_output_dir() {
+        let input = temp_dir("collect");
+        for name in ["a.jpg", "b.PNG", "c.txt", "sub/d.jpeg", "out/e.jpg"] {
+            touch(&input.join(name));
+        }
+        let output = input.join("out");
+
+        let images = collect_images_excluding(&input, &extensions(), Some(&output)).unwrap();
+        let expected: Vec<PathBuf> = ["a.jpg", "b.PNG", "sub/d.jpeg"]
+            .iter()
+            .map(|n| input.join(n))
+            .collect();
+        assert_eq!(images, expected);
+
+        // 제외하지 않으면 출력 폴더의 이미지도 포함, 아직 없는 출력 폴더는 무시
+        assert_eq!(collect_images(&input, &extensions()).unwrap().len(), 4);
+        let missing = input.join("missing");
+        assert_eq!(
+            collect_images_excluding(&input, &extensions(), Some(&missing)).unwrap(),
+            collect_images(&input, &extensions()).unwrap()
+        );
+        std::fs::remove_dir_all(&input).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn does_not_follow_directory_symlinks() {
+        let input = temp_dir("symlink");
+        touch(&input.join("sub/a.jpg"));
+        // 자기 자신을 가리키는 순환 링크와 하위 폴더 링크 모두 건너뜀
+        std::os::unix::fs::symlink(&input, input.join("loop")).unwrap();
+        std::os::unix::fs::symlink(input.join("sub"), input.join("link")).unwrap();
+
+        let images = collect_images(&input, &extensions()).unwrap();
+        assert_eq!(images, vec![input.join("sub/a.jpg")]);
+        std::fs::remove_dir_all(&input).unwrap();
+    }
+
+    #[test]
+    fn workers_share_the_available_cores() {
+        let cores = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        assert_eq!(worker_session_options(1).intra_threads, cores);
+        for workers in [2, 3, 8, cores * 2] {
+            let options = worker_session_options(workers);
+            assert!(options.intra_threads >= 1);
+            assert!(options.intra_threads * workers <= cores.max(workers));
+        }
+    }
+
+    #[test]
+    fn json_names_keep_the_source_extension() {
+        let config = BatchConfig::new("/data/in", "/data/out");
+        let (annotated, json) = output_paths(&config, Path::new("/data/in/x/a.jpg"));
+        assert_eq!(annotated, Path::new("/data/out/x/a.jpg"));
+        assert_eq!(json, Path::new("/data/out/x/a.jpg.json"));
+
+        let (_, png_json) = output_paths(&config, Path::new("/data/in/x/a.png"));
+        assert_ne!(json, png_json);
+    }
+
+    #[test]
+    fn skips_inputs_that_already_have_json() {
+        let root = temp_dir("resume");
+        let config = BatchConfig::new(root.join("in"), root.join("out"));
+        for name in ["a.jpg", "sub/b.png"] {
+            let input = config.input_dir.join(name);
+            touch(&input);
+            touch(&output_paths(&config, &input).1);
+        }
+
+        let mut events = Vec::new();
+        let report = run_batch(&config, |progress| events.push(progress.clone())).unwrap();
+        assert_eq!((report.total, report.skipped, report.processed), (2, 2, 0));
+        assert!(report.failed.is_empty());
+        assert!(events
+            .iter()
+            .all(|e| matches!(e, BatchProgress::Skipped { total: 2, .. })));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
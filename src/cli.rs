@@ -0,0 +1,402 @@
+use rf_detr_onnx_test_lib::{
+    export_crops, redact_folder, redact_image_file, run_batch, BatchConfig, BatchProgress,
+    ClassRemap, CropConfig, ModelCache, ModelSource, OrientationMode, RedactBatchConfig,
+    RedactionConfig, RedactionMode, Skeleton, CONFIDENCE_THRESHOLD,
+};
+use std::path::PathBuf;
+
+/// 사용법 안내 문자열
+const USAGE: &str = "\
+사용법:
+  rf_detr_onnx_test                         GUI 실행
+  rf_detr_onnx_test batch <입력 폴더> <출력 폴더> [옵션]
+      --workers <N>        워커 수 (기본: 코어 수 / 4)
+      --ext <png,jpg,...>  처리할 확장자
+      --overwrite          기존 출력도 다시 처리
+      --remap <JSON>       클래스 재매핑 설정 파일
+      --raw-orientation    EXIF 방향 태그를 무시하고 저장된 방향 그대로 처리
+      --skeleton <JSON>    키포인트 스켈레톤 파일 (이름, 연결선, 좌우 반전 쌍)
+  rf_detr_onnx_test crops <입력 폴더> <출력 폴더> [옵션]
+      --padding <비율>     박스 크기 대비 여백 (기본: 0.1)
+      --min-size <픽셀>    짧은 변이 이보다 작은 크롭 제외 (기본: 8)
+      --threshold <값>     신뢰도 임계값 (기본: 0.5)
+      --ext <png,jpg,...>  처리할 확장자
+      --remap <JSON>       클래스 재매핑 설정 파일
+      --raw-orientation    EXIF 방향 태그를 무시하고 저장된 방향 그대로 처리
+  rf_detr_onnx_test redact <입력 이미지|폴더> <출력 이미지|폴더> [옵션]
+      --mode <방식>        blur, pixelate, fill (기본: blur)
+      --classes <이름,...> 가릴 클래스 (기본: 모든 검출)
+      --margin <비율>      박스 크기 대비 확장 여백 (기본: 0.1)
+      --strength <값>      블러 시그마 또는 모자이크 블록 크기의 최솟값 (큰 박스는 짧은 변에 비례해 더 강하게)
+      --color <R,G,B>      fill 색상 (기본: 0,0,0)
+      --threshold <값>     신뢰도 임계값 (기본: 0.5)
+      --ext <png,jpg,...>  처리할 확장자 (폴더 입력)
+      --remap <JSON>       클래스 재매핑 설정 파일 (--classes는 대상 클래스 이름 기준)
+      --raw-orientation    EXIF 방향 태그를 무시하고 저장된 방향 그대로 처리
+  rf_detr_onnx_test inspect [모델 경로] [--json]
+                                            모델 입출력 및 메타데이터 출력 (기본: 임베디드 모델)";
+
+/// 명령행 인자 처리 (인자 없이 실행하면 GUI 사용)
+pub fn run(args: &[String]) -> anyhow::Result<()> {
+    match args.first().map(String::as_str) {
+        Some("batch") => run_batch_command(&args[1..]),
+        Some("crops") => run_crops_command(&args[1..]),
+        Some("redact") => run_redact_command(&args[1..]),
+        Some("inspect") => run_inspect_command(&args[1..]),
+        Some("-h") | Some("--help") | Some("help") => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Some(other) => Err(anyhow::anyhow!("알 수 없는 명령: {other}\n{USAGE}")),
+        None => Err(anyhow::anyhow!("{USAGE}")),
+    }
+}
+
+/// 옵션 값 가져오기
+fn option_value<'a>(args: &'a [String], i: &mut usize, name: &str) -> anyhow::Result<&'a str> {
+    *i += 1;
+    args.get(*i)
+        .map(String::as_str)
+        .ok_or_else(|| anyhow::anyhow!("{name} 옵션에 값이 필요합니다"))
+}
+
+/// --ext 값 파싱 (쉼표 구분, 점 제외 소문자)
+fn parse_extensions(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+        .filter(|e| !e.is_empty())
+        .collect()
+}
+
+/// --remap 설정 파일 로드
+fn load_remap(path: &str) -> anyhow::Result<ClassRemap> {
+    ClassRemap::from_file(path)
+        .map_err(|e| anyhow::anyhow!("클래스 재매핑 설정을 읽을 수 없습니다 ({path}): {e}"))
+}
+
+/// --skeleton 스켈레톤 파일 로드
+fn load_skeleton(path: &str) -> anyhow::Result<Skeleton> {
+    Skeleton::from_json_file(path)
+        .map_err(|e| anyhow::anyhow!("스켈레톤 파일을 읽을 수 없습니다 ({path}): {e}"))
+}
+
+/// 폴더 단위 명령의 진행 상황 출력
+fn print_progress(progress: &BatchProgress) {
+    match progress {
+        BatchProgress::Processed { path, done, total } => {
+            println!("[{done}/{total}] {}", path.display());
+        }
+        BatchProgress::Skipped { path, done, total } => {
+            println!(
+                "[{done}/{total}] skipped (already processed): {}",
+                path.display()
+            );
+        }
+        BatchProgress::Failed {
+            path,
+            error,
+            done,
+            total,
+        } => {
+            eprintln!("[{done}/{total}] failed: {} ({error})", path.display());
+        }
+    }
+}
+
+/// batch 명령 실행
+fn run_batch_command(args: &[String]) -> anyhow::Result<()> {
+    let mut positional = Vec::new();
+    let mut workers = None;
+    let mut extensions = None;
+    let mut overwrite = false;
+    let mut remap = None;
+    let mut orientation = OrientationMode::Apply;
+    let mut skeleton = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--workers" => workers = Some(option_value(args, &mut i, "--workers")?.parse()?),
+            "--ext" => extensions = Some(parse_extensions(option_value(args, &mut i, "--ext")?)),
+            "--overwrite" => overwrite = true,
+            "--raw-orientation" => orientation = OrientationMode::Raw,
+            "--remap" => remap = Some(load_remap(option_value(args, &mut i, "--remap")?)?),
+            "--skeleton" => {
+                skeleton = Some(load_skeleton(option_value(args, &mut i, "--skeleton")?)?)
+            }
+            other if other.starts_with("--") => {
+                return Err(anyhow::anyhow!("알 수 없는 옵션: {other}"));
+            }
+            other => positional.push(PathBuf::from(other)),
+        }
+        i += 1;
+    }
+
+    let [input, output] = positional.as_slice() else {
+        return Err(anyhow::anyhow!("입력/출력 폴더가 필요합니다\n{USAGE}"));
+    };
+
+    let mut config = BatchConfig::new(input, output);
+    if let Some(workers) = workers {
+        config.workers = workers;
+    }
+    if let Some(extensions) = extensions {
+        config.extensions = extensions;
+    }
+    config.overwrite = overwrite;
+    config.remap = remap;
+    config.orientation = orientation;
+    config.skeleton = skeleton;
+
+    println!(
+        "Batch: {} -> {} (workers: {})",
+        config.input_dir.display(),
+        config.output_dir.display(),
+        config.workers
+    );
+
+    let report = run_batch(&config, print_progress)?;
+
+    println!(
+        "Done: {} processed, {} skipped, {} failed (total {})",
+        report.processed,
+        report.skipped,
+        report.failed.len(),
+        report.total
+    );
+    for (path, error) in &report.failed {
+        eprintln!("  {}: {error}", path.display());
+    }
+
+    if report.failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "{}개 이미지 처리 실패",
+            report.failed.len()
+        ))
+    }
+}
+
+/// crops 명령 실행 (검출 영역을 클래스별 폴더에 저장)
+fn run_crops_command(args: &[String]) -> anyhow::Result<()> {
+    let mut positional = Vec::new();
+    let mut padding = None;
+    let mut min_size = None;
+    let mut threshold = None;
+    let mut extensions = None;
+    let mut remap = None;
+    let mut orientation = OrientationMode::Apply;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--padding" => padding = Some(option_value(args, &mut i, "--padding")?.parse()?),
+            "--min-size" => min_size = Some(option_value(args, &mut i, "--min-size")?.parse()?),
+            "--threshold" => threshold = Some(option_value(args, &mut i, "--threshold")?.parse()?),
+            "--ext" => extensions = Some(parse_extensions(option_value(args, &mut i, "--ext")?)),
+            "--remap" => remap = Some(load_remap(option_value(args, &mut i, "--remap")?)?),
+            "--raw-orientation" => orientation = OrientationMode::Raw,
+            other if other.starts_with("--") => {
+                return Err(anyhow::anyhow!("알 수 없는 옵션: {other}"));
+            }
+            other => positional.push(PathBuf::from(other)),
+        }
+        i += 1;
+    }
+
+    let [input, output] = positional.as_slice() else {
+        return Err(anyhow::anyhow!("입력/출력 폴더가 필요합니다\n{USAGE}"));
+    };
+
+    let mut config = CropConfig::new(input, output);
+    if let Some(padding) = padding {
+        config.padding = padding;
+    }
+    if let Some(min_size) = min_size {
+        config.min_size = min_size;
+    }
+    if let Some(threshold) = threshold {
+        config.confidence_threshold = threshold;
+    }
+    if let Some(extensions) = extensions {
+        config.extensions = extensions;
+    }
+    config.remap = remap;
+    config.orientation = orientation;
+
+    println!(
+        "Crops: {} -> {} (padding: {}, threshold: {})",
+        config.input_dir.display(),
+        config.output_dir.display(),
+        config.padding,
+        config.confidence_threshold
+    );
+
+    let (report, crops) = export_crops(&config, print_progress)?;
+
+    println!(
+        "Done: {} crops from {} images, {} failed (total {})",
+        crops,
+        report.processed,
+        report.failed.len(),
+        report.total
+    );
+
+    if report.failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "{}개 이미지 처리 실패",
+            report.failed.len()
+        ))
+    }
+}
+
+/// --color 값 파싱 (`R,G,B`)
+fn parse_color(value: &str) -> anyhow::Result<[u8; 3]> {
+    let channels = value
+        .split(',')
+        .map(|c| c.trim().parse::<u8>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| anyhow::anyhow!("잘못된 색상: {value} (예: 0,0,0)"))?;
+    <[u8; 3]>::try_from(channels).map_err(|_| anyhow::anyhow!("잘못된 색상: {value} (예: 0,0,0)"))
+}
+
+/// redact 명령 실행 (입력이 폴더면 폴더 단위 처리)
+fn run_redact_command(args: &[String]) -> anyhow::Result<()> {
+    let mut positional = Vec::new();
+    let mut mode = "blur".to_string();
+    let mut strength = None;
+    let mut color = None;
+    let mut redaction = RedactionConfig::default();
+    let mut threshold = CONFIDENCE_THRESHOLD;
+    let mut extensions = None;
+    let mut remap = None;
+    let mut orientation = OrientationMode::Apply;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--mode" => mode = option_value(args, &mut i, "--mode")?.to_string(),
+            "--classes" => {
+                redaction.classes = option_value(args, &mut i, "--classes")?
+                    .split(',')
+                    .map(|c| c.trim().to_string())
+                    .filter(|c| !c.is_empty())
+                    .collect();
+            }
+            "--margin" => redaction.margin = option_value(args, &mut i, "--margin")?.parse()?,
+            "--strength" => strength = Some(option_value(args, &mut i, "--strength")?.parse()?),
+            "--color" => color = Some(parse_color(option_value(args, &mut i, "--color")?)?),
+            "--threshold" => threshold = option_value(args, &mut i, "--threshold")?.parse()?,
+            "--ext" => extensions = Some(parse_extensions(option_value(args, &mut i, "--ext")?)),
+            "--remap" => remap = Some(load_remap(option_value(args, &mut i, "--remap")?)?),
+            "--raw-orientation" => orientation = OrientationMode::Raw,
+            other if other.starts_with("--") => {
+                return Err(anyhow::anyhow!("알 수 없는 옵션: {other}"));
+            }
+            other => positional.push(PathBuf::from(other)),
+        }
+        i += 1;
+    }
+
+    let [input, output] = positional.as_slice() else {
+        return Err(anyhow::anyhow!("입력/출력 경로가 필요합니다\n{USAGE}"));
+    };
+    redaction.mode = RedactionMode::from_name(&mode, strength)?;
+    if let (RedactionMode::Fill { color: fill }, Some(color)) = (&mut redaction.mode, color) {
+        *fill = color;
+    }
+
+    if !input.is_dir() {
+        let mut cache = ModelCache::new()?;
+        let redacted = redact_image_file(
+            input,
+            output,
+            &mut cache,
+            threshold,
+            remap.as_ref(),
+            orientation,
+            &redaction,
+        )?;
+        println!("Redacted {} region(s): {}", redacted, output.display());
+        return Ok(());
+    }
+
+    let mut config = RedactBatchConfig::new(input, output);
+    config.confidence_threshold = threshold;
+    config.remap = remap;
+    config.orientation = orientation;
+    config.redaction = redaction;
+    if let Some(extensions) = extensions {
+        config.extensions = extensions;
+    }
+
+    println!(
+        "Redact: {} -> {} (mode: {}, margin: {})",
+        config.input_dir.display(),
+        config.output_dir.display(),
+        mode,
+        config.redaction.margin
+    );
+
+    let (report, regions) = redact_folder(&config, print_progress)?;
+
+    println!(
+        "Done: {} regions in {} images, {} failed (total {})",
+        regions,
+        report.processed,
+        report.failed.len(),
+        report.total
+    );
+
+    if report.failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "{}개 이미지 처리 실패",
+            report.failed.len()
+        ))
+    }
+}
+
+/// inspect 명령 실행
+fn run_inspect_command(args: &[String]) -> anyhow::Result<()> {
+    let mut json = false;
+    let mut source = ModelSource::Embedded;
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            other if other.starts_with("--") => {
+                return Err(anyhow::anyhow!("알 수 없는 옵션: {other}"))
+            }
+            path if source == ModelSource::Embedded => {
+                source = ModelSource::File(PathBuf::from(path));
+            }
+            other => {
+                return Err(anyhow::anyhow!(
+                    "모델 경로는 하나만 지정할 수 있습니다: {other}"
+                ))
+            }
+        }
+    }
+
+    if let ModelSource::File(path) = &source {
+        if !path.is_file() {
+            return Err(anyhow::anyhow!(
+                "모델 파일을 찾을 수 없습니다: {}",
+                path.display()
+            ));
+        }
+    }
+    let mut cache = ModelCache::with_source(source)?;
+    let info = cache.info()?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+    } else {
+        print!("{}", info.describe());
+    }
+    Ok(())
+}
@@ -0,0 +1,198 @@
+use crate::{iou, Detection};
+use serde::Serialize;
+
+// 상수 정의
+pub const DEFAULT_MATCH_IOU: f32 = 0.5;
+
+/// IoU로 짝지어진 두 모델의 검출 쌍
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct MatchedPair {
+    /// 기준 모델(A) 검출 인덱스
+    pub reference: usize,
+    /// 비교 모델(B) 검출 인덱스
+    pub candidate: usize,
+    pub iou: f32,
+    /// 두 검출의 클래스가 같은지 여부
+    pub same_class: bool,
+    /// 신뢰도 차이 (B - A)
+    pub confidence_delta: f32,
+}
+
+/// 두 모델 검출 결과의 차이
+///
+/// - `matched`: 양쪽 모두 검출
+/// - `missing`: A만 검출 (B가 놓침)
+/// - `extra`: B만 검출 (A에 없음)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DetectionDiff {
+    pub matched: Vec<MatchedPair>,
+    pub missing: Vec<usize>,
+    pub extra: Vec<usize>,
+}
+
+impl DetectionDiff {
+    /// 짝지어진 쌍의 평균 IoU
+    pub fn mean_iou(&self) -> Option<f32> {
+        if self.matched.is_empty() {
+            return None;
+        }
+        Some(self.matched.iter().map(|m| m.iou).sum::<f32>() / self.matched.len() as f32)
+    }
+
+    /// 짝지어졌지만 클래스가 다른 쌍의 수
+    pub fn class_mismatches(&self) -> usize {
+        self.matched.iter().filter(|m| !m.same_class).count()
+    }
+
+    /// 기준 검출이 짝지어진 비교 검출 인덱스
+    pub fn match_for_reference(&self, index: usize) -> Option<&MatchedPair> {
+        self.matched.iter().find(|m| m.reference == index)
+    }
+
+    /// 비교 검출이 짝지어진 기준 검출 인덱스
+    pub fn match_for_candidate(&self, index: usize) -> Option<&MatchedPair> {
+        self.matched.iter().find(|m| m.candidate == index)
+    }
+}
+
+/// IoU 기반 탐욕적 1:1 매칭으로 두 검출 결과 비교
+///
+/// IoU가 높은 쌍부터 짝지으며, `require_same_class`가 true이면 같은 클래스끼리만 매칭합니다.
+pub fn diff_detections(
+    reference: &[Detection],
+    candidate: &[Detection],
+    iou_threshold: f32,
+    require_same_class: bool,
+) -> DetectionDiff {
+    let mut pairs: Vec<(usize, usize, f32)> = Vec::new();
+    for (i, a) in reference.iter().enumerate() {
+        for (j, b) in candidate.iter().enumerate() {
+            if require_same_class && a.class_id != b.class_id {
+                continue;
+            }
+            let overlap = iou(&a.bbox, &b.bbox);
+            if overlap >= iou_threshold {
+                pairs.push((i, j, overlap));
+            }
+        }
+    }
+    pairs.sort_by(|x, y| y.2.total_cmp(&x.2));
+
+    let mut reference_used = vec![false; reference.len()];
+    let mut candidate_used = vec![false; candidate.len()];
+    let mut diff = DetectionDiff::default();
+
+    for (i, j, overlap) in pairs {
+        if reference_used[i] || candidate_used[j] {
+            continue;
+        }
+        reference_used[i] = true;
+        candidate_used[j] = true;
+        diff.matched.push(MatchedPair {
+            reference: i,
+            candidate: j,
+            iou: overlap,
+            same_class: reference[i].class_id == candidate[j].class_id,
+            confidence_delta: candidate[j].confidence - reference[i].confidence,
+        });
+    }
+
+    diff.matched.sort_by_key(|m| m.reference);
+    diff.missing = (0..reference.len())
+        .filter(|i| !reference_used[*i])
+        .collect();
+    diff.extra = (0..candidate.len())
+        .filter(|j| !candidate_used[*j])
+        .collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detection(bbox: [f32; 4], confidence: f32, class_id: u32) -> Detection {
+        Detection {
+            bbox,
+            confidence,
+            class_id,
+            class_name: format!("class {}", class_id),
+            mask: None,
+            keypoints: None,
+            obb: None,
+            secondary: Vec::new(),
+            original_class_id: None,
+        }
+    }
+
+    #[test]
+    fn splits_matched_missing_and_extra() {
+        let reference = vec![
+            detection([0.0, 0.0, 0.2, 0.2], 0.9, 1),
+            detection([0.5, 0.5, 0.7, 0.7], 0.8, 1),
+        ];
+        let candidate = vec![
+            detection([0.8, 0.0, 1.0, 0.2], 0.7, 1),
+            detection([0.0, 0.0, 0.2, 0.2], 0.6, 1),
+        ];
+        let diff = diff_detections(&reference, &candidate, DEFAULT_MATCH_IOU, true);
+
+        assert_eq!(diff.matched.len(), 1);
+        let pair = diff.matched[0];
+        assert_eq!((pair.reference, pair.candidate), (0, 1));
+        assert!((pair.iou - 1.0).abs() < 1e-6);
+        assert!(pair.same_class);
+        assert!((pair.confidence_delta + 0.3).abs() < 1e-6);
+        assert_eq!(diff.missing, vec![1]);
+        assert_eq!(diff.extra, vec![0]);
+        assert_eq!(diff.match_for_candidate(1), Some(&pair));
+        assert_eq!(diff.match_for_reference(1), None);
+    }
+
+    #[test]
+    fn greedy_matching_is_one_to_one_by_highest_iou() {
+        let reference = vec![
+            detection([0.0, 0.0, 0.5, 0.5], 0.9, 1),
+            detection([0.05, 0.0, 0.55, 0.5], 0.9, 1),
+        ];
+        // 두 기준 검출 모두와 임계값 이상 겹치지만 IoU가 더 높은 쪽에만 매칭
+        let candidate = vec![detection([0.05, 0.0, 0.55, 0.5], 0.9, 1)];
+        let diff = diff_detections(&reference, &candidate, DEFAULT_MATCH_IOU, true);
+
+        assert_eq!(diff.matched.len(), 1);
+        assert_eq!(diff.matched[0].reference, 1);
+        assert_eq!(diff.missing, vec![0]);
+        assert!(diff.extra.is_empty());
+    }
+
+    #[test]
+    fn class_mismatch_depends_on_require_same_class() {
+        let reference = vec![detection([0.1, 0.1, 0.4, 0.4], 0.9, 1)];
+        let candidate = vec![detection([0.1, 0.1, 0.4, 0.4], 0.8, 2)];
+
+        let strict = diff_detections(&reference, &candidate, DEFAULT_MATCH_IOU, true);
+        assert!(strict.matched.is_empty());
+        assert_eq!(strict.missing, vec![0]);
+        assert_eq!(strict.extra, vec![0]);
+        assert_eq!(strict.mean_iou(), None);
+
+        let loose = diff_detections(&reference, &candidate, DEFAULT_MATCH_IOU, false);
+        assert_eq!(loose.matched.len(), 1);
+        assert!(!loose.matched[0].same_class);
+        assert_eq!(loose.class_mismatches(), 1);
+        assert!(loose.missing.is_empty() && loose.extra.is_empty());
+    }
+
+    #[test]
+    fn pairs_below_threshold_are_not_matched() {
+        let reference = vec![detection([0.0, 0.0, 0.5, 0.5], 0.9, 1)];
+        // IoU = 0.2 / 0.3
+        let candidate = vec![detection([0.1, 0.0, 0.6, 0.5], 0.9, 1)];
+
+        let diff = diff_detections(&reference, &candidate, 0.7, true);
+        assert!(diff.matched.is_empty());
+
+        let diff = diff_detections(&reference, &candidate, 0.6, true);
+        assert!((diff.mean_iou().unwrap() - 2.0 / 3.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,257 @@
+use crate::batch::{run_folder, BatchProgress, BatchReport, DEFAULT_EXTENSIONS};
+use crate::{
+    decode_image_with_orientation, run_inference_with_threshold, ClassRemap, Detection,
+    OrientationMode, CONFIDENCE_THRESHOLD, MODEL_INPUT_SIZE,
+};
+use image::RgbImage;
+use std::path::{Path, PathBuf};
+
+// 상수 정의
+/// 기본 패딩 (박스 너비/높이 대비 한쪽 여백 비율)
+pub const DEFAULT_CROP_PADDING: f32 = 0.1;
+/// 이보다 작은 크롭(픽셀, 짧은 변)은 저장하지 않음
+pub const DEFAULT_MIN_CROP_SIZE: u32 = 8;
+
+/// 크롭 내보내기 설정
+#[derive(Debug, Clone)]
+pub struct CropConfig {
+    pub input_dir: PathBuf,
+    /// 클래스별 하위 폴더가 만들어질 출력 폴더
+    pub output_dir: PathBuf,
+    /// 처리할 확장자 (소문자, 점 제외)
+    pub extensions: Vec<String>,
+    /// 박스 너비/높이 대비 한쪽 여백 비율 (0이면 박스 그대로)
+    pub padding: f32,
+    pub min_size: u32,
+    pub confidence_threshold: f32,
+    /// 클래스 재매핑 (대상 클래스 이름으로 폴더 생성)
+    pub remap: Option<ClassRemap>,
+    /// EXIF 방향 처리 방식 (기본: 적용)
+    pub orientation: OrientationMode,
+}
+
+impl CropConfig {
+    pub fn new(input_dir: impl Into<PathBuf>, output_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            input_dir: input_dir.into(),
+            output_dir: output_dir.into(),
+            extensions: DEFAULT_EXTENSIONS.iter().map(|e| e.to_string()).collect(),
+            padding: DEFAULT_CROP_PADDING,
+            min_size: DEFAULT_MIN_CROP_SIZE,
+            confidence_threshold: CONFIDENCE_THRESHOLD,
+            remap: None,
+            orientation: OrientationMode::Apply,
+        }
+    }
+}
+
+/// 정규화 박스에 패딩을 더한 픽셀 영역 (x, y, 너비, 높이), 이미지 밖은 잘라냄
+///
+/// 잘라낸 결과가 비어 있으면 None을 반환합니다.
+pub fn crop_rect(
+    bbox: &[f32; 4],
+    width: u32,
+    height: u32,
+    padding: f32,
+) -> Option<(u32, u32, u32, u32)> {
+    let (w, h) = (width as f32, height as f32);
+    let pad_x = (bbox[2] - bbox[0]).max(0.0) * padding.max(0.0);
+    let pad_y = (bbox[3] - bbox[1]).max(0.0) * padding.max(0.0);
+    let x1 = ((bbox[0] - pad_x) * w).floor().clamp(0.0, w) as u32;
+    let y1 = ((bbox[1] - pad_y) * h).floor().clamp(0.0, h) as u32;
+    let x2 = ((bbox[2] + pad_x) * w).ceil().clamp(0.0, w) as u32;
+    let y2 = ((bbox[3] + pad_y) * h).ceil().clamp(0.0, h) as u32;
+    (x2 > x1 && y2 > y1).then(|| (x1, y1, x2 - x1, y2 - y1))
+}
+
+impl Detection {
+    /// 원본 이미지에서 검출 영역 잘라내기 (`padding`은 박스 크기 대비 한쪽 여백 비율)
+    pub fn crop(&self, image: &RgbImage, padding: f32) -> Option<RgbImage> {
+        let (x, y, w, h) = crop_rect(&self.bbox, image.width(), image.height(), padding)?;
+        Some(image::imageops::crop_imm(image, x, y, w, h).to_image())
+    }
+}
+
+/// 폴더 이름으로 쓸 수 있도록 클래스 이름 정리 (경로 구분자 등은 `_`로 대체)
+pub fn class_dir_name(class_name: &str) -> String {
+    let name: String = class_name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.is_empty() {
+        "unknown".to_string()
+    } else {
+        name
+    }
+}
+
+/// 검출 결과를 `<출력 폴더>/<클래스>/<이름>_<번호>.png`로 저장 (저장한 크롭 수 반환)
+///
+/// `name`에 하위 폴더가 있으면 클래스 폴더 안에 같은 구조로 만듭니다.
+pub fn save_crops(
+    image: &RgbImage,
+    detections: &[Detection],
+    output_dir: &Path,
+    name: &Path,
+    padding: f32,
+    min_size: u32,
+) -> anyhow::Result<usize> {
+    let mut saved = 0;
+    for (i, detection) in detections.iter().enumerate() {
+        let Some(crop) = detection.crop(image, padding) else {
+            continue;
+        };
+        if crop.width().min(crop.height()) < min_size {
+            continue;
+        }
+        let mut file_name = name.as_os_str().to_os_string();
+        file_name.push(format!("_{:03}.png", i + 1));
+        let path = output_dir
+            .join(class_dir_name(&detection.class_name))
+            .join(file_name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        crop.save(path)?;
+        saved += 1;
+    }
+    Ok(saved)
+}
+
+/// 입력 경로를 크롭 이름으로 변환 (입력 폴더 기준 상대 경로, 확장자 유지)
+///
+/// 클래스 폴더 안에 입력과 같은 하위 폴더 구조로 저장하므로 `a.jpg`와 `a.png`,
+/// `a/b.jpg`와 `a_b.jpg`의 크롭이 서로 덮어쓰지 않습니다.
+fn crop_name(config: &CropConfig, input: &Path) -> PathBuf {
+    match input.strip_prefix(&config.input_dir) {
+        Ok(relative) => relative.to_path_buf(),
+        Err(_) => PathBuf::from(input.file_name().unwrap_or_default()),
+    }
+}
+
+/// 폴더의 모든 이미지를 검출하여 클래스별 크롭 저장 (분류 데이터셋 구축용, 저장한 크롭 수 함께 반환)
+pub fn export_crops(
+    config: &CropConfig,
+    on_progress: impl FnMut(&BatchProgress),
+) -> anyhow::Result<(BatchReport, usize)> {
+    run_folder(
+        &config.input_dir,
+        &config.output_dir,
+        &config.extensions,
+        on_progress,
+        |path, cache| {
+            let image = decode_image_with_orientation(&std::fs::read(path)?, config.orientation)?;
+            let (mut detections, _) = run_inference_with_threshold(
+                &image,
+                cache,
+                MODEL_INPUT_SIZE,
+                config.confidence_threshold,
+            )?;
+            if let Some(remap) = &config.remap {
+                remap.apply(&mut detections);
+            }
+            save_crops(
+                &image,
+                &detections,
+                &config.output_dir,
+                &crop_name(config, path),
+                config.padding,
+                config.min_size,
+            )
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detection(class_name: &str, bbox: [f32; 4]) -> Detection {
+        Detection {
+            bbox,
+            confidence: 0.9,
+            class_id: 1,
+            class_name: class_name.to_string(),
+            mask: None,
+            keypoints: None,
+            obb: None,
+            secondary: Vec::new(),
+            original_class_id: None,
+        }
+    }
+
+    /// 테스트마다 비어 있는 임시 폴더
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("rf_detr_crops_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn crop_names_keep_extension_and_directories() {
+        let config = CropConfig::new("/data/in", "/data/out");
+        let names: Vec<PathBuf> = ["a.jpg", "a.png", "a/b.jpg", "a_b.jpg"]
+            .iter()
+            .map(|p| crop_name(&config, &Path::new("/data/in").join(p)))
+            .collect();
+        for (i, a) in names.iter().enumerate() {
+            for b in &names[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+        assert_eq!(names[2], Path::new("a").join("b.jpg"));
+    }
+
+    #[test]
+    fn colliding_stems_do_not_overwrite_crops() {
+        let output = temp_dir("collide");
+        let config = CropConfig::new("/data/in", &output);
+        let image = RgbImage::new(40, 40);
+        let detections = [detection("person", [0.0, 0.0, 0.5, 0.5])];
+        for input in ["a.jpg", "a.png", "a/b.jpg", "a_b.jpg"] {
+            let name = crop_name(&config, &Path::new("/data/in").join(input));
+            assert_eq!(
+                save_crops(&image, &detections, &output, &name, 0.0, 1).unwrap(),
+                1
+            );
+        }
+
+        let class_dir = output.join("person");
+        assert!(class_dir.join("a.jpg_001.png").is_file());
+        assert!(class_dir.join("a.png_001.png").is_file());
+        assert!(class_dir.join("a_b.jpg_001.png").is_file());
+        assert!(class_dir.join("a").join("b.jpg_001.png").is_file());
+        std::fs::remove_dir_all(&output).unwrap();
+    }
+
+    #[test]
+    fn crop_rect_pads_and_clips_to_image() {
+        // 50% 패딩: 16x16 픽셀 박스 → 양쪽 8픽셀씩, 왼쪽/위는 이미지 경계에서 잘림
+        assert_eq!(
+            crop_rect(&[0.0, 0.0, 0.25, 0.25], 64, 64, 0.5),
+            Some((0, 0, 24, 24))
+        );
+        assert_eq!(
+            crop_rect(&[0.25, 0.25, 0.5, 0.5], 64, 64, 0.5),
+            Some((8, 8, 32, 32))
+        );
+        // 이미지 밖 박스는 None
+        assert_eq!(crop_rect(&[1.2, 1.2, 1.5, 1.5], 100, 100, 0.0), None);
+    }
+
+    #[test]
+    fn class_dir_name_replaces_separators() {
+        assert_eq!(class_dir_name("traffic light"), "traffic_light");
+        assert_eq!(class_dir_name("../etc"), "___etc");
+        assert_eq!(class_dir_name("  "), "unknown");
+    }
+}
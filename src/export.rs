@@ -0,0 +1,598 @@
+use crate::batch::ImageDetections;
+use crate::keypoints::DEFAULT_MIN_VISIBILITY;
+use crate::obb;
+use crate::{class_label_map, rf_detr_id_to_label, Detection, Keypoint, Skeleton};
+use serde_json::json;
+use std::fmt::Write as _;
+use std::path::Path;
+
+// 상수 정의
+/// YOLO 클래스 이름 파일 (라벨 파일과 같은 폴더)
+pub const YOLO_CLASSES_FILE: &str = "classes.txt";
+
+/// 검출 결과 내보내기 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// 이미지별 JSON (batch 출력과 동일한 형식)
+    Json,
+    /// 검출 하나당 한 줄인 CSV
+    Csv,
+    /// COCO detection 결과 형식
+    Coco,
+    /// YOLO 라벨 (cx cy w h, 정규화 좌표)
+    Yolo,
+    /// DOTA 라벨 (회전 박스 네 꼭짓점, 픽셀 좌표)
+    Dota,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 5] = [
+        ExportFormat::Json,
+        ExportFormat::Csv,
+        ExportFormat::Coco,
+        ExportFormat::Yolo,
+        ExportFormat::Dota,
+    ];
+
+    /// 표시용 이름
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "JSON",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Coco => "COCO",
+            ExportFormat::Yolo => "YOLO",
+            ExportFormat::Dota => "DOTA",
+        }
+    }
+
+    /// 기본 파일 확장자
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json | ExportFormat::Coco => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Yolo | ExportFormat::Dota => "txt",
+        }
+    }
+
+    /// 이미지 파일 이름(확장자 제외) 기준 기본 저장 이름
+    pub fn default_file_name(&self, stem: &str) -> String {
+        match self {
+            ExportFormat::Coco => format!("{stem}_coco.json"),
+            ExportFormat::Dota => format!("{stem}_dota.txt"),
+            _ => format!("{stem}.{}", self.extension()),
+        }
+    }
+}
+
+/// 정규화 bbox를 픽셀 [x, y, w, h]로 변환
+fn bbox_to_xywh_pixels(bbox: &[f32; 4], width: u32, height: u32) -> [f32; 4] {
+    let x1 = bbox[0] * width as f32;
+    let y1 = bbox[1] * height as f32;
+    let x2 = bbox[2] * width as f32;
+    let y2 = bbox[3] * height as f32;
+    [x1, y1, x2 - x1, y2 - y1]
+}
+
+/// 이미지별 JSON
+pub fn to_json(
+    image_name: &str,
+    width: u32,
+    height: u32,
+    inference_time_ms: f64,
+    detections: &[Detection],
+) -> anyhow::Result<String> {
+    let record = ImageDetections {
+        image: image_name.to_string(),
+        width,
+        height,
+        inference_time_ms,
+        detections: detections.to_vec(),
+    };
+    Ok(serde_json::to_string_pretty(&record)?)
+}
+
+/// CSV (정규화 좌표와 픽셀 좌표 모두 포함)
+pub fn to_csv(width: u32, height: u32, detections: &[Detection]) -> String {
+    let mut out = String::from(
+        "class_id,class_name,confidence,x1,y1,x2,y2,x1_px,y1_px,x2_px,y2_px,original_class_id\n",
+    );
+    for d in detections {
+        let [x1, y1, x2, y2] = d.bbox;
+        let original = d
+            .original_class_id
+            .map(|id| id.to_string())
+            .unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "{},\"{}\",{:.4},{:.4},{:.4},{:.4},{:.4},{:.0},{:.0},{:.0},{:.0},{}",
+            d.class_id,
+            d.class_name.replace('"', "\"\""),
+            d.confidence,
+            x1,
+            y1,
+            x2,
+            y2,
+            x1 * width as f32,
+            y1 * height as f32,
+            x2 * width as f32,
+            y2 * height as f32,
+            original
+        );
+    }
+    out
+}
+
+/// COCO 카테고리 목록 (RF-DETR 클래스 ID 그대로 사용)
+pub fn coco_categories() -> Vec<serde_json::Value> {
+    class_label_map()
+        .into_iter()
+        .map(|(id, name)| json!({ "id": id, "name": name }))
+        .collect()
+}
+
+/// COCO 키포인트 배열 ([x, y, v, ...] 픽셀 좌표, v는 보이면 2 아니면 1)
+pub fn coco_keypoints(keypoints: &[Keypoint], width: u32, height: u32) -> Vec<serde_json::Value> {
+    keypoints
+        .iter()
+        .flat_map(|k| {
+            let v = if k.visibility >= DEFAULT_MIN_VISIBILITY {
+                2
+            } else {
+                1
+            };
+            [
+                json!(k.x * width as f32),
+                json!(k.y * height as f32),
+                json!(v),
+            ]
+        })
+        .collect()
+}
+
+/// COCO 형식 (단일 이미지 데이터셋)
+///
+/// 키포인트가 있으면 annotation에 `keypoints`/`num_keypoints`를, 해당 카테고리에
+/// 키포인트 이름과 스켈레톤(1부터 시작하는 인덱스)을 추가합니다.
+pub fn to_coco(
+    file_name: &str,
+    width: u32,
+    height: u32,
+    detections: &[Detection],
+) -> serde_json::Value {
+    to_coco_with_classes(file_name, width, height, detections, None, None)
+}
+
+/// 지정한 클래스 목록(ID, 이름)을 카테고리로 쓰는 COCO 형식 (None이면 RF-DETR 클래스)
+///
+/// 재매핑된 검출은 annotation에 `original_category_id`를 함께 기록합니다.
+/// 키포인트 카테고리의 이름/연결선은 `skeleton`(키포인트 수가 다르면 기본 스켈레톤)을 따릅니다.
+pub fn to_coco_with_classes(
+    file_name: &str,
+    width: u32,
+    height: u32,
+    detections: &[Detection],
+    classes: Option<&[(u32, String)]>,
+    skeleton: Option<&Skeleton>,
+) -> serde_json::Value {
+    let annotations: Vec<serde_json::Value> = detections
+        .iter()
+        .enumerate()
+        .map(|(i, d)| {
+            let [x, y, w, h] = bbox_to_xywh_pixels(&d.bbox, width, height);
+            let mut annotation = json!({
+                "id": i + 1,
+                "image_id": 1,
+                "category_id": d.class_id,
+                "bbox": [x, y, w, h],
+                "area": w * h,
+                "iscrowd": 0,
+                "score": d.confidence,
+            });
+            // 마스크가 있으면 RLE 세그멘테이션과 마스크 면적 기록
+            if let Some(mask) = &d.mask {
+                annotation["segmentation"] = json!(mask.to_rle());
+                annotation["area"] = json!(mask.area());
+            }
+            if let Some(keypoints) = &d.keypoints {
+                annotation["keypoints"] = json!(coco_keypoints(keypoints, width, height));
+                annotation["num_keypoints"] = json!(keypoints
+                    .iter()
+                    .filter(|k| k.visibility >= DEFAULT_MIN_VISIBILITY)
+                    .count());
+            }
+            if let Some(original) = d.original_class_id {
+                annotation["original_category_id"] = json!(original);
+            }
+            annotation
+        })
+        .collect();
+
+    let mut categories = match classes {
+        Some(classes) => classes
+            .iter()
+            .map(|(id, name)| json!({ "id": id, "name": name }))
+            .collect(),
+        None => coco_categories(),
+    };
+    for d in detections {
+        let Some(keypoints) = &d.keypoints else {
+            continue;
+        };
+        let Some(category) = categories.iter_mut().find(|c| c["id"] == d.class_id) else {
+            continue;
+        };
+        if category.get("keypoints").is_none() {
+            let skeleton = Skeleton::resolve(skeleton, keypoints.len());
+            category["keypoints"] = json!(skeleton.names);
+            category["skeleton"] = json!(skeleton
+                .edges
+                .iter()
+                .map(|(a, b)| [a + 1, b + 1])
+                .collect::<Vec<_>>());
+        }
+    }
+
+    json!({
+        "images": [{ "id": 1, "file_name": file_name, "width": width, "height": height }],
+        "annotations": annotations,
+        "categories": categories,
+    })
+}
+
+/// YOLO 클래스 이름 목록 (0부터 시작하는 연속 인덱스 순서)
+pub fn yolo_class_names() -> Vec<&'static str> {
+    class_label_map()
+        .into_iter()
+        .map(|(_, name)| name)
+        .collect()
+}
+
+/// RF-DETR 클래스 ID를 YOLO 연속 인덱스로 변환
+pub fn yolo_class_index(class_id: u32) -> Option<usize> {
+    rf_detr_id_to_label(class_id)?;
+    Some(
+        (1..class_id)
+            .filter(|id| rf_detr_id_to_label(*id).is_some())
+            .count(),
+    )
+}
+
+/// YOLO 라벨 (class cx cy w h)
+pub fn to_yolo(detections: &[Detection]) -> String {
+    to_yolo_with_classes(detections, None)
+}
+
+/// 지정한 클래스 목록 순서를 YOLO 인덱스로 쓰는 YOLO 라벨 (None이면 RF-DETR 클래스)
+pub fn to_yolo_with_classes(detections: &[Detection], classes: Option<&[(u32, String)]>) -> String {
+    let mut out = String::new();
+    for d in detections {
+        let index = match classes {
+            Some(classes) => classes.iter().position(|(id, _)| *id == d.class_id),
+            None => yolo_class_index(d.class_id),
+        };
+        let Some(index) = index else {
+            continue;
+        };
+        let [x1, y1, x2, y2] = d.bbox;
+        let _ = writeln!(
+            out,
+            "{} {:.6} {:.6} {:.6} {:.6}",
+            index,
+            (x1 + x2) / 2.0,
+            (y1 + y2) / 2.0,
+            x2 - x1,
+            y2 - y1
+        );
+    }
+    out
+}
+
+/// YOLO `classes.txt` 내용 (인덱스 순서로 한 줄에 하나)
+pub fn yolo_classes_txt(classes: Option<&[(u32, String)]>) -> String {
+    let names = match classes {
+        Some(classes) => classes.iter().map(|(_, name)| name.as_str()).collect(),
+        None => yolo_class_names(),
+    };
+    names.join("\n") + "\n"
+}
+
+/// 폴더에 이번 내보내기와 다른 클래스 목록의 `classes.txt`가 이미 있는지 확인
+pub fn yolo_classes_conflict(dir: &Path, classes: Option<&[(u32, String)]>) -> bool {
+    std::fs::read_to_string(dir.join(YOLO_CLASSES_FILE))
+        .is_ok_and(|existing| existing != yolo_classes_txt(classes))
+}
+
+/// 지정한 형식으로 파일 저장
+///
+/// YOLO 형식은 같은 폴더에 `classes.txt`도 함께 기록합니다 (내용이 같으면 다시 쓰지 않음).
+/// 다른 내용의 파일을 덮어쓰기 전에 확인하려면 `yolo_classes_conflict`를 사용하세요.
+pub fn export_detections(
+    format: ExportFormat,
+    path: &Path,
+    image_name: &str,
+    width: u32,
+    height: u32,
+    inference_time_ms: f64,
+    detections: &[Detection],
+) -> anyhow::Result<()> {
+    export_detections_with_classes(
+        format,
+        path,
+        image_name,
+        width,
+        height,
+        inference_time_ms,
+        detections,
+        None,
+        None,
+    )
+}
+
+/// 클래스 목록(예: `ClassRemap::categories`)과 키포인트 스켈레톤을 지정한 `export_detections`
+///
+/// COCO 카테고리와 YOLO 인덱스/`classes.txt`에 RF-DETR 클래스 대신 이 목록을 사용하고,
+/// COCO 키포인트 카테고리에는 스켈레톤의 이름/연결선을 기록합니다.
+#[allow(clippy::too_many_arguments)]
+pub fn export_detections_with_classes(
+    format: ExportFormat,
+    path: &Path,
+    image_name: &str,
+    width: u32,
+    height: u32,
+    inference_time_ms: f64,
+    detections: &[Detection],
+    classes: Option<&[(u32, String)]>,
+    skeleton: Option<&Skeleton>,
+) -> anyhow::Result<()> {
+    let contents = match format {
+        ExportFormat::Json => to_json(image_name, width, height, inference_time_ms, detections)?,
+        ExportFormat::Csv => to_csv(width, height, detections),
+        ExportFormat::Coco => serde_json::to_string_pretty(&to_coco_with_classes(
+            image_name, width, height, detections, classes, skeleton,
+        ))?,
+        ExportFormat::Yolo => {
+            if let Some(dir) = path.parent() {
+                let names = yolo_classes_txt(classes);
+                let classes_path = dir.join(YOLO_CLASSES_FILE);
+                if std::fs::read_to_string(&classes_path).ok().as_deref() != Some(names.as_str()) {
+                    std::fs::write(classes_path, names)?;
+                }
+            }
+            to_yolo_with_classes(detections, classes)
+        }
+        ExportFormat::Dota => obb::to_dota(detections, width, height),
+    };
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // 200x100 이미지에서 정확히 표현되는 좌표
+    const WIDTH: u32 = 200;
+    const HEIGHT: u32 = 100;
+
+    fn detection(bbox: [f32; 4], class_id: u32, class_name: &str) -> Detection {
+        Detection {
+            bbox,
+            confidence: 0.9,
+            class_id,
+            class_name: class_name.to_string(),
+            mask: None,
+            keypoints: None,
+            obb: None,
+            secondary: Vec::new(),
+            original_class_id: None,
+        }
+    }
+
+    fn person() -> Detection {
+        detection([0.25, 0.5, 0.75, 1.0], 1, "person")
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("rf_detr_export_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn yolo_index_skips_unused_coco_ids() {
+        assert_eq!(yolo_class_index(1), Some(0));
+        assert_eq!(yolo_class_index(11), Some(10));
+        // 12는 COCO에서 비어 있는 ID
+        assert_eq!(yolo_class_index(12), None);
+        assert_eq!(yolo_class_index(13), Some(11));
+        assert_eq!(yolo_class_names()[11], "stop sign");
+    }
+
+    #[test]
+    fn yolo_writes_normalized_center_and_size() {
+        let detections = [
+            person(),
+            detection([0.0, 0.0, 0.5, 0.25], 13, "stop sign"),
+            detection([0.0, 0.0, 0.1, 0.1], 12, "unknown"),
+        ];
+        assert_eq!(
+            to_yolo(&detections),
+            "0 0.500000 0.750000 0.500000 0.500000\n11 0.250000 0.125000 0.500000 0.250000\n"
+        );
+    }
+
+    #[test]
+    fn yolo_round_trips_to_the_same_box() {
+        let original = detection([0.123, 0.456, 0.789, 0.901], 3, "car");
+        let line = to_yolo(std::slice::from_ref(&original));
+        let values: Vec<f32> = line
+            .split_whitespace()
+            .map(|v| v.parse().unwrap())
+            .collect();
+        let [class, cx, cy, w, h] = values[..] else {
+            panic!("unexpected YOLO line: {line}");
+        };
+        assert_eq!(yolo_class_names()[class as usize], "car");
+        let bbox = [cx - w / 2.0, cy - h / 2.0, cx + w / 2.0, cy + h / 2.0];
+        for (a, b) in bbox.iter().zip(original.bbox) {
+            assert!((a - b).abs() < 1e-5, "{bbox:?} != {:?}", original.bbox);
+        }
+    }
+
+    #[test]
+    fn yolo_with_classes_uses_list_order() {
+        let classes = vec![(5, "vehicle".to_string()), (1, "person".to_string())];
+        let detections = [person(), detection([0.0, 0.0, 0.5, 0.5], 3, "car")];
+        assert_eq!(
+            to_yolo_with_classes(&detections, Some(&classes)),
+            "1 0.500000 0.750000 0.500000 0.500000\n"
+        );
+        assert_eq!(yolo_classes_txt(Some(&classes)), "vehicle\nperson\n");
+    }
+
+    #[test]
+    fn coco_bbox_is_xywh_in_pixels() {
+        let mut remapped = detection([0.0, 0.0, 0.5, 0.5], 2, "vehicle");
+        remapped.original_class_id = Some(3);
+        let classes = vec![(1, "person".to_string()), (2, "vehicle".to_string())];
+        let coco = to_coco_with_classes(
+            "a.jpg",
+            WIDTH,
+            HEIGHT,
+            &[person(), remapped],
+            Some(&classes),
+            None,
+        );
+
+        let annotation = &coco["annotations"][0];
+        assert_eq!(annotation["bbox"], json!([50.0, 50.0, 100.0, 50.0]));
+        assert_eq!(annotation["area"], json!(5000.0));
+        assert_eq!(annotation["category_id"], json!(1));
+        assert!(annotation.get("original_category_id").is_none());
+        assert_eq!(
+            coco["annotations"][1]["bbox"],
+            json!([0.0, 0.0, 100.0, 50.0])
+        );
+        assert_eq!(coco["annotations"][1]["original_category_id"], json!(3));
+
+        assert_eq!(
+            coco["images"],
+            json!([{ "id": 1, "file_name": "a.jpg", "width": WIDTH, "height": HEIGHT }])
+        );
+        assert_eq!(coco["categories"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            to_coco("a.jpg", WIDTH, HEIGHT, &[])["categories"]
+                .as_array()
+                .unwrap()
+                .len(),
+            class_label_map().len()
+        );
+    }
+
+    #[test]
+    fn coco_keypoints_are_pixels_with_visibility_flags() {
+        let keypoints = [
+            Keypoint {
+                x: 0.5,
+                y: 0.5,
+                visibility: 0.9,
+            },
+            Keypoint {
+                x: 0.25,
+                y: 1.0,
+                visibility: 0.1,
+            },
+        ];
+        assert_eq!(
+            coco_keypoints(&keypoints, WIDTH, HEIGHT),
+            vec![
+                json!(100.0),
+                json!(50.0),
+                json!(2),
+                json!(50.0),
+                json!(100.0),
+                json!(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let detections = vec![person()];
+        let json = to_json("a.jpg", WIDTH, HEIGHT, 12.5, &detections).unwrap();
+        let record: ImageDetections = serde_json::from_str(&json).unwrap();
+        assert_eq!(record.image, "a.jpg");
+        assert_eq!((record.width, record.height), (WIDTH, HEIGHT));
+        assert_eq!(record.inference_time_ms, 12.5);
+        assert_eq!(record.detections, detections);
+    }
+
+    #[test]
+    fn csv_has_normalized_and_pixel_columns() {
+        let mut quoted = detection([0.0, 0.0, 0.5, 0.5], 2, "say \"hi\"");
+        quoted.original_class_id = Some(7);
+        let csv = to_csv(WIDTH, HEIGHT, &[person(), quoted]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "class_id,class_name,confidence,x1,y1,x2,y2,x1_px,y1_px,x2_px,y2_px,original_class_id"
+        );
+        assert_eq!(
+            lines[1],
+            "1,\"person\",0.9000,0.2500,0.5000,0.7500,1.0000,50,50,150,100,"
+        );
+        assert_eq!(
+            lines[2],
+            "2,\"say \"\"hi\"\"\",0.9000,0.0000,0.0000,0.5000,0.5000,0,0,100,50,7"
+        );
+    }
+
+    #[test]
+    fn dota_uses_axis_aligned_corners_without_obb() {
+        let detections = [detection([0.25, 0.5, 0.75, 1.0], 10, "traffic light")];
+        assert_eq!(
+            obb::to_dota(&detections, WIDTH, HEIGHT),
+            "50.0 50.0 150.0 50.0 150.0 100.0 50.0 100.0 traffic-light 0\n"
+        );
+    }
+
+    #[test]
+    fn yolo_export_writes_classes_txt_and_detects_conflicts() {
+        let dir = temp_dir("yolo");
+        let classes = vec![(1, "person".to_string())];
+        assert!(!yolo_classes_conflict(&dir, Some(&classes)));
+
+        let label = dir.join("a.txt");
+        export_detections_with_classes(
+            ExportFormat::Yolo,
+            &label,
+            "a.jpg",
+            WIDTH,
+            HEIGHT,
+            0.0,
+            &[person()],
+            Some(&classes),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&label).unwrap(),
+            "0 0.500000 0.750000 0.500000 0.500000\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join(YOLO_CLASSES_FILE)).unwrap(),
+            "person\n"
+        );
+
+        // 같은 목록은 충돌이 아니고, 다른 목록이나 기본 클래스는 충돌
+        assert!(!yolo_classes_conflict(&dir, Some(&classes)));
+        let other = vec![(1, "human".to_string())];
+        assert!(yolo_classes_conflict(&dir, Some(&other)));
+        assert!(yolo_classes_conflict(&dir, None));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
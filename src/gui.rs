@@ -1,7 +1,41 @@
+mod compare;
+mod crops;
+mod editor;
+mod live;
+mod masks;
+mod settings;
+mod summary;
+mod thumbnails;
+mod viewport;
+mod worker;
+
+use compare::ModelComparison;
+use crops::CropGallery;
+use editor::{AnnotationEditor, CandidateEdits};
 use eframe::egui;
-use rf_detr_onnx_test_lib::{detect_objects_with_cache, Detection, ModelCache};
-use std::fs;
+use live::LiveSession;
+use masks::MaskOverlay;
+use rf_detr_onnx_test_lib::export::yolo_classes_conflict;
+use rf_detr_onnx_test_lib::{
+    draw_detections_with_skeleton, export_detections_with_classes, filter_by_confidence,
+    ClassRemap, Detection, ExportFormat, FolderLoopSource, FrameSource, LabelMap, ModelInfo,
+    Skeleton, TestPatternSource,
+};
+use settings::{ModelConfig, Settings};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::path::PathBuf;
+use summary::DetectionListView;
+use thumbnails::{ThumbnailLoader, THUMBNAIL_SIZE};
+use viewport::{ImageView, ViewMode};
+use worker::{CachedDetection, InferenceWorker, Stage, WorkerEvent};
+
+// 상수 정의
+const LIVE_FOLDER_FPS: f32 = 10.0;
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "webp"];
+const HOVER_BOX_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 140, 0); // 주황색
+const SELECTED_BOX_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 230, 0); // 노란색
+const KEYPOINT_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 255, 0); // 초록색
+const SKELETON_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 200, 255); // 하늘색
 
 /// GUI 애플리케이션 실행
 pub fn run_gui() {
@@ -13,41 +47,319 @@ pub fn run_gui() {
     if let Err(e) = eframe::run_native(
         "RF-DETR Object Detection",
         options,
-        Box::new(|_cc| Ok(Box::new(RfDetrApp::default()))),
+        Box::new(|cc| Ok(Box::new(RfDetrApp::new(cc)))),
     ) {
         eprintln!("GUI 실행 오류: {e}");
     }
 }
 
+/// 처리 대기 중인 이미지 입력
+enum ImageInput {
+    /// 파일 경로 (파일 선택, 드래그 앤 드롭)
+    File(PathBuf),
+    /// 메모리 상의 이미지 (바이트만 전달된 드롭, 클립보드 붙여넣기)
+    Bytes { name: String, data: Vec<u8> },
+}
+
+impl ImageInput {
+    /// 표시용 경로 (메모리 이미지는 이름만 사용)
+    fn display_path(&self) -> PathBuf {
+        match self {
+            ImageInput::File(path) => path.clone(),
+            ImageInput::Bytes { name, .. } => PathBuf::from(name),
+        }
+    }
+
+    /// 검출 결과 캐시 키 (파일 입력만 캐시)
+    fn cache_key(&self) -> Option<PathBuf> {
+        match self {
+            ImageInput::File(path) => Some(path.clone()),
+            ImageInput::Bytes { .. } => None,
+        }
+    }
+}
+
+/// 신뢰도 임계값과 클래스 필터를 통과하는지 확인
+fn passes_filter(detection: &Detection, threshold: f32, hidden_classes: &BTreeSet<String>) -> bool {
+    detection.confidence > threshold && !hidden_classes.contains(&detection.class_name)
+}
+
+/// 워커 검출 결과에 라벨 맵과 클래스 재매핑 적용
+fn apply_labels(
+    label_map: &LabelMap,
+    class_remap: Option<&ClassRemap>,
+    detections: &mut Vec<Detection>,
+) {
+    label_map.apply(detections);
+    if let Some(remap) = class_remap {
+        remap.apply(detections);
+    }
+}
+
+/// 기존 `classes.txt`를 다른 클래스 목록으로 덮어쓸지 확인
+fn confirm_overwrite_classes(dir: &std::path::Path) -> bool {
+    rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Warning)
+        .set_title("Overwrite classes.txt?")
+        .set_description(format!(
+            "{} already lists different class names. Overwriting it changes the meaning of \
+             existing YOLO labels in that folder.",
+            dir.join("classes.txt").display()
+        ))
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show()
+        == rfd::MessageDialogResult::Yes
+}
+
+/// 모델 구성 변경으로 이미지별 수동 보정 내역을 버릴지 확인
+fn confirm_discard_edits(images: usize) -> bool {
+    rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Warning)
+        .set_title("Discard manual corrections?")
+        .set_description(format!(
+            "Changing the model, input size, EXIF orientation or classifier pipeline re-runs \
+             detection and discards the manual corrections on {} image(s). Save them first \
+             with Save annotations to keep them.",
+            images
+        ))
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show()
+        == rfd::MessageDialogResult::Yes
+}
+
+/// 폴더 내 이미지 목록 (파일 이름 순, 하위 폴더 제외)
+fn list_folder_images(dir: &std::path::Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut images: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && is_image_path(path))
+        .collect();
+    images.sort();
+    Ok(images)
+}
+
+/// 지원하는 이미지 확장자인지 확인
+fn is_image_path(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| {
+            IMAGE_EXTENSIONS
+                .iter()
+                .any(|ext| ext.eq_ignore_ascii_case(e))
+        })
+        .unwrap_or(false)
+}
+
 /// RF-DETR GUI 애플리케이션 구조체
 struct RfDetrApp {
     detections: Vec<Detection>,
+    candidates: Vec<Detection>,
+    source_image: Option<image::RgbImage>,
+    hidden_classes: BTreeSet<String>,
+    selected_detection: Option<usize>,
+    hovered_detection: Option<usize>,
+    /// 이전 프레임에 이미지 위에서 호버한 검출 (목록 패널이 이미지 패널보다 먼저 그려지므로 한 프레임 유지)
+    image_hovered_detection: Option<usize>,
+    scroll_to_selected: bool,
     is_processing: bool,
     error_message: Option<String>,
     selected_image_path: Option<PathBuf>,
     processed_image: Option<egui::TextureHandle>,
     image_size: egui::Vec2,
+    image_view: ImageView,
     inference_time_ms: Option<f64>,
-    model_cache: Option<ModelCache>,
+    worker: Option<InferenceWorker>,
+    current_job: Option<u64>,
+    current_stage: Option<Stage>,
+    job_started_at: Option<std::time::Instant>,
+    model_info: Option<ModelInfo>,
+    pending_images: VecDeque<ImageInput>,
+    current_job_key: Option<PathBuf>,
+    result_cache: HashMap<PathBuf, CachedDetection>,
+    /// 이미지별 수동 보정 내역 (`result_cache`의 원본 후보에 덧씌움)
+    candidate_edits: HashMap<PathBuf, CandidateEdits>,
+    folder_images: Vec<PathBuf>,
+    folder_index: Option<usize>,
+    thumbnails: Option<ThumbnailLoader>,
+    scroll_to_selected_thumbnail: bool,
+    status_message: Option<String>,
+    editor: Option<AnnotationEditor>,
+    displayed_key: Option<PathBuf>,
+    comparison: ModelComparison,
+    image_generation: u64,
+    live: Option<LiveSession>,
+    settings: Settings,
+    /// 워커에 마지막으로 전달한 모델 구성
+    applied_model: Option<ModelConfig>,
+    label_map: LabelMap,
+    label_map_status: String,
+    class_remap: Option<ClassRemap>,
+    class_remap_status: String,
+    /// 설정의 스켈레톤 파일 (None이면 키포인트 수에 맞는 기본값)
+    skeleton: Option<Skeleton>,
+    skeleton_status: String,
+    show_settings: bool,
+    list_view: DetectionListView,
+    crop_gallery: CropGallery,
+    mask_overlay: MaskOverlay,
 }
 
 impl Default for RfDetrApp {
     fn default() -> Self {
         Self {
             detections: Vec::new(),
+            candidates: Vec::new(),
+            source_image: None,
+            hidden_classes: BTreeSet::new(),
+            selected_detection: None,
+            hovered_detection: None,
+            image_hovered_detection: None,
+            scroll_to_selected: false,
             is_processing: false,
             error_message: None,
             selected_image_path: None,
             processed_image: None,
             image_size: egui::Vec2::ZERO,
+            image_view: ImageView::default(),
             inference_time_ms: None,
-            model_cache: None,
+            worker: None,
+            current_job: None,
+            current_stage: None,
+            job_started_at: None,
+            model_info: None,
+            pending_images: VecDeque::new(),
+            current_job_key: None,
+            result_cache: HashMap::new(),
+            candidate_edits: HashMap::new(),
+            folder_images: Vec::new(),
+            folder_index: None,
+            thumbnails: None,
+            scroll_to_selected_thumbnail: false,
+            status_message: None,
+            editor: None,
+            displayed_key: None,
+            comparison: ModelComparison::default(),
+            image_generation: 0,
+            live: None,
+            settings: Settings::default(),
+            applied_model: None,
+            label_map: LabelMap::default(),
+            label_map_status: String::new(),
+            class_remap: None,
+            class_remap_status: String::new(),
+            skeleton: None,
+            skeleton_status: String::new(),
+            show_settings: false,
+            list_view: DetectionListView::default(),
+            crop_gallery: CropGallery::default(),
+            mask_overlay: MaskOverlay::default(),
         }
     }
 }
 
+impl RfDetrApp {
+    /// 저장된 설정을 복원하여 생성
+    fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let mut app = Self::default();
+        if let Some(settings) = cc
+            .storage
+            .and_then(|storage| eframe::get_value::<Settings>(storage, eframe::APP_KEY))
+        {
+            app.settings = settings;
+        }
+        app.reload_label_map();
+        app.reload_skeleton();
+        app
+    }
+}
+
 impl eframe::App for RfDetrApp {
-        fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, &self.settings);
+    }
+
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // 드롭/붙여넣기 입력을 대기열에 추가
+        self.handle_dropped_files(ctx);
+        self.handle_clipboard_paste(ctx);
+
+        // 호버 상태는 매 프레임 목록/이미지 패널에서 다시 계산
+        self.hovered_detection = None;
+
+        // 좌우 화살표로 폴더 이미지 이동
+        self.handle_folder_navigation(ctx);
+
+        // 편집 모드 단축키 (삭제, 실행 취소/다시 실행)
+        if let Some(editor) = &mut self.editor {
+            editor.handle_shortcuts(ctx, &mut self.detections, &mut self.selected_detection);
+        }
+
+        // 모델/스레드 설정이 바뀌었으면 세션 재구성
+        self.sync_model_config(ctx);
+
+        // 워커 이벤트 반영 후 대기열에서 다음 이미지 제출
+        self.poll_worker(ctx);
+        self.poll_live(ctx);
+        if let Some(thumbnails) = &mut self.thumbnails {
+            thumbnails.poll(ctx);
+        }
+        if !self.is_processing {
+            if let Some(input) = self.pending_images.pop_front() {
+                self.process_image(ctx, input);
+            }
+        }
+
+        // 상단 메뉴 바 (저장/내보내기)
+        egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
+            egui::MenuBar::new().ui(ui, |ui| {
+                self.render_file_menu(ui);
+                self.render_live_menu(ui);
+                ui.menu_button("Tools", |ui| {
+                    if ui
+                        .checkbox(&mut self.comparison.open, "⚖ Compare models")
+                        .clicked()
+                    {
+                        ui.close();
+                    }
+                    if ui
+                        .checkbox(&mut self.crop_gallery.open, "🖼 Crop gallery")
+                        .clicked()
+                    {
+                        ui.close();
+                    }
+                    if ui.checkbox(&mut self.show_settings, "⚙ Settings").clicked() {
+                        ui.close();
+                    }
+                });
+            });
+        });
+
+        // 우측 설정 패널
+        if self.show_settings {
+            egui::SidePanel::right("settings_panel")
+                .default_width(280.0)
+                .show(ctx, |ui| {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        ui.heading("Settings");
+                        let changes = self.settings.ui(
+                            ui,
+                            &self.label_map_status,
+                            &self.class_remap_status,
+                            &self.skeleton_status,
+                        );
+                        if changes.skeleton {
+                            self.reload_skeleton();
+                        }
+                        if changes.label_map {
+                            self.reload_label_map();
+                            self.relabel_candidates();
+                        }
+                        if changes.filters || changes.label_map {
+                            self.apply_filters();
+                        }
+                    });
+                });
+        }
+
         // 좌측 사이드 패널 (검출 결과)
         egui::SidePanel::left("detections_panel")
             .resizable(false)
@@ -56,13 +368,55 @@ impl eframe::App for RfDetrApp {
             .show(ctx, |ui| {
                 self.render_header(ui);
                 self.render_error_message(ui);
+                // 편집 중에는 필터를 고정 (편집 대상 목록이 바뀌지 않도록)
+                ui.add_enabled_ui(self.editor.is_none(), |ui| {
+                    self.render_filter_controls(ui);
+                });
+                self.render_edit_panel(ui);
                 self.render_detections_panel(ui);
             });
 
+        // 하단 썸네일 스트립 (폴더를 연 경우)
+        if !self.folder_images.is_empty() {
+            egui::TopBottomPanel::bottom("thumbnail_strip")
+                .resizable(false)
+                .show(ctx, |ui| {
+                    self.render_thumbnail_strip(ui);
+                });
+        }
+
         // 중앙 패널 (이미지)
         egui::CentralPanel::default().show(ctx, |ui| {
             self.render_image_panel(ui);
         });
+
+        // 두 모델 비교 창 (현재 이미지에 두 모델을 실행하여 나란히 표시)
+        let threshold = self.settings.confidence_threshold;
+        let hidden = &self.hidden_classes;
+        self.comparison.show(
+            ctx,
+            self.processed_image.as_ref(),
+            self.source_image.as_ref(),
+            self.image_generation,
+            &|d| passes_filter(d, threshold, hidden),
+        );
+
+        // 검출 영역 크롭 갤러리 (클릭 시 해당 검출 선택)
+        let image_name = self.image_stem();
+        if let Some(index) = self.crop_gallery.show(
+            ctx,
+            self.source_image.as_ref(),
+            self.image_generation,
+            &self.detections,
+            &image_name,
+            &mut self.settings.last_export_dir,
+        ) {
+            self.selected_detection = Some(index);
+            self.scroll_to_selected = true;
+        }
+
+        // 파일을 창 위로 드래그하는 동안 안내 오버레이 표시
+        self.render_drop_overlay(ctx);
     }
 }
 
@@ -75,23 +429,58 @@ impl RfDetrApp {
         // 모델 정보 표시
         ui.horizontal(|ui| {
             ui.label("Model:");
-            ui.colored_label(
-                egui::Color32::from_rgb(0, 150, 255),
-                "RF-DETR Original (108 MB)"
-            );
+            match &self.model_info {
+                Some(info) => {
+                    ui.colored_label(egui::Color32::from_rgb(0, 150, 255), info.summary());
+                }
+                None => {
+                    ui.label("Not loaded (loads with the first image)");
+                }
+            }
         });
+        if let Some(info) = &self.model_info {
+            Self::render_model_details(ui, info);
+        }
 
         ui.vertical(|ui| {
-            if ui.add_sized(egui::vec2(380.0, 40.0), egui::Button::new("📁 Select Image")).clicked() && !self.is_processing {
-                self.select_image(ui.ctx());
+            ui.horizontal(|ui| {
+                if ui
+                    .add_sized(
+                        egui::vec2(188.0, 40.0),
+                        egui::Button::new("📁 Select Image"),
+                    )
+                    .clicked()
+                {
+                    self.select_image();
+                }
+                if ui
+                    .add_sized(egui::vec2(188.0, 40.0), egui::Button::new("🗂 Open Folder"))
+                    .clicked()
+                {
+                    self.select_folder(ui.ctx());
+                }
+            });
+
+            if ui
+                .add_sized(egui::vec2(380.0, 24.0), egui::Button::new("📋 Paste Image"))
+                .clicked()
+            {
+                if let Err(e) = self.paste_clipboard_image() {
+                    self.error_message = Some(format!("Clipboard: {}", e));
+                }
             }
 
             if self.is_processing {
-                ui.label("Processing...");
+                self.render_progress(ui);
+            }
+
+            if !self.pending_images.is_empty() {
+                ui.label(format!("Queued: {}", self.pending_images.len()));
             }
 
             if let Some(path) = &self.selected_image_path {
-                let file_name = path.file_name()
+                let file_name = path
+                    .file_name()
                     .map(|f| f.to_string_lossy())
                     .unwrap_or_else(|| "<unknown>".into());
                 ui.label(format!("Selected: {}", file_name));
@@ -104,12 +493,695 @@ impl RfDetrApp {
                 ui.label("⏱️ Inference Time:");
                 ui.colored_label(
                     egui::Color32::from_rgb(0, 150, 255),
-                    format!("{:.2} ms", inference_time)
+                    format!("{:.2} ms", inference_time),
                 );
             });
         }
     }
 
+    /// 처리 진행 상황 렌더링 (단계, 경과 시간, 취소 버튼)
+    fn render_progress(&mut self, ui: &mut egui::Ui) {
+        let stage = self.current_stage.unwrap_or(Stage::Reading);
+        let elapsed = self
+            .job_started_at
+            .map(|t| t.elapsed().as_secs_f32())
+            .unwrap_or(0.0);
+
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label(format!("{} ({:.1}s)", stage.label(), elapsed));
+        });
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::ProgressBar::new(stage.fraction())
+                    .desired_width(300.0)
+                    .animate(true),
+            );
+            if ui
+                .button("✖ Cancel")
+                .on_hover_text("Cancel the current image and clear the queue")
+                .clicked()
+            {
+                self.cancel_processing();
+            }
+        });
+        // 경과 시간 갱신을 위해 주기적으로 다시 그리기
+        ui.ctx()
+            .request_repaint_after(std::time::Duration::from_millis(100));
+    }
+
+    /// 모델 상세 정보 렌더링 (접이식)
+    fn render_model_details(ui: &mut egui::Ui, info: &ModelInfo) {
+        egui::CollapsingHeader::new("Model details")
+            .id_salt("model_details")
+            .show(ui, |ui| {
+                for input in &info.inputs {
+                    ui.label(format!(
+                        "Input  {}: {} {}",
+                        input.name,
+                        input.dtype,
+                        input.shape_string()
+                    ));
+                }
+                for output in &info.outputs {
+                    ui.label(format!(
+                        "Output {}: {} {}",
+                        output.name,
+                        output.dtype,
+                        output.shape_string()
+                    ));
+                }
+                for (key, value) in &info.metadata {
+                    ui.label(format!("{}: {}", key, value));
+                }
+            });
+    }
+
+    /// 신뢰도 슬라이더 및 클래스 필터 렌더링
+    fn render_filter_controls(&mut self, ui: &mut egui::Ui) {
+        if self.candidates.is_empty() {
+            return;
+        }
+        let mut changed = false;
+
+        changed |= ui
+            .add(
+                egui::Slider::new(&mut self.settings.confidence_threshold, 0.05..=0.95)
+                    .text("Confidence")
+                    .fixed_decimals(2),
+            )
+            .changed();
+
+        // 후보에 등장하는 클래스별 (현재 임계값 통과 수, 전체 후보 수)
+        let mut classes: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+        for candidate in &self.candidates {
+            let entry = classes.entry(candidate.class_name.as_str()).or_default();
+            entry.1 += 1;
+            if candidate.confidence > self.settings.confidence_threshold {
+                entry.0 += 1;
+            }
+        }
+
+        egui::CollapsingHeader::new("Class filter")
+            .id_salt("class_filter")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.small_button("All").clicked() {
+                        self.hidden_classes.clear();
+                        changed = true;
+                    }
+                    if ui.small_button("None").clicked() {
+                        self.hidden_classes = classes.keys().map(|c| c.to_string()).collect();
+                        changed = true;
+                    }
+                });
+                for (class_name, (shown, total)) in &classes {
+                    let mut visible = !self.hidden_classes.contains(*class_name);
+                    let label = format!("{} ({}/{})", class_name, shown, total);
+                    if ui.checkbox(&mut visible, label).changed() {
+                        if visible {
+                            self.hidden_classes.remove(*class_name);
+                        } else {
+                            self.hidden_classes.insert(class_name.to_string());
+                        }
+                        changed = true;
+                    }
+                }
+            });
+
+        if changed {
+            self.apply_filters();
+        }
+    }
+
+    /// 저장된 후보에 임계값/클래스/top-k 필터 적용 (재추론 없음, 오버레이는 매 프레임 그려짐)
+    fn apply_filters(&mut self) {
+        let mut detections =
+            filter_by_confidence(&self.candidates, self.settings.confidence_threshold);
+        detections.retain(|d| !self.hidden_classes.contains(&d.class_name));
+        if detections.len() > self.settings.top_k {
+            detections.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+            detections.truncate(self.settings.top_k);
+        }
+        self.detections = detections;
+        self.selected_detection = None;
+        self.hovered_detection = None;
+        self.image_hovered_detection = None;
+    }
+
+    /// 설정의 라벨 맵과 클래스 재매핑 파일 다시 로드 (실패 시 기본 이름, 재매핑 없음)
+    fn reload_label_map(&mut self) {
+        match self.settings.label_map_path.clone() {
+            None => {
+                self.label_map = LabelMap::default();
+                self.label_map_status.clear();
+            }
+            Some(path) => match LabelMap::from_file(&path) {
+                Ok(map) => {
+                    self.label_map_status = format!("{} class names loaded", map.len());
+                    self.label_map = map;
+                }
+                Err(e) => {
+                    self.label_map = LabelMap::default();
+                    self.label_map_status = format!("Failed to load: {}", e);
+                }
+            },
+        }
+
+        self.class_remap = None;
+        self.class_remap_status.clear();
+        if let Some(path) = self.settings.class_remap_path.clone() {
+            match ClassRemap::from_file(&path) {
+                Ok(remap) => {
+                    self.class_remap_status =
+                        format!("{} target classes loaded", remap.classes.len());
+                    self.class_remap = Some(remap);
+                }
+                Err(e) => self.class_remap_status = format!("Failed to load: {}", e),
+            }
+        }
+    }
+
+    /// 설정의 스켈레톤 파일 다시 로드 (실패 시 기본 스켈레톤)
+    fn reload_skeleton(&mut self) {
+        self.skeleton = None;
+        self.skeleton_status.clear();
+        if let Some(path) = self.settings.skeleton_path.clone() {
+            match Skeleton::from_json_file(&path) {
+                Ok(skeleton) => {
+                    self.skeleton_status = format!(
+                        "{} keypoints, {} edges, {} flip pairs",
+                        skeleton.names.len(),
+                        skeleton.edges.len(),
+                        skeleton.flip_pairs.len()
+                    );
+                    self.skeleton = Some(skeleton);
+                }
+                Err(e) => self.skeleton_status = format!("Failed to load: {}", e),
+            }
+        }
+    }
+
+    /// 워커 검출 결과에 라벨 맵과 클래스 재매핑을 적용하여 후보 목록으로 설정
+    fn set_candidates(&mut self, mut candidates: Vec<Detection>) {
+        apply_labels(&self.label_map, self.class_remap.as_ref(), &mut candidates);
+        self.candidates = candidates;
+    }
+
+    /// 캐시된 원본 후보로 후보 목록 구성 (해당 이미지의 수동 보정 내역이 있으면 덧씌움)
+    fn set_cached_candidates(&mut self, key: Option<&PathBuf>, raw: Vec<Detection>) {
+        match key.and_then(|key| self.candidate_edits.get(key)) {
+            Some(edits) => {
+                self.candidates = edits.apply(&raw, |d| {
+                    apply_labels(&self.label_map, self.class_remap.as_ref(), d)
+                });
+            }
+            None => self.set_candidates(raw),
+        }
+    }
+
+    /// 라벨 맵/재매핑 변경 후 현재 이미지 후보 목록 다시 구성
+    ///
+    /// 재매핑으로 제외된 검출도 되살릴 수 있도록 이미지별 캐시의 원본 결과를 사용합니다.
+    fn relabel_candidates(&mut self) {
+        let key = self.displayed_key.clone();
+        match key.as_ref().and_then(|key| self.result_cache.get(key)) {
+            Some(cached) => {
+                let raw = cached.candidates.clone();
+                self.set_cached_candidates(key.as_ref(), raw);
+            }
+            None => {
+                let candidates = std::mem::take(&mut self.candidates);
+                self.set_candidates(candidates);
+            }
+        }
+    }
+
+    /// 설정의 모델 구성이 워커에 적용된 것과 다르면 세션 재구성 후 현재 이미지 재검출
+    ///
+    /// 입력 크기를 드래그하는 동안에는 반영을 미룹니다.
+    fn sync_model_config(&mut self, ctx: &egui::Context) {
+        let config = self.settings.model_config();
+        if self.applied_model.as_ref() == Some(&config) || ctx.input(|i| i.pointer.any_down()) {
+            return;
+        }
+        if self.worker.is_none() {
+            // 워커가 아직 없으면 첫 이미지 때 이 구성으로 시작
+            return;
+        }
+        // 입력 크기나 EXIF 방향만 바뀐 경우에는 세션이 유지되므로 모델 정보도 그대로 둠
+        let session_changed = self.applied_model.as_ref().is_none_or(|applied| {
+            applied.source != config.source || applied.options != config.options
+        });
+        // 스레드 수만 바뀐 경우에는 결과가 같으므로 캐시와 보정 내역을 유지
+        let results_changed = self
+            .applied_model
+            .as_ref()
+            .is_none_or(|applied| applied.changes_results(&config));
+        if results_changed {
+            self.finish_editing();
+            if !self.candidate_edits.is_empty()
+                && !confirm_discard_edits(self.candidate_edits.len())
+            {
+                if let Some(applied) = self.applied_model.clone() {
+                    self.settings.set_model_config(&applied);
+                }
+                return;
+            }
+        }
+        let Some(worker) = &self.worker else {
+            return;
+        };
+        if let Err(e) = worker.configure(config.clone()) {
+            self.error_message = Some(e.to_string());
+            self.worker = None;
+            return;
+        }
+        self.applied_model = Some(config);
+        if session_changed {
+            self.model_info = None;
+        }
+        if !results_changed {
+            return;
+        }
+        // 이전 구성의 결과와 보정 내역은 더 이상 맞지 않음 (진행 중인 작업도 새 구성으로 다시 실행)
+        self.result_cache.clear();
+        self.candidate_edits.clear();
+        let rerun = if self.is_processing {
+            let key = self.current_job_key.clone();
+            if let (Some(worker), Some(job_id)) = (&self.worker, self.current_job) {
+                worker.cancel(job_id);
+            }
+            self.finish_job();
+            key
+        } else {
+            self.displayed_key.clone()
+        };
+        if let Some(path) = rerun.filter(|_| self.live.is_none()) {
+            self.pending_images.push_front(ImageInput::File(path));
+        }
+    }
+
+    /// 편집 모드 켜기/끄기
+    fn toggle_edit_mode(&mut self) {
+        if self.editor.is_some() {
+            self.finish_editing();
+        } else if self.source_image.is_some() && self.live.is_none() {
+            self.editor = Some(AnnotationEditor::new(
+                &self.detections,
+                self.class_remap.as_ref(),
+            ));
+        }
+    }
+
+    /// 편집 모드 종료 (보정 결과를 후보 목록과 이미지별 보정 내역에 반영)
+    fn finish_editing(&mut self) {
+        let Some(editor) = self.editor.take() else {
+            return;
+        };
+        if !editor.is_modified() {
+            return;
+        }
+        // 이후 필터를 바꿔도 보정 결과가 유지되도록 보이던 후보만 편집 결과로 교체
+        self.candidates = editor.merge_into(&self.candidates, &self.detections);
+        // 캐시의 원본 후보는 그대로 두고 차이만 따로 보관
+        if let Some(key) = &self.displayed_key {
+            if let Some(cached) = self.result_cache.get(key) {
+                let edits =
+                    CandidateEdits::from_merged(&cached.candidates, &self.candidates, |d| {
+                        apply_labels(&self.label_map, self.class_remap.as_ref(), d)
+                    });
+                self.candidate_edits.insert(key.clone(), edits);
+            }
+        }
+    }
+
+    /// 편집 도구 렌더링 (실행 취소/다시 실행, 클래스 변경, 삭제, 저장)
+    fn render_edit_panel(&mut self, ui: &mut egui::Ui) {
+        let Some(editor) = &mut self.editor else {
+            return;
+        };
+        let mut save_format = None;
+        let mut done = false;
+
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                ui.strong("✏ Edit mode");
+                if ui
+                    .add_enabled(editor.can_undo(), egui::Button::new("↶ Undo"))
+                    .clicked()
+                {
+                    editor.undo(&mut self.detections);
+                    self.selected_detection = None;
+                }
+                if ui
+                    .add_enabled(editor.can_redo(), egui::Button::new("↷ Redo"))
+                    .clicked()
+                {
+                    editor.redo(&mut self.detections);
+                    self.selected_detection = None;
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("New box class:");
+                editor::class_combo(
+                    ui,
+                    "new_box_class",
+                    &editor.classes,
+                    &mut editor.new_box_class,
+                );
+            });
+
+            if let Some(index) = self
+                .selected_detection
+                .filter(|i| *i < self.detections.len())
+            {
+                ui.horizontal(|ui| {
+                    ui.label(format!("#{} class:", index + 1));
+                    let mut class_id = self.detections[index].class_id;
+                    if editor::class_combo(ui, "selected_box_class", &editor.classes, &mut class_id)
+                    {
+                        editor.set_class(&mut self.detections, index, class_id);
+                    }
+                    if ui.button("🗑 Delete").clicked() {
+                        editor.delete(&mut self.detections, index);
+                        self.selected_detection = None;
+                    }
+                });
+            }
+
+            ui.label(
+                egui::RichText::new(
+                    "Drag a corner to resize, drag a box to move, drag on empty space to draw. \
+                     Delete removes the selected box. Right/middle drag pans.",
+                )
+                .small()
+                .weak(),
+            );
+
+            ui.horizontal(|ui| {
+                ui.menu_button("💾 Save annotations", |ui| {
+                    for format in ExportFormat::ALL {
+                        if ui.button(format.label()).clicked() {
+                            ui.close();
+                            save_format = Some(format);
+                        }
+                    }
+                });
+                if ui.button("✔ Done").clicked() {
+                    done = true;
+                }
+            });
+        });
+        ui.add_space(5.0);
+
+        if let Some(format) = save_format {
+            let result = self.export_current_detections(format);
+            self.report_export(result);
+        }
+        if done {
+            self.finish_editing();
+        }
+    }
+
+    /// 라이브 메뉴 렌더링 (카메라 없이 동작하는 합성/폴더 반복 소스)
+    fn render_live_menu(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button("Live", |ui| {
+            if ui.button("▶ Test pattern").clicked() {
+                ui.close();
+                self.start_live(ui.ctx(), Box::new(TestPatternSource::default()));
+            }
+            if ui.button("▶ Loop folder…").clicked() {
+                ui.close();
+                if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                    let orientation = self.settings.model_config().orientation;
+                    match FolderLoopSource::with_orientation(&dir, LIVE_FOLDER_FPS, orientation) {
+                        Ok(source) => self.start_live(ui.ctx(), Box::new(source)),
+                        Err(e) => self.error_message = Some(e.to_string()),
+                    }
+                }
+            }
+            if ui
+                .add_enabled(self.live.is_some(), egui::Button::new("⏹ Stop"))
+                .clicked()
+            {
+                ui.close();
+                self.live = None;
+            }
+        });
+    }
+
+    /// 라이브 모드 시작 (진행 중인 작업과 편집은 정리)
+    fn start_live(&mut self, ctx: &egui::Context, source: Box<dyn FrameSource>) {
+        self.finish_editing();
+        self.cancel_processing();
+        self.error_message = None;
+        self.selected_image_path = None;
+        self.displayed_key = None;
+        // 이전 세션의 스레드를 먼저 정리한 뒤 새로 시작
+        drop(self.live.take());
+        self.live = Some(LiveSession::start(
+            ctx.clone(),
+            source,
+            self.settings.model_config(),
+        ));
+    }
+
+    /// 라이브 결과 반영 (가장 최근 프레임만 표시)
+    fn poll_live(&mut self, ctx: &egui::Context) {
+        let Some(live) = &mut self.live else {
+            return;
+        };
+        let frame = live.poll();
+        if let Some(warning) = live.take_warnings().pop() {
+            self.error_message = Some(format!("Live mode: {}", warning));
+        }
+        if live.is_finished() {
+            if let Some(error) = live.take_error() {
+                self.error_message = Some(format!("Live mode stopped: {}", error));
+            }
+            self.live = None;
+        }
+
+        if let Some(frame) = frame {
+            self.set_candidates(frame.candidates);
+            self.inference_time_ms = Some(frame.inference_time_ms);
+            self.update_live_texture(ctx, &frame.image);
+            self.source_image = Some(frame.image);
+            self.apply_filters();
+        }
+    }
+
+    /// 라이브 프레임 텍스처 갱신 (크기가 같으면 보기 상태 유지)
+    fn update_live_texture(&mut self, ctx: &egui::Context, image: &image::RgbImage) {
+        let size = [image.width() as usize, image.height() as usize];
+        if let Some(texture) = self.processed_image.as_mut().filter(|t| t.size() == size) {
+            let color_image = egui::ColorImage::from_rgb(size, image.as_raw());
+            texture.set(color_image, Default::default());
+            return;
+        }
+        self.load_texture(ctx, image);
+    }
+
+    /// 파일 메뉴 렌더링 (결과 이미지 저장, 검출 결과 내보내기, 클립보드 복사)
+    fn render_file_menu(&mut self, ui: &mut egui::Ui) {
+        let has_image = self.source_image.is_some();
+        ui.menu_button("File", |ui| {
+            let last_folder = self.settings.last_folder.clone();
+            if ui
+                .add_enabled(
+                    last_folder.is_some(),
+                    egui::Button::new("🗂 Reopen last folder"),
+                )
+                .on_hover_text(
+                    last_folder
+                        .as_ref()
+                        .map(|p| p.display().to_string())
+                        .unwrap_or_default(),
+                )
+                .clicked()
+            {
+                ui.close();
+                if let Some(dir) = last_folder {
+                    self.open_folder(ui.ctx(), dir);
+                }
+            }
+            ui.separator();
+
+            if ui
+                .add_enabled(has_image, egui::Button::new("💾 Save annotated image…"))
+                .clicked()
+            {
+                ui.close();
+                let result = self.save_annotated_image();
+                self.report_export(result);
+            }
+
+            ui.add_enabled_ui(has_image, |ui| {
+                ui.menu_button("📤 Export detections", |ui| {
+                    for format in ExportFormat::ALL {
+                        if ui.button(format.label()).clicked() {
+                            ui.close();
+                            let result = self.export_current_detections(format);
+                            self.report_export(result);
+                        }
+                    }
+                });
+            });
+
+            if ui
+                .add_enabled(has_image, egui::Button::new("📋 Copy image to clipboard"))
+                .clicked()
+            {
+                ui.close();
+                let result = self
+                    .copy_annotated_image()
+                    .map(|()| Some("Copied image to clipboard".to_string()));
+                self.report_export(result);
+            }
+        });
+
+        if let Some(message) = &self.status_message {
+            ui.separator();
+            ui.label(message);
+        }
+    }
+
+    /// 저장/내보내기 결과를 상태 메시지 또는 에러 메시지로 반영
+    fn report_export(&mut self, result: anyhow::Result<Option<String>>) {
+        match result {
+            Ok(Some(message)) => {
+                self.status_message = Some(message);
+                self.error_message = None;
+            }
+            Ok(None) => {}
+            Err(e) => self.error_message = Some(format!("Export failed: {}", e)),
+        }
+    }
+
+    /// 현재 필터가 적용된 검출 박스를 그린 이미지
+    fn annotated_image(&self) -> Option<image::RgbImage> {
+        let mut image = self.source_image.clone()?;
+        draw_detections_with_skeleton(
+            &mut image,
+            &self.detections,
+            &self.settings.draw_style,
+            self.skeleton.as_ref(),
+        );
+        Some(image)
+    }
+
+    /// 현재 이미지 이름 (확장자 제외)
+    fn image_stem(&self) -> String {
+        self.selected_image_path
+            .as_ref()
+            .and_then(|p| p.file_stem())
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "image".to_string())
+    }
+
+    /// 저장 대화상자 (마지막 저장 폴더 기억, 확장자 없이 입력하면 첫 번째 확장자 추가)
+    fn pick_save_path(
+        &mut self,
+        file_name: &str,
+        filter_name: &str,
+        extensions: &[&str],
+    ) -> Option<PathBuf> {
+        let mut dialog = rfd::FileDialog::new()
+            .add_filter(filter_name, extensions)
+            .set_file_name(file_name);
+        if let Some(dir) = &self.settings.last_export_dir {
+            dialog = dialog.set_directory(dir);
+        }
+        let mut path = dialog.save_file()?;
+        if path.extension().is_none() {
+            if let Some(extension) = extensions.first() {
+                path.set_extension(extension);
+            }
+        }
+        self.settings.last_export_dir = path.parent().map(|p| p.to_path_buf());
+        Some(path)
+    }
+
+    /// 박스를 그린 이미지 저장
+    fn save_annotated_image(&mut self) -> anyhow::Result<Option<String>> {
+        let image = self
+            .annotated_image()
+            .ok_or_else(|| anyhow::anyhow!("No image loaded"))?;
+        let file_name = format!("{}_annotated.png", self.image_stem());
+        let Some(path) = self.pick_save_path(&file_name, "Image files", IMAGE_EXTENSIONS) else {
+            return Ok(None);
+        };
+        image.save(&path)?;
+        Ok(Some(format!("Saved {}", path.display())))
+    }
+
+    /// 현재 표시 중인 검출 결과를 지정 형식으로 내보내기
+    fn export_current_detections(
+        &mut self,
+        format: ExportFormat,
+    ) -> anyhow::Result<Option<String>> {
+        let (width, height) = self
+            .source_image
+            .as_ref()
+            .map(|img| img.dimensions())
+            .ok_or_else(|| anyhow::anyhow!("No image loaded"))?;
+        let stem = self.image_stem();
+        let image_name = self
+            .selected_image_path
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| stem.clone());
+
+        let file_name = format.default_file_name(&stem);
+        let Some(path) = self.pick_save_path(&file_name, format.label(), &[format.extension()])
+        else {
+            return Ok(None);
+        };
+        let classes = self.class_remap.as_ref().map(ClassRemap::categories);
+        if format == ExportFormat::Yolo {
+            let dir = path.parent().unwrap_or(std::path::Path::new("."));
+            if yolo_classes_conflict(dir, classes.as_deref()) && !confirm_overwrite_classes(dir) {
+                return Ok(Some("Export cancelled (classes.txt kept)".to_string()));
+            }
+        }
+        export_detections_with_classes(
+            format,
+            &path,
+            &image_name,
+            width,
+            height,
+            self.inference_time_ms.unwrap_or(0.0),
+            &self.detections,
+            classes.as_deref(),
+            self.skeleton.as_ref(),
+        )?;
+        Ok(Some(format!(
+            "Exported {} detection(s) as {} to {}",
+            self.detections.len(),
+            format.label(),
+            path.display()
+        )))
+    }
+
+    /// 박스를 그린 이미지를 클립보드에 복사
+    fn copy_annotated_image(&self) -> anyhow::Result<()> {
+        let image = self
+            .annotated_image()
+            .ok_or_else(|| anyhow::anyhow!("No image loaded"))?;
+        let rgba = image::DynamicImage::ImageRgb8(image).to_rgba8();
+        let mut clipboard = arboard::Clipboard::new()?;
+        clipboard.set_image(arboard::ImageData {
+            width: rgba.width() as usize,
+            height: rgba.height() as usize,
+            bytes: std::borrow::Cow::Owned(rgba.into_raw()),
+        })?;
+        Ok(())
+    }
+
     /// 에러 메시지 렌더링
     fn render_error_message(&self, ui: &mut egui::Ui) {
         if let Some(error) = &self.error_message {
@@ -119,10 +1191,34 @@ impl RfDetrApp {
     }
 
     /// 검출 결과 패널 렌더링
-    fn render_detections_panel(&self, ui: &mut egui::Ui) {
+    fn render_detections_panel(&mut self, ui: &mut egui::Ui) {
         ui.heading(format!("Detections ({})", self.detections.len()));
+
+        if !self.candidates.is_empty() {
+            egui::CollapsingHeader::new("Summary")
+                .id_salt("detection_summary")
+                .default_open(true)
+                .show(ui, |ui| {
+                    if let Some(class_name) = summary::summary_table(ui, &self.detections) {
+                        self.list_view.toggle_class_filter(class_name);
+                    }
+                    ui.add_space(5.0);
+                    ui.label("Confidence distribution (all candidates)");
+                    summary::histogram(ui, &self.candidates, self.settings.confidence_threshold);
+                });
+            self.list_view.toolbar(ui);
+        }
+
+        let visible = self.list_view.visible_indices(&self.detections);
+        if visible.len() < self.detections.len() {
+            ui.label(format!(
+                "Showing {} of {}",
+                visible.len(),
+                self.detections.len()
+            ));
+        }
         let available_height = ui.available_height();
-        
+
         // 스크롤 가능한 영역 생성
         egui::ScrollArea::vertical()
             .id_salt("scroll_area_detections")
@@ -135,44 +1231,335 @@ impl RfDetrApp {
                         ui.label("Select an image to get started.");
                     });
                 } else {
-                    for (i, detection) in self.detections.iter().enumerate() {
-                        self.render_detection_item(ui, i, detection);
-                        ui.add_space(5.0);
+                    let mut list_hovered = None;
+                    for i in visible {
+                        let response = self.render_detection_item(ui, i, &self.detections[i]);
+                        if response.clicked() {
+                            self.selected_detection =
+                                (self.selected_detection != Some(i)).then_some(i);
+                        }
+                        if response.hovered() {
+                            list_hovered = Some(i);
+                        }
+                        if self.scroll_to_selected && self.selected_detection == Some(i) {
+                            response.scroll_to_me(Some(egui::Align::Center));
+                            self.scroll_to_selected = false;
+                        }
+                        ui.add_space(2.0);
                     }
+                    // 목록 위 호버는 이미지 쪽 박스 강조에 사용
+                    self.hovered_detection = list_hovered;
                 }
             });
     }
 
-    /// 개별 검출 결과 아이템 렌더링
-    fn render_detection_item(&self, ui: &mut egui::Ui, index: usize, detection: &Detection) {
-        ui.group(|ui| {
-            ui.heading(format!("Detection #{}", index + 1));
-            ui.label(format!("Class: {} (ID: {})", detection.class_name, detection.class_id));
-            ui.label(format!("Confidence: {:.1}%", detection.confidence * 100.0));
-            ui.label(format!(
-                "BBox: [{:.3}, {:.3}, {:.3}, {:.3}]",
-                detection.bbox[0], detection.bbox[1], detection.bbox[2], detection.bbox[3]
+    /// 개별 검출 결과 아이템 렌더링 (한 줄 요약, 클릭/호버 감지용 응답 반환)
+    fn render_detection_item(
+        &self,
+        ui: &mut egui::Ui,
+        index: usize,
+        detection: &Detection,
+    ) -> egui::Response {
+        let is_selected = self.selected_detection == Some(index);
+        let is_hovered =
+            self.hovered_detection == Some(index) || self.image_hovered_detection == Some(index);
+
+        let mut frame = egui::Frame::group(ui.style()).inner_margin(egui::Margin::symmetric(6, 3));
+        if is_selected {
+            frame = frame.stroke(egui::Stroke::new(2.0, SELECTED_BOX_COLOR));
+        } else if is_hovered {
+            frame = frame.stroke(egui::Stroke::new(1.0, HOVER_BOX_COLOR));
+        }
+
+        let [x1, y1, x2, y2] = detection.bbox;
+        let pixel_area = (x2 - x1) * self.image_size.x * (y2 - y1) * self.image_size.y;
+        let response = frame
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal(|ui| {
+                    ui.monospace(format!("#{:<3}", index + 1));
+                    ui.strong(&detection.class_name);
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(format!("{:.0} px²", pixel_area.max(0.0)));
+                        ui.separator();
+                        ui.label(format!("{:.1}%", detection.confidence * 100.0));
+                    });
+                });
+            })
+            .response;
+        let mut hover = format!(
+            "Class: {} (ID: {})\nBBox: [{:.3}, {:.3}, {:.3}, {:.3}]",
+            detection.class_name, detection.class_id, x1, y1, x2, y2
+        );
+        for label in &detection.secondary {
+            hover.push_str(&format!(
+                "\n{}: {} {:.1}%",
+                label.stage,
+                label.label,
+                label.score * 100.0
             ));
-        });
+        }
+        response.interact(egui::Sense::click()).on_hover_text(hover)
     }
 
     /// 이미지 패널 렌더링
-    fn render_image_panel(&self, ui: &mut egui::Ui) {
-        ui.heading("Processed Image");
-        let available_height = ui.available_height();
+    fn render_image_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading("Processed Image");
+            if self.processed_image.is_some() {
+                self.render_view_toolbar(ui);
+                ui.separator();
+                if ui
+                    .add_enabled(
+                        self.live.is_none(),
+                        egui::Button::selectable(self.editor.is_some(), "✏ Edit"),
+                    )
+                    .on_hover_text(
+                        "Correct boxes by hand: resize, move, draw, delete, reassign class",
+                    )
+                    .clicked()
+                {
+                    self.toggle_edit_mode();
+                }
+            }
+            if let Some(live) = &self.live {
+                ui.separator();
+                live.render_status(ui);
+            }
+        });
 
-        // 스크롤 가능한 영역 생성
-        egui::ScrollArea::both()
-            .id_salt("scroll_area_image")
-            .max_height(available_height) // 헤더 공간 제외
-            .show(ui, |ui| {
-                if let Some(texture) = &self.processed_image {
-                    // 이미지 표시 (egui가 자동으로 스케일링)
-                    ui.image(texture);
-                } else {
-                    self.render_empty_image_placeholder(ui);
+        let Some(texture) = self.processed_image.clone() else {
+            self.render_empty_image_placeholder(ui);
+            return;
+        };
+
+        // 남은 영역 전체를 뷰포트로 사용 (휠 확대, 드래그 이동, 클릭 선택)
+        let (viewport, response) =
+            ui.allocate_exact_size(ui.available_size(), egui::Sense::click_and_drag());
+        // 편집 모드에서는 왼쪽 드래그를 박스 편집에 사용
+        self.image_view
+            .handle_input(ui, &response, self.image_size, self.editor.is_none());
+
+        let image_rect = self.image_view.image_rect(viewport, self.image_size);
+        let painter = ui.painter_at(viewport);
+        painter.rect_filled(viewport, 0.0, ui.visuals().extreme_bg_color);
+        painter.image(
+            texture.id(),
+            image_rect,
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            egui::Color32::WHITE,
+        );
+        // 세그멘테이션 모델의 인스턴스 마스크 (박스 아래에 그림)
+        if let Some(overlay) = self.mask_overlay.update(
+            ui.ctx(),
+            self.image_generation,
+            &self.detections,
+            self.settings.draw_style.color,
+            self.settings.draw_style.mask_alpha,
+        ) {
+            painter.image(
+                overlay.id(),
+                image_rect,
+                egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                egui::Color32::WHITE,
+            );
+        }
+
+        if let Some(editor) = &mut self.editor {
+            editor.handle_pointer(
+                ui,
+                &response,
+                image_rect,
+                &mut self.detections,
+                &mut self.selected_detection,
+            );
+        }
+
+        // 원본 이미지 위에 검출 박스를 egui 도형으로 그림
+        self.render_detection_overlay(ui, &response, image_rect);
+        if let Some(editor) = &self.editor {
+            editor.paint(
+                ui,
+                &painter,
+                image_rect,
+                &self.detections,
+                self.selected_detection,
+            );
+        }
+        self.image_view
+            .render_minimap(ui, &texture, viewport, self.image_size);
+    }
+
+    /// 표시 모드/확대 배율 툴바
+    fn render_view_toolbar(&mut self, ui: &mut egui::Ui) {
+        ui.separator();
+        for (mode, label) in [
+            (ViewMode::Fit, "Fit"),
+            (ViewMode::Fill, "Fill"),
+            (ViewMode::Actual, "100%"),
+        ] {
+            if ui
+                .selectable_label(self.image_view.mode == mode, label)
+                .clicked()
+            {
+                self.image_view.set_mode(mode);
+            }
+        }
+
+        if ui.small_button("➖").clicked() {
+            self.image_view.zoom_step(1.0 / 1.25, self.image_size);
+        }
+        if ui.small_button("➕").clicked() {
+            self.image_view.zoom_step(1.25, self.image_size);
+        }
+        // 마지막 프레임의 패널 크기 기준 배율
+        ui.label(format!(
+            "{:.0}%",
+            self.image_view.current_zoom(self.image_size) * 100.0
+        ));
+        ui.label(format!("{} × {}", self.image_size.x, self.image_size.y));
+    }
+
+    /// 정규화 bbox를 화면 좌표 사각형으로 변환
+    fn bbox_to_screen(bbox: &[f32; 4], image_rect: egui::Rect) -> egui::Rect {
+        egui::Rect::from_min_max(
+            image_rect.lerp_inside(egui::vec2(bbox[0], bbox[1])),
+            image_rect.lerp_inside(egui::vec2(bbox[2], bbox[3])),
+        )
+    }
+
+    /// 이미지 위 검출 박스 오버레이 (호버 툴팁, 클릭 선택)
+    fn render_detection_overlay(
+        &mut self,
+        ui: &egui::Ui,
+        response: &egui::Response,
+        image_rect: egui::Rect,
+    ) {
+        let painter = ui.painter_at(response.rect);
+
+        // 포인터 아래 박스 중 가장 작은 박스를 호버 대상으로 선택
+        let pointer_hit = response.hover_pos().and_then(|pos| {
+            self.detections
+                .iter()
+                .enumerate()
+                .filter(|(_, d)| Self::bbox_to_screen(&d.bbox, image_rect).contains(pos))
+                .min_by(|(_, a), (_, b)| {
+                    let area = |d: &Detection| (d.bbox[2] - d.bbox[0]) * (d.bbox[3] - d.bbox[1]);
+                    area(a).total_cmp(&area(b))
+                })
+                .map(|(i, _)| i)
+        });
+        self.image_hovered_detection = if response.hovered() {
+            pointer_hit
+        } else {
+            None
+        };
+        if response.hovered() {
+            self.hovered_detection = pointer_hit;
+        }
+
+        for (i, detection) in self.detections.iter().enumerate() {
+            let rect = Self::bbox_to_screen(&detection.bbox, image_rect);
+            let base_width = self.settings.draw_style.line_width as f32;
+            let (color, width) = if self.selected_detection == Some(i) {
+                (SELECTED_BOX_COLOR, base_width + 1.5)
+            } else if self.hovered_detection == Some(i) {
+                (HOVER_BOX_COLOR, base_width + 0.5)
+            } else {
+                (self.settings.box_color(), base_width)
+            };
+            match &detection.obb {
+                // 회전 박스는 꼭짓점을 이미지 픽셀 → 화면 좌표로 옮겨 다각형으로 그림
+                Some(obb) => {
+                    let points = obb
+                        .corners()
+                        .iter()
+                        .map(|&(x, y)| {
+                            image_rect.lerp_inside(egui::vec2(
+                                x / self.image_size.x,
+                                y / self.image_size.y,
+                            ))
+                        })
+                        .collect();
+                    painter.add(egui::Shape::closed_line(
+                        points,
+                        egui::Stroke::new(width, color),
+                    ));
+                }
+                None => {
+                    painter.rect_stroke(
+                        rect,
+                        0.0,
+                        egui::Stroke::new(width, color),
+                        egui::StrokeKind::Outside,
+                    );
+                }
+            }
+
+            if self.settings.show_labels
+                || self.selected_detection == Some(i)
+                || self.hovered_detection == Some(i)
+            {
+                painter.text(
+                    rect.left_top(),
+                    egui::Align2::LEFT_BOTTOM,
+                    format!(
+                        "{} {:.0}%",
+                        detection.class_name,
+                        detection.confidence * 100.0
+                    ),
+                    egui::FontId::proportional(self.settings.label_size),
+                    color,
+                );
+            }
+        }
+
+        // 포즈 모델의 키포인트와 스켈레톤
+        let style = &self.settings.draw_style;
+        for points in self
+            .detections
+            .iter()
+            .filter_map(|d| d.keypoints.as_deref())
+        {
+            let skeleton = Skeleton::resolve(self.skeleton.as_ref(), points.len());
+            let visible = |i: usize| {
+                points
+                    .get(i)
+                    .filter(|k| k.visibility >= style.min_keypoint_visibility)
+                    .map(|k| image_rect.lerp_inside(egui::vec2(k.x, k.y)))
+            };
+            for &(a, b) in &skeleton.edges {
+                if let (Some(pa), Some(pb)) = (visible(a), visible(b)) {
+                    painter.line_segment([pa, pb], egui::Stroke::new(2.0, SKELETON_COLOR));
+                }
+            }
+            for i in 0..points.len() {
+                if let Some(p) = visible(i) {
+                    painter.circle_filled(p, style.keypoint_radius as f32, KEYPOINT_COLOR);
+                }
+            }
+        }
+
+        if let Some(i) = pointer_hit {
+            let detection = &self.detections[i];
+            response.clone().on_hover_ui_at_pointer(|ui| {
+                ui.label(format!("#{} {}", i + 1, detection.class_name));
+                ui.label(format!("Confidence: {:.1}%", detection.confidence * 100.0));
+                for label in &detection.secondary {
+                    ui.label(format!(
+                        "{}: {} {:.1}%",
+                        label.stage,
+                        label.label,
+                        label.score * 100.0
+                    ));
                 }
             });
+        }
+
+        if response.clicked() {
+            self.selected_detection = pointer_hit;
+            self.scroll_to_selected = pointer_hit.is_some();
+        }
     }
 
     /// 빈 이미지 플레이스홀더 렌더링
@@ -185,82 +1572,436 @@ impl RfDetrApp {
         });
     }
 
+    /// 드래그 중 안내 오버레이 렌더링
+    fn render_drop_overlay(&self, ctx: &egui::Context) {
+        let hovered = ctx.input(|i| i.raw.hovered_files.len());
+        if hovered == 0 {
+            return;
+        }
+
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("file_drop_overlay"),
+        ));
+        let screen_rect = ctx.screen_rect();
+        painter.rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(160));
+        painter.text(
+            screen_rect.center(),
+            egui::Align2::CENTER_CENTER,
+            format!("Drop {} file(s) to add to the queue", hovered),
+            egui::FontId::proportional(24.0),
+            egui::Color32::WHITE,
+        );
+    }
+
+    /// 드롭된 파일을 대기열에 추가
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped = ctx.input(|i| i.raw.dropped_files.clone());
+        let mut rejected = Vec::new();
+
+        for file in dropped {
+            if let Some(path) = file.path {
+                if is_image_path(&path) {
+                    self.pending_images.push_back(ImageInput::File(path));
+                } else {
+                    rejected.push(path.display().to_string());
+                }
+            } else if let Some(bytes) = file.bytes {
+                self.pending_images.push_back(ImageInput::Bytes {
+                    name: file.name,
+                    data: bytes.to_vec(),
+                });
+            }
+        }
+
+        if !rejected.is_empty() {
+            self.error_message = Some(format!("Unsupported file(s): {}", rejected.join(", ")));
+        }
+    }
+
+    /// Ctrl/Cmd+V 처리: 클립보드 이미지 또는 이미지 파일 경로 붙여넣기
+    fn handle_clipboard_paste(&mut self, ctx: &egui::Context) {
+        let (pasted_text, paste_released) = ctx.input(|i| {
+            let text = i.events.iter().find_map(|e| match e {
+                egui::Event::Paste(text) => Some(text.clone()),
+                _ => None,
+            });
+            // 붙여넣기 단축키의 키 누름은 통합 계층에서 소비되므로 키 뗌으로 감지
+            let released = i.events.iter().any(|e| {
+                matches!(
+                    e,
+                    egui::Event::Key { key: egui::Key::V, pressed: false, modifiers, .. }
+                        if modifiers.command
+                )
+            });
+            (text, released)
+        });
+
+        if let Some(text) = pasted_text {
+            // 텍스트로 붙여넣은 이미지 파일 경로
+            for line in text.lines() {
+                let path = PathBuf::from(line.trim());
+                if is_image_path(&path) && path.is_file() {
+                    self.pending_images.push_back(ImageInput::File(path));
+                }
+            }
+        } else if paste_released {
+            // 클립보드에 이미지가 없으면 조용히 무시
+            let _ = self.paste_clipboard_image();
+        }
+    }
+
+    /// 클립보드 이미지를 PNG로 인코딩하여 대기열에 추가
+    fn paste_clipboard_image(&mut self) -> anyhow::Result<()> {
+        let mut clipboard = arboard::Clipboard::new()?;
+        let image = clipboard.get_image()?;
+        let rgba = image::RgbaImage::from_raw(
+            image.width as u32,
+            image.height as u32,
+            image.bytes.into_owned(),
+        )
+        .ok_or_else(|| anyhow::anyhow!("Invalid clipboard image data"))?;
+
+        let mut data = Vec::new();
+        rgba.write_to(
+            &mut std::io::Cursor::new(&mut data),
+            image::ImageFormat::Png,
+        )?;
+        self.pending_images.push_back(ImageInput::Bytes {
+            name: "clipboard.png".to_string(),
+            data,
+        });
+        Ok(())
+    }
+
     /// 이미지 파일 선택
-    fn select_image(&mut self, ctx: &egui::Context) {
+    fn select_image(&mut self) {
         if let Some(path) = rfd::FileDialog::new()
-            .add_filter("Image files", &["png", "jpg", "jpeg", "bmp", "webp"])
+            .add_filter("Image files", IMAGE_EXTENSIONS)
             .pick_file()
         {
-            self.selected_image_path = Some(path.clone());
-            self.process_image(ctx, path);
+            self.pending_images.push_back(ImageInput::File(path));
         }
     }
 
-    /// 이미지 처리
-    fn process_image(&mut self, ctx: &egui::Context, path: PathBuf) {
-        self.is_processing = true;
-        self.error_message = None;
-        self.processed_image = None;
-        self.detections.clear();
-        self.inference_time_ms = None;
-
-        // 이미지 파일 읽기
-        match fs::read(&path) {
-            Ok(image_data) => {
-                // 모델 캐시 초기화 (필요한 경우)
-                if self.model_cache.is_none() {
-                    match ModelCache::new() {
-                        Ok(cache) => {
-                            self.model_cache = Some(cache);
-                            println!("Model cache initialized");
-                        }
-                        Err(e) => {
-                            self.error_message = Some(format!("Failed to initialize model cache: {}", e));
-                            return;
-                        }
-                    }
-                }
+    /// 폴더 선택 후 썸네일 스트립 구성
+    fn select_folder(&mut self, ctx: &egui::Context) {
+        let mut dialog = rfd::FileDialog::new();
+        if let Some(dir) = &self.settings.last_folder {
+            dialog = dialog.set_directory(dir);
+        }
+        let Some(dir) = dialog.pick_folder() else {
+            return;
+        };
+        self.open_folder(ctx, dir);
+    }
+
+    /// 폴더의 이미지 목록으로 썸네일 스트립 구성 후 첫 이미지 열기
+    fn open_folder(&mut self, ctx: &egui::Context, dir: PathBuf) {
+        match list_folder_images(&dir) {
+            Ok(images) if images.is_empty() => {
+                self.error_message = Some(format!("No images found in {}", dir.display()));
+            }
+            Ok(images) => {
+                self.settings.last_folder = Some(dir);
+                self.folder_images = images;
+                self.folder_index = None;
+                self.thumbnails
+                    .get_or_insert_with(|| ThumbnailLoader::spawn(ctx.clone()))
+                    .clear();
+                self.open_folder_image(ctx, 0);
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to read folder: {}", e));
+            }
+        }
+    }
+
+    /// 폴더의 index번째 이미지 열기 (진행 중인 작업은 취소)
+    fn open_folder_image(&mut self, ctx: &egui::Context, index: usize) {
+        let Some(path) = self.folder_images.get(index).cloned() else {
+            return;
+        };
+        if self.folder_index == Some(index) && self.is_processing {
+            return;
+        }
+        if let (Some(worker), Some(job_id)) = (&self.worker, self.current_job) {
+            worker.cancel(job_id);
+            self.finish_job();
+        }
+        self.folder_index = Some(index);
+        self.process_image(ctx, ImageInput::File(path));
+    }
 
-                // 객체 검출 실행 (캐시된 모델 사용)
-                if let Some(cache) = &mut self.model_cache {
-                    match detect_objects_with_cache(&image_data, cache) {
-                        Ok(result) => {
-                            self.detections = result.detections;
-                            self.inference_time_ms = Some(result.inference_time_ms);
-                            self.load_texture(ctx, result.result_image);
+    /// 좌우 화살표 키로 이전/다음 이미지 이동
+    fn handle_folder_navigation(&mut self, ctx: &egui::Context) {
+        if self.folder_images.is_empty() || ctx.wants_keyboard_input() {
+            return;
+        }
+        let (left, right) = ctx.input(|i| {
+            (
+                i.key_pressed(egui::Key::ArrowLeft),
+                i.key_pressed(egui::Key::ArrowRight),
+            )
+        });
+        let current = self.folder_index.unwrap_or(0);
+        if left && current > 0 {
+            self.open_folder_image(ctx, current - 1);
+        } else if right && current + 1 < self.folder_images.len() {
+            self.open_folder_image(ctx, current + 1);
+        }
+    }
+
+    /// 썸네일 스트립 렌더링 (보이는 항목만 썸네일 로딩)
+    fn render_thumbnail_strip(&mut self, ui: &mut egui::Ui) {
+        let mut clicked = None;
+        let threshold = self.settings.confidence_threshold;
+        let hidden = &self.hidden_classes;
+        let thumb_size = egui::vec2(THUMBNAIL_SIZE as f32, THUMBNAIL_SIZE as f32);
+
+        egui::ScrollArea::horizontal()
+            .id_salt("scroll_area_thumbnails")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for (i, path) in self.folder_images.iter().enumerate() {
+                        let is_current = self.folder_index == Some(i);
+                        let cached = self.result_cache.get(path);
+
+                        let response = ui
+                            .vertical(|ui| {
+                                let (rect, response) =
+                                    ui.allocate_exact_size(thumb_size, egui::Sense::click());
+                                if ui.is_rect_visible(rect) {
+                                    let painter = ui.painter_at(rect);
+                                    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+                                    let thumbnail = self
+                                        .thumbnails
+                                        .as_mut()
+                                        .and_then(|t| t.get_or_request(path));
+                                    if let Some(texture) = thumbnail {
+                                        // 종횡비 유지하여 중앙 배치
+                                        let size = texture.size_vec2();
+                                        let scale =
+                                            (thumb_size.x / size.x).min(thumb_size.y / size.y);
+                                        let image_rect = egui::Rect::from_center_size(
+                                            rect.center(),
+                                            size * scale,
+                                        );
+                                        painter.image(
+                                            texture.id(),
+                                            image_rect,
+                                            egui::Rect::from_min_max(
+                                                egui::pos2(0.0, 0.0),
+                                                egui::pos2(1.0, 1.0),
+                                            ),
+                                            egui::Color32::WHITE,
+                                        );
+                                    } else {
+                                        let failed = self
+                                            .thumbnails
+                                            .as_ref()
+                                            .is_some_and(|t| t.is_failed(path));
+                                        painter.text(
+                                            rect.center(),
+                                            egui::Align2::CENTER_CENTER,
+                                            if failed { "⚠" } else { "…" },
+                                            egui::FontId::proportional(20.0),
+                                            ui.visuals().weak_text_color(),
+                                        );
+                                    }
+                                    if is_current {
+                                        painter.rect_stroke(
+                                            rect,
+                                            2.0,
+                                            egui::Stroke::new(3.0, SELECTED_BOX_COLOR),
+                                            egui::StrokeKind::Inside,
+                                        );
+                                    }
+                                }
+
+                                // 검출 수 (임계값/클래스 필터 적용)
+                                let count_label = match cached {
+                                    Some(c) => format!(
+                                        "{} objects",
+                                        c.candidates
+                                            .iter()
+                                            .filter(|d| passes_filter(d, threshold, hidden))
+                                            .count()
+                                    ),
+                                    None => "—".to_string(),
+                                };
+                                ui.label(egui::RichText::new(count_label).small());
+                                response
+                            })
+                            .inner;
+
+                        let file_name = path
+                            .file_name()
+                            .map(|f| f.to_string_lossy().into_owned())
+                            .unwrap_or_default();
+                        let response = response.on_hover_ui(|ui| {
+                            ui.label(&file_name);
+                            if let Some(c) = cached {
+                                let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+                                for d in c
+                                    .candidates
+                                    .iter()
+                                    .filter(|d| passes_filter(d, threshold, hidden))
+                                {
+                                    *counts.entry(d.class_name.as_str()).or_default() += 1;
+                                }
+                                for (class_name, count) in counts {
+                                    ui.label(format!("{}: {}", class_name, count));
+                                }
+                            }
+                        });
+                        if response.clicked() {
+                            clicked = Some(i);
                         }
-                        Err(e) => {
-                            self.error_message = Some(format!("Detection error: {}", e));
+                        if is_current && self.scroll_to_selected_thumbnail {
+                            response.scroll_to_me(Some(egui::Align::Center));
                         }
                     }
-                }
+                });
+            });
+        self.scroll_to_selected_thumbnail = false;
+
+        if let Some(i) = clicked {
+            self.open_folder_image(ui.ctx(), i);
+        }
+    }
+
+    /// 이미지 처리 요청 (백그라운드 워커에 제출)
+    ///
+    /// 이미 검출한 파일은 캐시된 결과를 사용하고 디코딩만 요청합니다.
+    fn process_image(&mut self, ctx: &egui::Context, input: ImageInput) {
+        // 이미지를 열면 라이브 모드 종료, 다른 이미지로 넘어가기 전에 편집 결과 반영
+        self.live = None;
+        self.finish_editing();
+        self.error_message = None;
+        self.selected_image_path = Some(input.display_path());
+
+        // 폴더 목록에 있는 파일이면 현재 위치 동기화
+        let key = input.cache_key();
+        if let Some(index) = key
+            .as_ref()
+            .and_then(|k| self.folder_images.iter().position(|p| p == k))
+        {
+            self.folder_index = Some(index);
+            self.scroll_to_selected_thumbnail = true;
+        }
+        let run_detection = key
+            .as_ref()
+            .is_none_or(|k| !self.result_cache.contains_key(k));
+        self.current_job_key = key;
+
+        if self.worker.is_none() {
+            let config = self.settings.model_config();
+            self.worker = Some(InferenceWorker::spawn(ctx.clone(), config.clone()));
+            self.applied_model = Some(config);
+        }
+        let worker = self.worker.as_ref().expect("worker spawned above");
+        match worker.submit(input, run_detection) {
+            Ok(job_id) => {
+                self.is_processing = true;
+                self.current_job = Some(job_id);
+                self.current_stage = None;
+                self.job_started_at = Some(std::time::Instant::now());
             }
             Err(e) => {
-                self.error_message = Some(format!("Failed to read file: {}", e));
+                self.error_message = Some(e.to_string());
+                self.worker = None;
             }
         }
+    }
 
-        self.is_processing = false;
+    /// 현재 작업 취소 및 대기열 비우기
+    fn cancel_processing(&mut self) {
+        self.pending_images.clear();
+        if let (Some(worker), Some(job_id)) = (&self.worker, self.current_job) {
+            worker.cancel(job_id);
+        }
+        self.finish_job();
     }
 
-    /// 텍스처 로딩
-    fn load_texture(&mut self, ctx: &egui::Context, result_image: image::RgbImage) {
-        let mut buffer = Vec::new();
-        if let Ok(()) = result_image.write_to(
-            &mut std::io::Cursor::new(&mut buffer),
-            image::ImageFormat::Png,
-        ) {
-            if let Ok(image) = image::load_from_memory(&buffer) {
-                let rgba = image.to_rgba8();
-                let size = [rgba.width() as _, rgba.height() as _];
+    /// 작업 상태 초기화
+    fn finish_job(&mut self) {
+        self.is_processing = false;
+        self.current_job = None;
+        self.current_job_key = None;
+        self.current_stage = None;
+        self.job_started_at = None;
+    }
 
-                // ColorImage 생성
-                let color_image = egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_raw());
+    /// 워커 이벤트 처리
+    fn poll_worker(&mut self, ctx: &egui::Context) {
+        let events = match &self.worker {
+            Some(worker) => worker.poll(),
+            None => return,
+        };
 
-                let texture = ctx.load_texture("processed_image", color_image, Default::default());
-                self.processed_image = Some(texture);
-                self.image_size = egui::vec2(size[0] as f32, size[1] as f32);
+        for event in events {
+            match event {
+                WorkerEvent::ModelLoaded(info) => self.model_info = Some(*info),
+                WorkerEvent::ModelFailed(error) => {
+                    self.model_info = None;
+                    self.error_message = Some(format!("Model load failed: {}", error));
+                }
+                // 취소되었거나 이전 작업의 이벤트는 무시
+                WorkerEvent::Stage { job_id, .. }
+                | WorkerEvent::Finished { job_id, .. }
+                | WorkerEvent::Failed { job_id, .. }
+                | WorkerEvent::Cancelled { job_id }
+                    if Some(job_id) != self.current_job => {}
+                WorkerEvent::Stage { stage, .. } => self.current_stage = Some(stage),
+                WorkerEvent::Finished { output, .. } => {
+                    // 새 검출 결과는 캐시에 저장, 디코딩만 한 경우 캐시에서 가져옴
+                    let detection = match (output.detection, &self.current_job_key) {
+                        (Some(detection), Some(key)) => {
+                            self.result_cache.insert(key.clone(), detection.clone());
+                            Some(detection)
+                        }
+                        (Some(detection), None) => Some(detection),
+                        (None, Some(key)) => self.result_cache.get(key).cloned(),
+                        (None, None) => None,
+                    };
+                    let detection = detection.unwrap_or(CachedDetection {
+                        candidates: Vec::new(),
+                        inference_time_ms: 0.0,
+                    });
+                    let key = self.current_job_key.clone();
+                    self.set_cached_candidates(key.as_ref(), detection.candidates);
+                    self.inference_time_ms = Some(detection.inference_time_ms);
+                    self.load_texture(ctx, &output.image);
+                    self.source_image = Some(output.image);
+                    self.displayed_key = self.current_job_key.clone();
+                    self.image_generation += 1;
+                    self.apply_filters();
+                    self.finish_job();
+                }
+                WorkerEvent::Failed { error, .. } => {
+                    self.error_message = Some(format!("Detection error: {}", error));
+                    self.processed_image = None;
+                    self.source_image = None;
+                    self.displayed_key = None;
+                    self.candidates.clear();
+                    self.detections.clear();
+                    self.inference_time_ms = None;
+                    self.finish_job();
+                }
+                WorkerEvent::Cancelled { .. } => self.finish_job(),
             }
         }
     }
+
+    /// 텍스처 로딩 (박스가 그려지지 않은 원본 이미지)
+    fn load_texture(&mut self, ctx: &egui::Context, image: &image::RgbImage) {
+        let size = [image.width() as _, image.height() as _];
+        let color_image = egui::ColorImage::from_rgb(size, image.as_raw());
+
+        let texture = ctx.load_texture("processed_image", color_image, Default::default());
+        self.processed_image = Some(texture);
+        self.image_size = egui::vec2(size[0] as f32, size[1] as f32);
+        self.image_view.set_mode(ViewMode::Fit);
+    }
 }
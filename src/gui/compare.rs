@@ -0,0 +1,500 @@
+use eframe::egui;
+use image::RgbImage;
+use rf_detr_onnx_test_lib::compare::DEFAULT_MATCH_IOU;
+use rf_detr_onnx_test_lib::{
+    diff_detections, run_inference_with_threshold, Detection, DetectionDiff, ModelCache,
+    ModelSource, MODEL_INPUT_SIZE,
+};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+
+// 상수 정의
+const FP16_MODEL_PATH: &str = "assets/models/model_fp16.onnx";
+const MATCHED_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 200, 0); // 초록색
+const CLASS_MISMATCH_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 230, 0); // 노란색
+const MISSING_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 0, 0); // 빨간색
+const EXTRA_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 0, 255); // 자홍색
+const MODEL_NAMES: [&str; 2] = ["A", "B"];
+
+type RunResult = anyhow::Result<(Vec<Detection>, f64)>;
+
+/// 모델별 추론 스레드 (세션은 스레드가 소유)
+///
+/// 스레드는 분리(detach)되어 있어, 러너를 버리면 대기 중인 요청은 건너뛰고
+/// 진행 중인 추론만 끝낸 뒤 종료합니다.
+struct ModelRunner {
+    request_tx: Option<Sender<(u64, Arc<RgbImage>)>>,
+    result_rx: Receiver<(u64, RunResult)>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ModelRunner {
+    fn spawn(ctx: egui::Context, source: ModelSource) -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<(u64, Arc<RgbImage>)>();
+        let (result_tx, result_rx) = mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let thread_cancelled = Arc::clone(&cancelled);
+        std::thread::Builder::new()
+            .name(format!("compare-{}", source.label()))
+            .spawn(move || {
+                let mut cache: Option<ModelCache> = None;
+                for (run_id, image) in request_rx {
+                    if thread_cancelled.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let result = (|| -> RunResult {
+                        if cache.is_none() {
+                            cache = Some(ModelCache::with_source(source.clone())?);
+                        }
+                        let cache = cache.as_mut().expect("model cache initialized above");
+                        // 임계값 0으로 모든 후보를 받아 UI의 필터를 그대로 적용
+                        run_inference_with_threshold(&image, cache, MODEL_INPUT_SIZE, 0.0)
+                    })();
+                    if result_tx.send((run_id, result)).is_err() {
+                        break;
+                    }
+                    ctx.request_repaint();
+                }
+            })
+            .expect("failed to spawn comparison thread");
+
+        Self {
+            request_tx: Some(request_tx),
+            result_rx,
+            cancelled,
+        }
+    }
+
+    fn submit(&self, run_id: u64, image: Arc<RgbImage>) -> bool {
+        self.request_tx
+            .as_ref()
+            .is_some_and(|tx| tx.send((run_id, image)).is_ok())
+    }
+}
+
+impl Drop for ModelRunner {
+    fn drop(&mut self) {
+        // 중단 신호를 보내고 요청 채널을 닫음 (스레드를 기다리지 않아 UI가 멈추지 않음)
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.request_tx.take();
+    }
+}
+
+/// 모델 하나의 비교 상태
+enum RunState {
+    Idle,
+    Running,
+    Done {
+        candidates: Vec<Detection>,
+        inference_time_ms: f64,
+    },
+    Failed(String),
+}
+
+/// 모델 선택 슬롯 (A: 기준, B: 비교 대상)
+struct ModelSlot {
+    source: ModelSource,
+    runner: Option<ModelRunner>,
+    state: RunState,
+}
+
+impl ModelSlot {
+    fn new(source: ModelSource) -> Self {
+        Self {
+            source,
+            runner: None,
+            state: RunState::Idle,
+        }
+    }
+
+    /// 모델 변경 (기존 세션은 폐기)
+    fn set_source(&mut self, source: ModelSource) {
+        if self.source != source {
+            self.source = source;
+            self.runner = None;
+            self.state = RunState::Idle;
+        }
+    }
+}
+
+/// 두 모델 비교 창
+///
+/// 두 모델을 각각 별도 스레드에 로드하여 현재 이미지에 실행하고, 결과를 나란히 표시합니다.
+/// 검출 차이는 A를 기준으로 한 IoU 매칭(`diff_detections`)으로 계산합니다.
+pub struct ModelComparison {
+    pub open: bool,
+    slots: [ModelSlot; 2],
+    run_id: u64,
+    compared_generation: Option<u64>,
+    iou_threshold: f32,
+    require_same_class: bool,
+}
+
+impl Default for ModelComparison {
+    fn default() -> Self {
+        // FP16 모델이 있으면 B의 기본값으로 사용
+        let fp16 = PathBuf::from(FP16_MODEL_PATH);
+        let second = if fp16.is_file() {
+            ModelSource::File(fp16)
+        } else {
+            ModelSource::Embedded
+        };
+        Self {
+            open: false,
+            slots: [
+                ModelSlot::new(ModelSource::Embedded),
+                ModelSlot::new(second),
+            ],
+            run_id: 0,
+            compared_generation: None,
+            iou_threshold: DEFAULT_MATCH_IOU,
+            require_same_class: true,
+        }
+    }
+}
+
+/// 필터를 통과한 검출 목록
+fn filtered(
+    state: &RunState,
+    filter: &dyn Fn(&Detection) -> bool,
+) -> Option<(Vec<Detection>, f64)> {
+    match state {
+        RunState::Done {
+            candidates,
+            inference_time_ms,
+        } => Some((
+            candidates.iter().filter(|d| filter(d)).cloned().collect(),
+            *inference_time_ms,
+        )),
+        _ => None,
+    }
+}
+
+impl ModelComparison {
+    /// 두 모델에 현재 이미지 추론 요청
+    fn run(&mut self, ctx: &egui::Context, image: &RgbImage, generation: u64) {
+        self.run_id += 1;
+        self.compared_generation = Some(generation);
+        let image = Arc::new(image.clone());
+        for slot in &mut self.slots {
+            let runner = slot
+                .runner
+                .get_or_insert_with(|| ModelRunner::spawn(ctx.clone(), slot.source.clone()));
+            slot.state = if runner.submit(self.run_id, Arc::clone(&image)) {
+                RunState::Running
+            } else {
+                RunState::Failed("Comparison worker stopped unexpectedly".to_string())
+            };
+        }
+    }
+
+    /// 도착한 결과 반영 (이전 실행 결과는 무시)
+    fn poll(&mut self) {
+        for slot in &mut self.slots {
+            let Some(runner) = &slot.runner else {
+                continue;
+            };
+            for (run_id, result) in runner.result_rx.try_iter() {
+                if run_id != self.run_id {
+                    continue;
+                }
+                slot.state = match result {
+                    Ok((candidates, inference_time_ms)) => RunState::Done {
+                        candidates,
+                        inference_time_ms,
+                    },
+                    Err(e) => RunState::Failed(e.to_string()),
+                };
+            }
+        }
+    }
+
+    /// 비교 창 렌더링
+    ///
+    /// `generation`은 표시 중인 이미지가 바뀔 때마다 증가하며, 창이 열려 있으면 자동으로 다시 실행합니다.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        texture: Option<&egui::TextureHandle>,
+        image: Option<&image::RgbImage>,
+        generation: u64,
+        filter: &dyn Fn(&Detection) -> bool,
+    ) {
+        if !self.open {
+            return;
+        }
+        self.poll();
+
+        let mut run_requested = false;
+        if let Some(image) = image {
+            if self.compared_generation != Some(generation) {
+                self.run(ctx, image, generation);
+            }
+        }
+
+        let mut open = self.open;
+        egui::Window::new("⚖ Compare models")
+            .open(&mut open)
+            .default_size([960.0, 640.0])
+            .show(ctx, |ui| {
+                run_requested = self.render_controls(ui, image.is_some());
+                ui.separator();
+
+                let a = filtered(&self.slots[0].state, filter);
+                let b = filtered(&self.slots[1].state, filter);
+                let diff = match (&a, &b) {
+                    (Some((a, _)), Some((b, _))) => Some(diff_detections(
+                        a,
+                        b,
+                        self.iou_threshold,
+                        self.require_same_class,
+                    )),
+                    _ => None,
+                };
+                if let (Some((a_dets, a_ms)), Some((b_dets, b_ms)), Some(diff)) = (&a, &b, &diff) {
+                    Self::render_summary(ui, a_dets.len(), b_dets.len(), *a_ms, *b_ms, diff);
+                    ui.separator();
+                }
+
+                ui.columns(2, |columns| {
+                    for (k, ui) in columns.iter_mut().enumerate() {
+                        let detections = if k == 0 { &a } else { &b };
+                        self.render_column(
+                            ui,
+                            k,
+                            texture,
+                            detections.as_ref().map(|d| &d.0),
+                            diff.as_ref(),
+                        );
+                    }
+                });
+            });
+        self.open = open;
+
+        if run_requested {
+            if let Some(image) = image {
+                self.run(ctx, image, generation);
+            }
+        }
+    }
+
+    /// 모델 선택, IoU 임계값, 실행 버튼 (다시 실행 요청 시 true)
+    fn render_controls(&mut self, ui: &mut egui::Ui, has_image: bool) -> bool {
+        let mut changed = false;
+        egui::Grid::new("compare_models_grid")
+            .num_columns(4)
+            .show(ui, |ui| {
+                for (k, slot) in self.slots.iter_mut().enumerate() {
+                    ui.strong(format!("Model {}", MODEL_NAMES[k]));
+                    ui.label(slot.source.label())
+                        .on_hover_text(format!("{:?}", slot.source));
+                    if ui.button("📂 Browse…").clicked() {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("ONNX model", &["onnx"])
+                            .pick_file()
+                        {
+                            slot.set_source(ModelSource::File(path));
+                            changed = true;
+                        }
+                    }
+                    if ui.button("Embedded").clicked() && slot.source != ModelSource::Embedded {
+                        slot.set_source(ModelSource::Embedded);
+                        changed = true;
+                    }
+                    ui.end_row();
+                }
+            });
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::Slider::new(&mut self.iou_threshold, 0.1..=0.95)
+                    .text("Match IoU")
+                    .fixed_decimals(2),
+            );
+            ui.checkbox(&mut self.require_same_class, "Same class only");
+            if ui
+                .add_enabled(has_image, egui::Button::new("▶ Run"))
+                .clicked()
+            {
+                changed = true;
+            }
+        });
+        changed
+    }
+
+    /// 지연 시간과 매칭 요약
+    fn render_summary(
+        ui: &mut egui::Ui,
+        a_count: usize,
+        b_count: usize,
+        a_ms: f64,
+        b_ms: f64,
+        diff: &DetectionDiff,
+    ) {
+        ui.horizontal(|ui| {
+            ui.label("⏱️ Latency:");
+            ui.colored_label(
+                egui::Color32::from_rgb(0, 150, 255),
+                format!(
+                    "A {:.2} ms, B {:.2} ms (Δ {:+.2} ms, B/A {:.2}×)",
+                    a_ms,
+                    b_ms,
+                    b_ms - a_ms,
+                    if a_ms > 0.0 { b_ms / a_ms } else { 0.0 }
+                ),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.label(format!("Detections: A {}, B {}", a_count, b_count));
+            ui.separator();
+            let mean_iou = diff
+                .mean_iou()
+                .map(|v| format!(", mean IoU {:.3}", v))
+                .unwrap_or_default();
+            ui.colored_label(
+                MATCHED_COLOR,
+                format!("Matched {}{}", diff.matched.len(), mean_iou),
+            );
+            if diff.class_mismatches() > 0 {
+                ui.colored_label(
+                    CLASS_MISMATCH_COLOR,
+                    format!("Class differs {}", diff.class_mismatches()),
+                );
+            }
+            ui.colored_label(
+                MISSING_COLOR,
+                format!("Missing in B {}", diff.missing.len()),
+            );
+            ui.colored_label(EXTRA_COLOR, format!("Extra in B {}", diff.extra.len()));
+        });
+    }
+
+    /// 모델 하나의 결과 열 (이미지와 차이 목록)
+    fn render_column(
+        &self,
+        ui: &mut egui::Ui,
+        k: usize,
+        texture: Option<&egui::TextureHandle>,
+        detections: Option<&Vec<Detection>>,
+        diff: Option<&DetectionDiff>,
+    ) {
+        let slot = &self.slots[k];
+        ui.heading(format!("{}: {}", MODEL_NAMES[k], slot.source.label()));
+        match &slot.state {
+            RunState::Idle => {
+                ui.label("Not run yet");
+            }
+            RunState::Running => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Running inference...");
+                });
+            }
+            RunState::Done {
+                inference_time_ms, ..
+            } => {
+                ui.colored_label(
+                    egui::Color32::from_rgb(0, 150, 255),
+                    format!(
+                        "{:.2} ms, {} detections",
+                        inference_time_ms,
+                        detections.map_or(0, |d| d.len())
+                    ),
+                );
+            }
+            RunState::Failed(error) => {
+                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+            }
+        }
+
+        let Some(texture) = texture else {
+            return;
+        };
+        let image_size = texture.size_vec2();
+        let max_size = egui::vec2(ui.available_width(), ui.available_height() * 0.7);
+        let scale = (max_size.x / image_size.x)
+            .min(max_size.y / image_size.y)
+            .max(0.0);
+        let (image_rect, response) =
+            ui.allocate_exact_size(image_size * scale, egui::Sense::hover());
+        let painter = ui.painter_at(image_rect);
+        painter.image(
+            texture.id(),
+            image_rect,
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            egui::Color32::WHITE,
+        );
+
+        let (Some(detections), Some(diff)) = (detections, diff) else {
+            return;
+        };
+
+        // A는 놓친 검출(빨강), B는 추가 검출(자홍)을 강조
+        let status = |i: usize| {
+            let pair = if k == 0 {
+                diff.match_for_reference(i)
+            } else {
+                diff.match_for_candidate(i)
+            };
+            match pair {
+                Some(pair) if pair.same_class => MATCHED_COLOR,
+                Some(_) => CLASS_MISMATCH_COLOR,
+                None if k == 0 => MISSING_COLOR,
+                None => EXTRA_COLOR,
+            }
+        };
+        let mut hovered = None;
+        for (i, detection) in detections.iter().enumerate() {
+            let [x1, y1, x2, y2] = detection.bbox;
+            let rect = egui::Rect::from_min_max(
+                image_rect.lerp_inside(egui::vec2(x1, y1)),
+                image_rect.lerp_inside(egui::vec2(x2, y2)),
+            );
+            let color = status(i);
+            painter.rect_stroke(
+                rect,
+                0.0,
+                egui::Stroke::new(1.5, color),
+                egui::StrokeKind::Outside,
+            );
+            if response.hover_pos().is_some_and(|p| rect.contains(p)) {
+                hovered = Some((i, detection, color));
+            }
+        }
+        if let Some((i, detection, color)) = hovered {
+            response.on_hover_ui_at_pointer(|ui| {
+                ui.colored_label(color, format!("#{} {}", i + 1, detection.class_name));
+                ui.label(format!("Confidence: {:.1}%", detection.confidence * 100.0));
+            });
+        }
+
+        // 한쪽에만 있는 검출 목록
+        let unmatched = if k == 0 { &diff.missing } else { &diff.extra };
+        let (title, color) = if k == 0 {
+            ("Missing in B", MISSING_COLOR)
+        } else {
+            ("Extra in B", EXTRA_COLOR)
+        };
+        ui.colored_label(color, format!("{} ({})", title, unmatched.len()));
+        egui::ScrollArea::vertical()
+            .id_salt(("compare_unmatched", k))
+            .show(ui, |ui| {
+                for &i in unmatched {
+                    let d = &detections[i];
+                    ui.label(format!(
+                        "#{} {} {:.1}% [{:.3}, {:.3}, {:.3}, {:.3}]",
+                        i + 1,
+                        d.class_name,
+                        d.confidence * 100.0,
+                        d.bbox[0],
+                        d.bbox[1],
+                        d.bbox[2],
+                        d.bbox[3]
+                    ));
+                }
+            });
+    }
+}
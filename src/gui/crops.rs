@@ -0,0 +1,198 @@
+use eframe::egui;
+use rf_detr_onnx_test_lib::crops::{save_crops, DEFAULT_CROP_PADDING, DEFAULT_MIN_CROP_SIZE};
+use rf_detr_onnx_test_lib::Detection;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+// 상수 정의
+const CROP_TILE_SIZE: f32 = 96.0;
+const MAX_PADDING: f32 = 1.0;
+
+/// 갤러리 한 칸 (검출 인덱스와 크롭 텍스처)
+struct CropTile {
+    index: usize,
+    confidence: f32,
+    texture: egui::TextureHandle,
+}
+
+/// 검출 영역 크롭 갤러리 창 (클래스별 그룹)
+pub struct CropGallery {
+    pub open: bool,
+    padding: f32,
+    /// 클래스 이름 → 크롭 목록
+    groups: BTreeMap<String, Vec<CropTile>>,
+    /// 갤러리를 만든 기준 (이미지 세대, 검출 목록, 패딩)
+    built_for: Option<(u64, Vec<Detection>, f32)>,
+    status: Option<String>,
+}
+
+impl Default for CropGallery {
+    fn default() -> Self {
+        Self {
+            open: false,
+            padding: DEFAULT_CROP_PADDING,
+            groups: BTreeMap::new(),
+            built_for: None,
+            status: None,
+        }
+    }
+}
+
+impl CropGallery {
+    /// 갤러리 창 렌더링 (크롭을 클릭하면 해당 검출 인덱스 반환)
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        image: Option<&image::RgbImage>,
+        generation: u64,
+        detections: &[Detection],
+        image_name: &str,
+        export_dir: &mut Option<PathBuf>,
+    ) -> Option<usize> {
+        if !self.open {
+            return None;
+        }
+        if let Some(image) = image {
+            self.rebuild_if_needed(ctx, image, generation, detections);
+        } else {
+            self.groups.clear();
+            self.built_for = None;
+        }
+
+        let mut clicked = None;
+        let mut open = self.open;
+        egui::Window::new("🖼 Crop gallery")
+            .open(&mut open)
+            .default_size([520.0, 480.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::Slider::new(&mut self.padding, 0.0..=MAX_PADDING)
+                            .text("Padding")
+                            .fixed_decimals(2),
+                    )
+                    .on_hover_text("Margin on each side as a fraction of the box size");
+                    if ui
+                        .add_enabled(
+                            image.is_some() && !detections.is_empty(),
+                            egui::Button::new("💾 Save crops…"),
+                        )
+                        .on_hover_text("Writes <folder>/<class>/<image>_<n>.png")
+                        .clicked()
+                    {
+                        if let Some(image) = image {
+                            self.save(image, detections, image_name, export_dir);
+                        }
+                    }
+                });
+                if let Some(status) = &self.status {
+                    ui.label(egui::RichText::new(status).small().weak());
+                }
+                ui.separator();
+
+                if self.groups.is_empty() {
+                    ui.label("No detections to crop.");
+                    return;
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for (class_name, tiles) in &self.groups {
+                        egui::CollapsingHeader::new(format!("{} ({})", class_name, tiles.len()))
+                            .id_salt(("crop_group", class_name))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                ui.horizontal_wrapped(|ui| {
+                                    for tile in tiles {
+                                        let response = ui
+                                            .add(
+                                                egui::Image::new(&tile.texture)
+                                                    .max_size(egui::vec2(
+                                                        CROP_TILE_SIZE,
+                                                        CROP_TILE_SIZE,
+                                                    ))
+                                                    .sense(egui::Sense::click()),
+                                            )
+                                            .on_hover_text(format!(
+                                                "#{} {:.1}%",
+                                                tile.index + 1,
+                                                tile.confidence * 100.0
+                                            ));
+                                        if response.clicked() {
+                                            clicked = Some(tile.index);
+                                        }
+                                    }
+                                });
+                            });
+                    }
+                });
+            });
+        self.open = open;
+        clicked
+    }
+
+    /// 이미지, 검출 목록 또는 패딩이 바뀌었으면 크롭 텍스처 다시 생성
+    fn rebuild_if_needed(
+        &mut self,
+        ctx: &egui::Context,
+        image: &image::RgbImage,
+        generation: u64,
+        detections: &[Detection],
+    ) {
+        let unchanged = self.built_for.as_ref().is_some_and(|(g, d, p)| {
+            *g == generation && d.as_slice() == detections && *p == self.padding
+        });
+        if unchanged {
+            return;
+        }
+
+        self.groups.clear();
+        for (index, detection) in detections.iter().enumerate() {
+            let Some(crop) = detection.crop(image, self.padding) else {
+                continue;
+            };
+            let size = [crop.width() as usize, crop.height() as usize];
+            let color_image = egui::ColorImage::from_rgb(size, crop.as_raw());
+            let texture =
+                ctx.load_texture(format!("crop_{}", index), color_image, Default::default());
+            self.groups
+                .entry(detection.class_name.clone())
+                .or_default()
+                .push(CropTile {
+                    index,
+                    confidence: detection.confidence,
+                    texture,
+                });
+        }
+        self.built_for = Some((generation, detections.to_vec(), self.padding));
+    }
+
+    /// 폴더를 골라 클래스별 하위 폴더에 크롭 저장
+    fn save(
+        &mut self,
+        image: &image::RgbImage,
+        detections: &[Detection],
+        image_name: &str,
+        export_dir: &mut Option<PathBuf>,
+    ) {
+        let mut dialog = rfd::FileDialog::new();
+        if let Some(dir) = export_dir.as_ref() {
+            dialog = dialog.set_directory(dir);
+        }
+        let Some(dir) = dialog.pick_folder() else {
+            return;
+        };
+        self.status = Some(
+            match save_crops(
+                image,
+                detections,
+                &dir,
+                Path::new(image_name),
+                self.padding,
+                DEFAULT_MIN_CROP_SIZE,
+            ) {
+                Ok(saved) => format!("Saved {} crops to {}", saved, dir.display()),
+                Err(e) => format!("Failed to save crops: {}", e),
+            },
+        );
+        *export_dir = Some(dir);
+    }
+}
@@ -0,0 +1,551 @@
+use eframe::egui;
+use rf_detr_onnx_test_lib::{class_label_map, ClassRemap, Detection};
+
+// 상수 정의
+const HANDLE_RADIUS: f32 = 4.0;
+const HANDLE_HIT_RADIUS: f32 = 8.0;
+const MIN_BOX_SIZE: f32 = 4.0; // 화면 픽셀, 새 박스와 모서리 조절 결과 모두
+const MAX_HISTORY: usize = 200;
+const HANDLE_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 230, 0); // 노란색
+const DRAW_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 200, 255); // 하늘색
+
+/// 진행 중인 드래그 동작
+enum DragKind {
+    /// 박스 모서리 이동 (0: 좌상, 1: 우상, 2: 우하, 3: 좌하)
+    Corner { index: usize, corner: usize },
+    /// 박스 전체 이동
+    Move { index: usize },
+    /// 새 박스 그리기 (정규화 시작 좌표)
+    Draw { start: egui::Pos2 },
+}
+
+struct DragState {
+    kind: DragKind,
+    /// 드래그 시작 전 상태 (변경 시 실행 취소 기록에 추가)
+    before: Vec<Detection>,
+}
+
+/// 검출 결과 수동 보정 상태 (편집 모드)
+///
+/// 편집 대상 `Vec<Detection>`은 앱이 소유하고, 편집기는 실행 취소/다시 실행 기록과
+/// 드래그 상태, 편집 시작 시점의 목록만 관리합니다.
+pub struct AnnotationEditor {
+    /// 편집 시작 시점에 보이던 검출 (편집 결과를 전체 후보에 합칠 때 사용)
+    original: Vec<Detection>,
+    undo_stack: Vec<Vec<Detection>>,
+    redo_stack: Vec<Vec<Detection>>,
+    drag: Option<DragState>,
+    /// 고를 수 있는 클래스 (ID, 이름), 재매핑이 있으면 재매핑 분류 체계
+    pub classes: Vec<(u32, String)>,
+    /// 편집 시작 시점의 클래스 재매핑 (직접 고른 클래스의 원본 ID 결정용)
+    remap: Option<ClassRemap>,
+    /// 새로 그리는 박스의 클래스 ID
+    pub new_box_class: u32,
+}
+
+/// 화면 좌표를 이미지 정규화 좌표(0-1)로 변환
+fn screen_to_normalized(pos: egui::Pos2, image_rect: egui::Rect) -> egui::Pos2 {
+    let uv = (pos - image_rect.min) / image_rect.size();
+    egui::pos2(uv.x.clamp(0.0, 1.0), uv.y.clamp(0.0, 1.0))
+}
+
+/// 모서리 번호에 해당하는 bbox 좌표 인덱스 (x, y)
+fn corner_indices(corner: usize) -> (usize, usize) {
+    match corner {
+        0 => (0, 1),
+        1 => (2, 1),
+        2 => (2, 3),
+        _ => (0, 3),
+    }
+}
+
+/// 모서리 이동 후 뒤집힌 좌표 정리
+fn normalize_bbox(bbox: &mut [f32; 4]) {
+    if bbox[0] > bbox[2] {
+        bbox.swap(0, 2);
+    }
+    if bbox[1] > bbox[3] {
+        bbox.swap(1, 3);
+    }
+}
+
+/// 클래스 ID에 해당하는 이름 (목록에 없으면 ID 문자열)
+pub fn class_name(classes: &[(u32, String)], class_id: u32) -> String {
+    classes
+        .iter()
+        .find(|(id, _)| *id == class_id)
+        .map(|(_, name)| name.clone())
+        .unwrap_or_else(|| format!("class_{}", class_id))
+}
+
+/// 클래스 목록에서 고르는 콤보 박스 (변경 시 true)
+pub fn class_combo(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    classes: &[(u32, String)],
+    class_id: &mut u32,
+) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(class_name(classes, *class_id))
+        .height(300.0)
+        .show_ui(ui, |ui| {
+            for (id, name) in classes {
+                changed |= ui.selectable_value(class_id, *id, name).changed();
+            }
+        });
+    changed
+}
+
+/// 이미지별 수동 보정 내역 (워커 원본 후보와 따로 보관)
+///
+/// 라벨 맵이나 재매핑을 바꾸면 원본 후보에서 목록을 다시 만든 뒤 보정 내역을 덧씌우므로,
+/// 이전 재매핑에서 제외되었던 검출도 되살아납니다.
+#[derive(Debug, Clone, Default)]
+pub struct CandidateEdits {
+    /// 편집으로 바뀌거나 지워진 원본 후보 (워커 출력 그대로)
+    removed: Vec<Detection>,
+    /// 편집으로 바뀌거나 새로 추가된 검출
+    edited: Vec<Detection>,
+}
+
+impl CandidateEdits {
+    /// 편집 후 전체 목록과 원본 후보를 비교하여 보정 내역 계산
+    ///
+    /// `relabel`은 현재 라벨 맵과 재매핑을 적용하는 함수입니다. 재매핑으로 제외되어
+    /// 편집 중 보이지 않던 원본 후보는 지운 것으로 보지 않습니다.
+    pub fn from_merged(
+        raw: &[Detection],
+        merged: &[Detection],
+        relabel: impl Fn(&mut Vec<Detection>),
+    ) -> Self {
+        // 원본 후보별 현재 라벨 적용 결과 (제외되었거나 편집 결과에 그대로 남은 후보는 None)
+        let mut unmatched: Vec<Option<Detection>> = raw
+            .iter()
+            .map(|d| {
+                let mut one = vec![d.clone()];
+                relabel(&mut one);
+                one.pop()
+            })
+            .collect();
+        let mut edited = Vec::new();
+        for detection in merged {
+            match unmatched.iter().position(|d| d.as_ref() == Some(detection)) {
+                Some(i) => unmatched[i] = None,
+                None => edited.push(detection.clone()),
+            }
+        }
+        let removed = raw
+            .iter()
+            .zip(&unmatched)
+            .filter(|(_, labelled)| labelled.is_some())
+            .map(|(d, _)| d.clone())
+            .collect();
+        Self { removed, edited }
+    }
+
+    /// 원본 후보에 보정 내역을 적용한 목록
+    ///
+    /// 편집된 검출도 원래 클래스(`original_class_id`)로 되돌린 뒤 현재 라벨 기준으로 다시 판정합니다.
+    pub fn apply(
+        &self,
+        raw: &[Detection],
+        relabel: impl Fn(&mut Vec<Detection>),
+    ) -> Vec<Detection> {
+        let mut removed: Vec<&Detection> = self.removed.iter().collect();
+        let mut candidates: Vec<Detection> = raw
+            .iter()
+            .filter(|d| match removed.iter().position(|r| r == d) {
+                Some(i) => {
+                    removed.swap_remove(i);
+                    false
+                }
+                None => true,
+            })
+            .cloned()
+            .collect();
+        relabel(&mut candidates);
+
+        let mut edited: Vec<Detection> = self
+            .edited
+            .iter()
+            .cloned()
+            .map(|mut d| {
+                if let Some(source) = d.original_class_id.take() {
+                    d.class_id = source;
+                }
+                d
+            })
+            .collect();
+        relabel(&mut edited);
+        candidates.extend(edited);
+        candidates
+    }
+}
+
+impl AnnotationEditor {
+    /// 현재 보이는 검출 목록으로 편집 시작
+    ///
+    /// 클래스 재매핑이 있으면 클래스 선택에 COCO 클래스 대신 `ClassRemap::categories`를 사용합니다.
+    pub fn new(detections: &[Detection], remap: Option<&ClassRemap>) -> Self {
+        let classes: Vec<(u32, String)> = match remap {
+            Some(remap) => remap.categories(),
+            None => class_label_map()
+                .into_iter()
+                .map(|(id, name)| (id, name.to_string()))
+                .collect(),
+        };
+        let new_box_class = classes.first().map_or(1, |(id, _)| *id);
+        Self {
+            original: detections.to_vec(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            drag: None,
+            classes,
+            remap: remap.cloned(),
+            new_box_class,
+        }
+    }
+
+    /// 직접 고른 클래스의 원본 ID
+    ///
+    /// 재매핑 중에는 다시 적용해도 같은 대상이 되도록 대표 원본 ID를 쓰고,
+    /// 재매핑이 없으면 고른 클래스 자체가 이후 재매핑의 원본이 되도록 None입니다.
+    fn original_class_id(&self, class_id: u32) -> Option<u32> {
+        self.remap
+            .as_ref()
+            .and_then(|remap| remap.source_id(class_id))
+    }
+
+    /// 편집 결과를 전체 후보 목록에 합침
+    ///
+    /// 편집 시작 때 보이던 검출만 편집 결과로 바꾸고, 임계값 미만이거나 숨긴 클래스라서
+    /// 보이지 않던 후보는 그대로 둡니다.
+    pub fn merge_into(&self, candidates: &[Detection], edited: &[Detection]) -> Vec<Detection> {
+        let mut replaced: Vec<&Detection> = self.original.iter().collect();
+        let mut merged = Vec::with_capacity(candidates.len() + edited.len());
+        for candidate in candidates {
+            match replaced.iter().position(|d| *d == candidate) {
+                Some(i) => {
+                    replaced.swap_remove(i);
+                }
+                None => merged.push(candidate.clone()),
+            }
+        }
+        merged.extend_from_slice(edited);
+        merged
+    }
+
+    /// 변경 직전 상태를 실행 취소 기록에 추가
+    fn push_undo(&mut self, before: Vec<Detection>) {
+        self.undo_stack.push(before);
+        if self.undo_stack.len() > MAX_HISTORY {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// 편집 기록이 있는지 여부
+    pub fn is_modified(&self) -> bool {
+        self.can_undo()
+    }
+
+    /// 실행 취소
+    pub fn undo(&mut self, detections: &mut Vec<Detection>) -> bool {
+        match self.undo_stack.pop() {
+            Some(previous) => {
+                self.redo_stack
+                    .push(std::mem::replace(detections, previous));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 다시 실행
+    pub fn redo(&mut self, detections: &mut Vec<Detection>) -> bool {
+        match self.redo_stack.pop() {
+            Some(next) => {
+                self.undo_stack.push(std::mem::replace(detections, next));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 박스 삭제 (오검출 제거)
+    pub fn delete(&mut self, detections: &mut Vec<Detection>, index: usize) {
+        if index < detections.len() {
+            self.push_undo(detections.clone());
+            detections.remove(index);
+        }
+    }
+
+    /// 박스 클래스 변경
+    pub fn set_class(&mut self, detections: &mut [Detection], index: usize, class_id: u32) {
+        let Some(detection) = detections.get(index) else {
+            return;
+        };
+        if detection.class_id == class_id {
+            return;
+        }
+        self.push_undo(detections.to_vec());
+        let detection = &mut detections[index];
+        detection.class_id = class_id;
+        detection.class_name = class_name(&self.classes, class_id);
+        detection.original_class_id = self.original_class_id(class_id);
+    }
+
+    /// 단축키 처리: Delete/Backspace 삭제, Ctrl/Cmd+Z 실행 취소, Ctrl/Cmd+Shift+Z 또는 Ctrl/Cmd+Y 다시 실행
+    pub fn handle_shortcuts(
+        &mut self,
+        ctx: &egui::Context,
+        detections: &mut Vec<Detection>,
+        selected: &mut Option<usize>,
+    ) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let (delete, undo, redo) = ctx.input_mut(|i| {
+            let redo = i.consume_key(
+                egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                egui::Key::Z,
+            ) || i.consume_key(egui::Modifiers::COMMAND, egui::Key::Y);
+            let undo = i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z);
+            let delete = i.consume_key(egui::Modifiers::NONE, egui::Key::Delete)
+                || i.consume_key(egui::Modifiers::NONE, egui::Key::Backspace);
+            (delete, undo, redo)
+        });
+
+        if delete {
+            if let Some(index) = selected.take() {
+                self.delete(detections, index);
+            }
+        }
+        if (undo && self.undo(detections)) || (redo && self.redo(detections)) {
+            *selected = None;
+        }
+    }
+
+    /// 이미지 패널 포인터 입력 처리 (모서리 드래그, 박스 이동, 새 박스 그리기)
+    pub fn handle_pointer(
+        &mut self,
+        ui: &egui::Ui,
+        response: &egui::Response,
+        image_rect: egui::Rect,
+        detections: &mut Vec<Detection>,
+        selected: &mut Option<usize>,
+    ) {
+        if response.drag_started_by(egui::PointerButton::Primary) {
+            let origin = ui.input(|i| i.pointer.press_origin());
+            if let Some(origin) = origin {
+                let kind = self.hit_test(origin, image_rect, detections, *selected);
+                if let DragKind::Move { index } | DragKind::Corner { index, .. } = kind {
+                    *selected = Some(index);
+                }
+                self.drag = Some(DragState {
+                    kind,
+                    before: detections.clone(),
+                });
+            }
+        }
+
+        let Some(drag) = &self.drag else {
+            return;
+        };
+
+        if response.dragged_by(egui::PointerButton::Primary) {
+            let delta = response.drag_delta() / image_rect.size();
+            let pointer = response
+                .interact_pointer_pos()
+                .map(|pos| screen_to_normalized(pos, image_rect));
+            match drag.kind {
+                DragKind::Corner { index, corner } => {
+                    if let (Some(p), Some(detection)) = (pointer, detections.get_mut(index)) {
+                        let (xi, yi) = corner_indices(corner);
+                        detection.bbox[xi] = p.x;
+                        detection.bbox[yi] = p.y;
+                        // 박스를 손으로 고치면 모델 마스크/키포인트/회전 박스는 더 이상 맞지 않음
+                        detection.mask = None;
+                        detection.keypoints = None;
+                        detection.obb = None;
+                    }
+                }
+                DragKind::Move { index } => {
+                    if let Some(detection) = detections.get_mut(index) {
+                        detection.mask = None;
+                        detection.keypoints = None;
+                        detection.obb = None;
+                        let bbox = &mut detection.bbox;
+                        // 이미지 밖으로 나가지 않도록 이동량 제한
+                        let dx = delta.x.max(-bbox[0]).min(1.0 - bbox[2]);
+                        let dy = delta.y.max(-bbox[1]).min(1.0 - bbox[3]);
+                        bbox[0] += dx;
+                        bbox[2] += dx;
+                        bbox[1] += dy;
+                        bbox[3] += dy;
+                    }
+                }
+                DragKind::Draw { .. } => {}
+            }
+        }
+
+        if response.drag_stopped() {
+            let Some(drag) = self.drag.take() else {
+                return;
+            };
+            match drag.kind {
+                DragKind::Corner { index, .. } | DragKind::Move { index } => {
+                    if let Some(detection) = detections.get_mut(index) {
+                        normalize_bbox(&mut detection.bbox);
+                        // 모서리를 맞붙여 면적이 없어진 박스는 드래그 전으로 되돌림
+                        let [x1, y1, x2, y2] = detection.bbox;
+                        let screen_size = egui::vec2(x2 - x1, y2 - y1) * image_rect.size();
+                        if screen_size.x < MIN_BOX_SIZE || screen_size.y < MIN_BOX_SIZE {
+                            *detections = drag.before;
+                            return;
+                        }
+                    }
+                    if *detections != drag.before {
+                        self.push_undo(drag.before);
+                    }
+                }
+                DragKind::Draw { start } => {
+                    let end = ui
+                        .input(|i| i.pointer.interact_pos())
+                        .map(|pos| screen_to_normalized(pos, image_rect));
+                    let Some(end) = end else {
+                        return;
+                    };
+                    let rect = egui::Rect::from_two_pos(start, end);
+                    let screen_size = rect.size() * image_rect.size();
+                    if screen_size.x < MIN_BOX_SIZE || screen_size.y < MIN_BOX_SIZE {
+                        return;
+                    }
+                    self.push_undo(drag.before);
+                    // 수동으로 추가한 박스는 신뢰도 1.0
+                    detections.push(Detection {
+                        bbox: [rect.min.x, rect.min.y, rect.max.x, rect.max.y],
+                        confidence: 1.0,
+                        class_id: self.new_box_class,
+                        class_name: class_name(&self.classes, self.new_box_class),
+                        mask: None,
+                        keypoints: None,
+                        obb: None,
+                        secondary: Vec::new(),
+                        original_class_id: self.original_class_id(self.new_box_class),
+                    });
+                    *selected = Some(detections.len() - 1);
+                }
+            }
+        }
+    }
+
+    /// 드래그 시작 지점이 가리키는 대상 판정
+    fn hit_test(
+        &self,
+        pos: egui::Pos2,
+        image_rect: egui::Rect,
+        detections: &[Detection],
+        selected: Option<usize>,
+    ) -> DragKind {
+        let to_screen = |bbox: &[f32; 4]| {
+            egui::Rect::from_min_max(
+                image_rect.lerp_inside(egui::vec2(bbox[0], bbox[1])),
+                image_rect.lerp_inside(egui::vec2(bbox[2], bbox[3])),
+            )
+        };
+
+        // 선택된 박스의 모서리 핸들 우선
+        if let Some(index) = selected.filter(|i| *i < detections.len()) {
+            let rect = to_screen(&detections[index].bbox);
+            let corners = [
+                rect.left_top(),
+                rect.right_top(),
+                rect.right_bottom(),
+                rect.left_bottom(),
+            ];
+            if let Some(corner) = corners
+                .iter()
+                .position(|c| c.distance(pos) <= HANDLE_HIT_RADIUS)
+            {
+                return DragKind::Corner { index, corner };
+            }
+        }
+
+        // 포인터 아래 박스 중 가장 작은 박스 이동
+        let hit = detections
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| to_screen(&d.bbox).contains(pos))
+            .min_by(|(_, a), (_, b)| {
+                let area = |d: &Detection| (d.bbox[2] - d.bbox[0]) * (d.bbox[3] - d.bbox[1]);
+                area(a).total_cmp(&area(b))
+            })
+            .map(|(i, _)| i);
+        match hit {
+            Some(index) => DragKind::Move { index },
+            None => DragKind::Draw {
+                start: screen_to_normalized(pos, image_rect),
+            },
+        }
+    }
+
+    /// 선택된 박스의 모서리 핸들과 그리는 중인 박스 표시
+    pub fn paint(
+        &self,
+        ui: &egui::Ui,
+        painter: &egui::Painter,
+        image_rect: egui::Rect,
+        detections: &[Detection],
+        selected: Option<usize>,
+    ) {
+        if let Some(detection) = selected.and_then(|i| detections.get(i)) {
+            let [x1, y1, x2, y2] = detection.bbox;
+            for (x, y) in [(x1, y1), (x2, y1), (x2, y2), (x1, y2)] {
+                let center = image_rect.lerp_inside(egui::vec2(x, y));
+                painter.circle(
+                    center,
+                    HANDLE_RADIUS,
+                    HANDLE_COLOR,
+                    egui::Stroke::new(1.0, egui::Color32::BLACK),
+                );
+            }
+        }
+
+        if let Some(DragState {
+            kind: DragKind::Draw { start },
+            ..
+        }) = &self.drag
+        {
+            if let Some(pos) = ui.input(|i| i.pointer.interact_pos()) {
+                let end = screen_to_normalized(pos, image_rect);
+                let rect = egui::Rect::from_two_pos(
+                    image_rect.lerp_inside(start.to_vec2()),
+                    image_rect.lerp_inside(end.to_vec2()),
+                );
+                painter.rect_stroke(
+                    rect,
+                    0.0,
+                    egui::Stroke::new(2.0, DRAW_COLOR),
+                    egui::StrokeKind::Outside,
+                );
+                painter.text(
+                    rect.left_top(),
+                    egui::Align2::LEFT_BOTTOM,
+                    class_name(&self.classes, self.new_box_class),
+                    egui::FontId::proportional(14.0),
+                    DRAW_COLOR,
+                );
+            }
+        }
+    }
+}
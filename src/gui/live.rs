@@ -0,0 +1,252 @@
+use super::settings::ModelConfig;
+use eframe::egui;
+use image::RgbImage;
+use rf_detr_onnx_test_lib::{
+    run_inference_with_threshold, Detection, FpsMeter, Frame, FrameSource, LatestFrame, ModelCache,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// 상수 정의
+const FRAME_WAIT: Duration = Duration::from_millis(100);
+
+/// 추론이 끝난 라이브 프레임
+pub struct LiveFrame {
+    pub image: RgbImage,
+    /// 쿼리별 최고 점수 후보 (임계값 미적용)
+    pub candidates: Vec<Detection>,
+    pub inference_time_ms: f64,
+    /// 캡처부터 추론 완료까지 걸린 시간
+    pub latency_ms: f64,
+}
+
+enum LiveEvent {
+    Frame(Box<LiveFrame>),
+    /// 소스가 건너뛴 입력 등 세션은 계속되는 문제
+    Warning(String),
+    Failed(String),
+    Ended,
+}
+
+/// 라이브 모드 세션
+///
+/// 캡처 스레드는 `FrameSource`에서 프레임을 읽어 `LatestFrame`에 게시하고, 추론 스레드는
+/// 가장 최근 프레임만 가져가 검출합니다. 추론이 느리면 중간 프레임은 버려집니다.
+/// 두 스레드는 분리(detach)되어 있어 세션을 멈춰도 UI는 진행 중인 추론을 기다리지 않습니다.
+pub struct LiveSession {
+    name: String,
+    latest: Arc<LatestFrame>,
+    stop: Arc<AtomicBool>,
+    event_rx: Receiver<LiveEvent>,
+    fps: FpsMeter,
+    frames_processed: u64,
+    last_latency_ms: f64,
+    last_inference_ms: f64,
+    error: Option<String>,
+    warnings: Vec<String>,
+    finished: bool,
+}
+
+impl LiveSession {
+    /// 캡처/추론 스레드 시작
+    pub fn start(
+        ctx: egui::Context,
+        mut source: Box<dyn FrameSource>,
+        config: ModelConfig,
+    ) -> Self {
+        let name = source.name();
+        let latest = Arc::new(LatestFrame::new());
+        let stop = Arc::new(AtomicBool::new(false));
+        let (event_tx, event_rx) = mpsc::channel();
+
+        let send = {
+            let ctx = ctx.clone();
+            move |tx: &Sender<LiveEvent>, event: LiveEvent| {
+                let _ = tx.send(event);
+                ctx.request_repaint();
+            }
+        };
+
+        {
+            let latest = Arc::clone(&latest);
+            let stop = Arc::clone(&stop);
+            let event_tx = event_tx.clone();
+            let send = send.clone();
+            std::thread::Builder::new()
+                .name("live-capture".to_string())
+                .spawn(move || {
+                    let mut index = 0;
+                    let mut next_due = Instant::now();
+                    while !stop.load(Ordering::SeqCst) {
+                        let next = source.next_frame();
+                        for warning in source.take_warnings() {
+                            send(&event_tx, LiveEvent::Warning(warning));
+                        }
+                        match next {
+                            Ok(Some(image)) => {
+                                latest.publish(Frame {
+                                    image,
+                                    index,
+                                    captured_at: Instant::now(),
+                                });
+                                index += 1;
+                            }
+                            Ok(None) => {
+                                send(&event_tx, LiveEvent::Ended);
+                                break;
+                            }
+                            Err(e) => {
+                                send(&event_tx, LiveEvent::Failed(e.to_string()));
+                                break;
+                            }
+                        }
+
+                        // 소스 프레임 간격 유지 (밀린 경우 기준 시각 재설정)
+                        if let Some(interval) = source.frame_interval() {
+                            next_due += interval;
+                            let now = Instant::now();
+                            if next_due > now {
+                                std::thread::sleep(next_due - now);
+                            } else {
+                                next_due = now;
+                            }
+                        }
+                    }
+                    latest.close();
+                })
+                .expect("failed to spawn live capture thread");
+        }
+
+        {
+            let latest = Arc::clone(&latest);
+            let stop = Arc::clone(&stop);
+            std::thread::Builder::new()
+                .name("live-inference".to_string())
+                .spawn(move || {
+                    let mut cache = match ModelCache::with_options(config.source, config.options) {
+                        Ok(cache) => cache,
+                        Err(e) => {
+                            send(&event_tx, LiveEvent::Failed(e.to_string()));
+                            return;
+                        }
+                    };
+                    while !stop.load(Ordering::SeqCst) {
+                        let Some(frame) = latest.take(FRAME_WAIT) else {
+                            if latest.is_closed() {
+                                break;
+                            }
+                            continue;
+                        };
+                        // 임계값 0으로 모든 후보를 받아 UI 필터를 그대로 적용
+                        match run_inference_with_threshold(
+                            &frame.image,
+                            &mut cache,
+                            config.input_size,
+                            0.0,
+                        ) {
+                            Ok((candidates, inference_time_ms)) => {
+                                let latency_ms = frame.captured_at.elapsed().as_secs_f64() * 1000.0;
+                                send(
+                                    &event_tx,
+                                    LiveEvent::Frame(Box::new(LiveFrame {
+                                        image: frame.image,
+                                        candidates,
+                                        inference_time_ms,
+                                        latency_ms,
+                                    })),
+                                );
+                            }
+                            Err(e) => {
+                                send(&event_tx, LiveEvent::Failed(e.to_string()));
+                                break;
+                            }
+                        }
+                    }
+                })
+                .expect("failed to spawn live inference thread");
+        }
+
+        Self {
+            name,
+            latest,
+            stop,
+            event_rx,
+            fps: FpsMeter::default(),
+            frames_processed: 0,
+            last_latency_ms: 0.0,
+            last_inference_ms: 0.0,
+            error: None,
+            warnings: Vec::new(),
+            finished: false,
+        }
+    }
+
+    /// 도착한 결과 중 가장 최근 프레임 반환 (통계 갱신)
+    pub fn poll(&mut self) -> Option<LiveFrame> {
+        let mut newest = None;
+        for event in self.event_rx.try_iter() {
+            match event {
+                LiveEvent::Frame(frame) => {
+                    self.fps.tick(Instant::now());
+                    self.frames_processed += 1;
+                    self.last_latency_ms = frame.latency_ms;
+                    self.last_inference_ms = frame.inference_time_ms;
+                    newest = Some(*frame);
+                }
+                LiveEvent::Warning(warning) => self.warnings.push(warning),
+                LiveEvent::Failed(error) => {
+                    self.error = Some(error);
+                    self.finished = true;
+                }
+                LiveEvent::Ended => self.finished = true,
+            }
+        }
+        newest
+    }
+
+    /// 소스가 끝났거나 오류로 중단되었는지 여부
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// 중단 원인 (소스가 정상 종료된 경우 None)
+    pub fn take_error(&mut self) -> Option<String> {
+        self.error.take()
+    }
+
+    /// 세션을 멈추지 않은 경고 (가져가면 비워짐)
+    pub fn take_warnings(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// 상태 표시 (FPS, 지연 시간, 버려진 프레임 수)
+    pub fn render_status(&self, ui: &mut egui::Ui) {
+        ui.colored_label(egui::Color32::RED, "● LIVE")
+            .on_hover_text(&self.name);
+        ui.colored_label(
+            egui::Color32::from_rgb(0, 150, 255),
+            format!(
+                "{:.1} FPS | latency {:.0} ms | inference {:.0} ms",
+                self.fps.fps(),
+                self.last_latency_ms,
+                self.last_inference_ms
+            ),
+        );
+        ui.label(format!(
+            "frames {} | dropped {}",
+            self.frames_processed,
+            self.latest.dropped()
+        ))
+        .on_hover_text("Frames skipped because inference fell behind the source");
+    }
+}
+
+impl Drop for LiveSession {
+    fn drop(&mut self) {
+        // 중단 신호만 보내고 기다리지 않음 (진행 중인 추론이 끝나면 스레드가 스스로 종료)
+        self.stop.store(true, Ordering::SeqCst);
+        self.latest.close();
+    }
+}
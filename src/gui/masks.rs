@@ -0,0 +1,86 @@
+use eframe::egui;
+use rf_detr_onnx_test_lib::Detection;
+
+// 상수 정의
+/// 오버레이 텍스처의 긴 변 최대 크기 (큰 이미지에서 필터 변경 시 재생성 비용 제한)
+const MAX_OVERLAY_SIZE: u32 = 1024;
+
+/// 인스턴스 마스크 반투명 오버레이 텍스처
+///
+/// 마스크는 픽셀 단위라 egui 도형으로 그릴 수 없으므로, 검출 목록이나 스타일이 바뀔 때만
+/// RGBA 텍스처를 다시 만들어 원본 이미지 위에 겹쳐 그립니다.
+#[derive(Default)]
+pub struct MaskOverlay {
+    texture: Option<egui::TextureHandle>,
+    /// 텍스처를 만든 기준 (이미지 세대, 박스 목록, 색상, 불투명도)
+    built_for: Option<(u64, Vec<[f32; 4]>, [u8; 3], f32)>,
+}
+
+impl MaskOverlay {
+    /// 필요하면 텍스처를 다시 만들고 반환 (마스크가 없으면 None)
+    pub fn update(
+        &mut self,
+        ctx: &egui::Context,
+        generation: u64,
+        detections: &[Detection],
+        color: [u8; 3],
+        alpha: f32,
+    ) -> Option<&egui::TextureHandle> {
+        let key = (
+            generation,
+            detections.iter().map(|d| d.bbox).collect::<Vec<_>>(),
+            color,
+            alpha,
+        );
+        if self.built_for.as_ref() != Some(&key) {
+            self.texture = Self::build(ctx, detections, color, alpha);
+            self.built_for = Some(key);
+        }
+        self.texture.as_ref()
+    }
+
+    fn build(
+        ctx: &egui::Context,
+        detections: &[Detection],
+        color: [u8; 3],
+        alpha: f32,
+    ) -> Option<egui::TextureHandle> {
+        if alpha <= 0.0 {
+            return None;
+        }
+        let masks: Vec<_> = detections.iter().filter_map(|d| d.mask.as_ref()).collect();
+        let (image_width, image_height) = masks.first()?.image_size();
+        let scale = (MAX_OVERLAY_SIZE as f32 / image_width.max(image_height) as f32).min(1.0);
+        let width = ((image_width as f32 * scale).round() as u32).max(1);
+        let height = ((image_height as f32 * scale).round() as u32).max(1);
+
+        let fill = egui::Color32::from_rgba_unmultiplied(
+            color[0],
+            color[1],
+            color[2],
+            (alpha.clamp(0.0, 1.0) * 255.0) as u8,
+        );
+        let mut image = egui::ColorImage::filled(
+            [width as usize, height as usize],
+            egui::Color32::TRANSPARENT,
+        );
+        for mask in masks {
+            // 마스크 영역에 해당하는 오버레이 픽셀만 순회
+            let [rx, ry, rw, rh] = mask.region();
+            let x_start = (rx as f32 * scale).floor() as u32;
+            let y_start = (ry as f32 * scale).floor() as u32;
+            let x_end = (((rx + rw) as f32 * scale).ceil() as u32).min(width);
+            let y_end = (((ry + rh) as f32 * scale).ceil() as u32).min(height);
+            for y in y_start..y_end {
+                let my = ((y as f32 + 0.5) / scale) as u32;
+                for x in x_start..x_end {
+                    let mx = ((x as f32 + 0.5) / scale) as u32;
+                    if mask.contains(mx, my) {
+                        image.pixels[(y * width + x) as usize] = fill;
+                    }
+                }
+            }
+        }
+        Some(ctx.load_texture("mask_overlay", image, egui::TextureOptions::LINEAR))
+    }
+}
@@ -0,0 +1,414 @@
+use eframe::egui;
+use rf_detr_onnx_test_lib::{
+    DrawStyle, ModelSource, OrientationMode, SessionOptions, CONFIDENCE_THRESHOLD, MODEL_INPUT_SIZE,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+// 상수 정의
+/// 모델 출력에서 파싱하는 최대 쿼리 수와 같음
+const MAX_TOP_K: usize = 100;
+const MIN_INPUT_SIZE: u32 = 32;
+const MAX_INPUT_SIZE: u32 = 2048;
+const MAX_THREADS: usize = 64;
+
+/// 추론 세션 구성 (바뀌면 워커가 세션을 다시 만듦)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelConfig {
+    pub source: ModelSource,
+    pub options: SessionOptions,
+    pub input_size: u32,
+    /// 디코딩 시 EXIF 방향 처리 (바뀌면 세션은 그대로 두고 재검출만 수행)
+    pub orientation: OrientationMode,
+    /// 검출 후 실행할 분류 단계 설정 파일 (`PipelineConfig`의 `stages`만 사용)
+    pub pipeline: Option<PathBuf>,
+}
+
+impl ModelConfig {
+    /// 검출 결과가 달라지는 변경인지 여부 (스레드 수만 바뀌면 같은 결과)
+    pub fn changes_results(&self, other: &ModelConfig) -> bool {
+        self.source != other.source
+            || self.input_size != other.input_size
+            || self.orientation != other.orientation
+            || self.pipeline != other.pipeline
+    }
+}
+
+/// eframe 저장소에 보관되는 GUI 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub model_source: ModelSource,
+    pub session_options: SessionOptions,
+    /// 모델 입력 크기 (동적 입력을 지원하는 모델에서만 변경 가능)
+    pub input_size: u32,
+    /// 휴대폰 사진의 EXIF 방향 태그 적용 여부
+    pub apply_exif_orientation: bool,
+    pub confidence_threshold: f32,
+    /// 임계값 통과 후 신뢰도 상위 k개만 표시
+    pub top_k: usize,
+    pub draw_style: DrawStyle,
+    /// 모든 박스에 클래스 라벨 표시 (끄면 선택/호버한 박스만)
+    pub show_labels: bool,
+    pub label_size: f32,
+    /// 클래스 이름 재정의 파일 (`LabelMap::from_file`)
+    pub label_map_path: Option<PathBuf>,
+    /// 클래스 재매핑 설정 파일 (`ClassRemap::from_file`)
+    pub class_remap_path: Option<PathBuf>,
+    /// 키포인트 스켈레톤 파일 (`Skeleton::from_json_file`, 그리기와 COCO 내보내기에 사용)
+    pub skeleton_path: Option<PathBuf>,
+    /// 2단계 분류 파이프라인 설정 파일 (`PipelineConfig::from_json_file`)
+    pub pipeline_path: Option<PathBuf>,
+    pub last_folder: Option<PathBuf>,
+    pub last_export_dir: Option<PathBuf>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            model_source: ModelSource::Embedded,
+            session_options: SessionOptions::default(),
+            input_size: MODEL_INPUT_SIZE,
+            apply_exif_orientation: true,
+            confidence_threshold: CONFIDENCE_THRESHOLD,
+            top_k: MAX_TOP_K,
+            draw_style: DrawStyle {
+                line_width: 2,
+                ..DrawStyle::default()
+            },
+            show_labels: false,
+            label_size: 14.0,
+            label_map_path: None,
+            class_remap_path: None,
+            skeleton_path: None,
+            pipeline_path: None,
+            last_folder: None,
+            last_export_dir: None,
+        }
+    }
+}
+
+/// 설정 패널에서 발생한 변경 (모델 구성 변경은 앱이 매 프레임 비교하여 반영)
+#[derive(Debug, Default)]
+pub struct SettingsChanges {
+    /// 임계값/top-k 변경 (재추론 없이 필터만 다시 적용)
+    pub filters: bool,
+    /// 라벨 맵 또는 클래스 재매핑 파일 변경
+    pub label_map: bool,
+    /// 스켈레톤 파일 변경
+    pub skeleton: bool,
+}
+
+impl Settings {
+    /// 현재 설정의 모델 구성
+    pub fn model_config(&self) -> ModelConfig {
+        ModelConfig {
+            source: self.model_source.clone(),
+            options: self.session_options,
+            input_size: self.input_size,
+            orientation: if self.apply_exif_orientation {
+                OrientationMode::Apply
+            } else {
+                OrientationMode::Raw
+            },
+            pipeline: self.pipeline_path.clone(),
+        }
+    }
+
+    /// 모델 구성을 설정에 되돌림 (구성 변경을 취소할 때 사용)
+    pub fn set_model_config(&mut self, config: &ModelConfig) {
+        self.model_source = config.source.clone();
+        self.session_options = config.options;
+        self.input_size = config.input_size;
+        self.apply_exif_orientation = config.orientation == OrientationMode::Apply;
+        self.pipeline_path = config.pipeline.clone();
+    }
+
+    /// 박스 색상 (egui)
+    pub fn box_color(&self) -> egui::Color32 {
+        let [r, g, b] = self.draw_style.color;
+        egui::Color32::from_rgb(r, g, b)
+    }
+
+    /// 설정 패널 렌더링
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        label_map_status: &str,
+        class_remap_status: &str,
+        skeleton_status: &str,
+    ) -> SettingsChanges {
+        let mut changes = SettingsChanges::default();
+
+        egui::CollapsingHeader::new("Model")
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Model:");
+                    ui.strong(self.model_source.label())
+                        .on_hover_text(format!("{:?}", self.model_source));
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("📂 Browse…").clicked() {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("ONNX model", &["onnx"])
+                            .pick_file()
+                        {
+                            self.model_source = ModelSource::File(path);
+                        }
+                    }
+                    if ui.button("Embedded").clicked() {
+                        self.model_source = ModelSource::Embedded;
+                    }
+                });
+                egui::Grid::new("settings_model_grid").num_columns(2).show(ui, |ui| {
+                    ui.label("Input size")
+                        .on_hover_text("Only models with dynamic input support sizes other than 560");
+                    ui.add(
+                        egui::DragValue::new(&mut self.input_size)
+                            .range(MIN_INPUT_SIZE..=MAX_INPUT_SIZE)
+                            .speed(32.0),
+                    );
+                    ui.end_row();
+                    ui.label("Intra-op threads");
+                    ui.add(egui::DragValue::new(&mut self.session_options.intra_threads).range(1..=MAX_THREADS));
+                    ui.end_row();
+                    ui.label("Inter-op threads");
+                    ui.add(egui::DragValue::new(&mut self.session_options.inter_threads).range(1..=MAX_THREADS));
+                    ui.end_row();
+                });
+                ui.checkbox(&mut self.apply_exif_orientation, "Apply EXIF orientation")
+                    .on_hover_text("Rotate phone photos as other viewers show them; off keeps the stored pixel orientation");
+                ui.label(
+                    egui::RichText::new("Model and thread changes rebuild the session automatically.")
+                        .small()
+                        .weak(),
+                );
+            });
+
+        egui::CollapsingHeader::new("Detection")
+            .default_open(true)
+            .show(ui, |ui| {
+                changes.filters |= ui
+                    .add(
+                        egui::Slider::new(&mut self.confidence_threshold, 0.05..=0.95)
+                            .text("Confidence")
+                            .fixed_decimals(2),
+                    )
+                    .changed();
+                changes.filters |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.top_k)
+                            .range(1..=MAX_TOP_K)
+                            .prefix("Top-k: "),
+                    )
+                    .changed();
+            });
+
+        egui::CollapsingHeader::new("Drawing")
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Box color");
+                    ui.color_edit_button_srgb(&mut self.draw_style.color);
+                });
+                ui.add(
+                    egui::Slider::new(&mut self.draw_style.line_width, 1..=10).text("Line width"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut self.draw_style.mask_alpha, 0.0..=1.0)
+                        .text("Mask opacity")
+                        .fixed_decimals(2),
+                )
+                .on_hover_text("Instance masks from segmentation models (0 hides them)");
+                ui.add(
+                    egui::Slider::new(&mut self.draw_style.keypoint_radius, 1..=10)
+                        .text("Keypoint radius"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut self.draw_style.min_keypoint_visibility, 0.0..=1.0)
+                        .text("Min keypoint visibility")
+                        .fixed_decimals(2),
+                )
+                .on_hover_text(
+                    "Keypoints below this visibility score (and their skeleton lines) are hidden",
+                );
+                ui.checkbox(&mut self.show_labels, "Show labels on all boxes");
+                ui.add(egui::Slider::new(&mut self.label_size, 8.0..=32.0).text("Label size"));
+            });
+
+        egui::CollapsingHeader::new("Label map")
+            .default_open(true)
+            .show(ui, |ui| {
+                let path = self
+                    .label_map_path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "Default (COCO)".to_string());
+                ui.label(path);
+                ui.label(egui::RichText::new(label_map_status).small().weak());
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("📂 Load…")
+                        .on_hover_text("JSON object {\"1\": \"person\"} or one name per line (line N = class ID N)")
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Label map", &["json", "txt"])
+                            .pick_file()
+                        {
+                            self.label_map_path = Some(path);
+                            changes.label_map = true;
+                        }
+                    }
+                    if ui
+                        .add_enabled(self.label_map_path.is_some(), egui::Button::new("Clear"))
+                        .clicked()
+                    {
+                        self.label_map_path = None;
+                        changes.label_map = true;
+                    }
+                });
+            });
+
+        egui::CollapsingHeader::new("Class remap")
+            .default_open(true)
+            .show(ui, |ui| {
+                let path = self
+                    .class_remap_path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "None (model classes)".to_string());
+                ui.label(path);
+                ui.label(egui::RichText::new(class_remap_status).small().weak());
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("📂 Load…")
+                        .on_hover_text(
+                            "JSON {\"classes\": [{\"name\": \"vehicle\", \"sources\": [\"car\", \"truck\", \"bus\"]}]}",
+                        )
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Class remap", &["json"])
+                            .pick_file()
+                        {
+                            self.class_remap_path = Some(path);
+                            changes.label_map = true;
+                        }
+                    }
+                    if ui
+                        .add_enabled(self.class_remap_path.is_some(), egui::Button::new("Clear"))
+                        .clicked()
+                    {
+                        self.class_remap_path = None;
+                        changes.label_map = true;
+                    }
+                });
+            });
+
+        egui::CollapsingHeader::new("Skeleton")
+            .default_open(false)
+            .show(ui, |ui| {
+                let path = self
+                    .skeleton_path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "Default (COCO for 17 keypoints)".to_string());
+                ui.label(path);
+                ui.label(egui::RichText::new(skeleton_status).small().weak());
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("📂 Load…")
+                        .on_hover_text(
+                            "JSON {\"names\": [...], \"edges\": [[0, 1], ...], \"flip_pairs\": [[1, 2], ...]}",
+                        )
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Skeleton", &["json"])
+                            .pick_file()
+                        {
+                            self.skeleton_path = Some(path);
+                            changes.skeleton = true;
+                        }
+                    }
+                    if ui
+                        .add_enabled(self.skeleton_path.is_some(), egui::Button::new("Clear"))
+                        .clicked()
+                    {
+                        self.skeleton_path = None;
+                        changes.skeleton = true;
+                    }
+                });
+            });
+
+        egui::CollapsingHeader::new("Classifier pipeline")
+            .default_open(false)
+            .show(ui, |ui| {
+                let path = self
+                    .pipeline_path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "None (detection only)".to_string());
+                ui.label(path);
+                ui.label(
+                    egui::RichText::new(
+                        "Runs the file's classifier stages on each detection; hover a box to see the labels.",
+                    )
+                    .small()
+                    .weak(),
+                );
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("📂 Load…")
+                        .on_hover_text("PipelineConfig JSON; its detector settings are ignored in favour of the Model section")
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Pipeline", &["json"])
+                            .pick_file()
+                        {
+                            self.pipeline_path = Some(path);
+                        }
+                    }
+                    if ui
+                        .add_enabled(self.pipeline_path.is_some(), egui::Button::new("Clear"))
+                        .clicked()
+                    {
+                        self.pipeline_path = None;
+                    }
+                });
+            });
+
+        egui::CollapsingHeader::new("Folders").show(ui, |ui| {
+            let show = |p: &Option<PathBuf>| {
+                p.as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "—".to_string())
+            };
+            ui.label(format!("Last folder: {}", show(&self.last_folder)));
+            ui.label(format!(
+                "Last export folder: {}",
+                show(&self.last_export_dir)
+            ));
+        });
+
+        ui.separator();
+        if ui.button("↺ Reset to defaults").clicked() {
+            // 최근 폴더 기록은 유지
+            let (last_folder, last_export_dir) =
+                (self.last_folder.take(), self.last_export_dir.take());
+            *self = Settings {
+                last_folder,
+                last_export_dir,
+                ..Settings::default()
+            };
+            changes.filters = true;
+            changes.label_map = true;
+            changes.skeleton = true;
+        }
+
+        changes
+    }
+}
@@ -0,0 +1,286 @@
+use eframe::egui;
+use rf_detr_onnx_test_lib::Detection;
+use std::collections::BTreeMap;
+
+// 상수 정의
+const HISTOGRAM_BINS: usize = 20;
+const HISTOGRAM_HEIGHT: f32 = 60.0;
+const HISTOGRAM_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 150, 255);
+const THRESHOLD_LINE_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 80, 80);
+
+/// 클래스별 검출 통계
+pub struct ClassSummary {
+    pub class_name: String,
+    pub count: usize,
+    pub mean_confidence: f32,
+    pub max_confidence: f32,
+}
+
+/// 클래스별 개수/평균/최대 신뢰도 (개수 많은 순, 같으면 이름 순)
+pub fn summarize(detections: &[Detection]) -> Vec<ClassSummary> {
+    let mut by_class: BTreeMap<&str, (usize, f32, f32)> = BTreeMap::new();
+    for detection in detections {
+        let entry = by_class.entry(detection.class_name.as_str()).or_default();
+        entry.0 += 1;
+        entry.1 += detection.confidence;
+        entry.2 = entry.2.max(detection.confidence);
+    }
+    let mut summaries: Vec<ClassSummary> = by_class
+        .into_iter()
+        .map(|(class_name, (count, sum, max))| ClassSummary {
+            class_name: class_name.to_string(),
+            count,
+            mean_confidence: sum / count as f32,
+            max_confidence: max,
+        })
+        .collect();
+    summaries.sort_by(|a, b| b.count.cmp(&a.count));
+    summaries
+}
+
+/// 신뢰도 구간별 개수 ([0, 1]을 `bins`개로 균등 분할)
+pub fn confidence_histogram(detections: &[Detection], bins: usize) -> Vec<usize> {
+    let mut counts = vec![0; bins.max(1)];
+    let last = counts.len() - 1;
+    for detection in detections {
+        let bin = (detection.confidence.clamp(0.0, 1.0) * counts.len() as f32) as usize;
+        counts[bin.min(last)] += 1;
+    }
+    counts
+}
+
+/// 정규화 좌표 기준 박스 면적
+fn bbox_area(detection: &Detection) -> f32 {
+    (detection.bbox[2] - detection.bbox[0]).max(0.0)
+        * (detection.bbox[3] - detection.bbox[1]).max(0.0)
+}
+
+/// 검출 목록 정렬 기준
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortKey {
+    #[default]
+    Score,
+    Class,
+    Area,
+}
+
+impl SortKey {
+    const ALL: [SortKey; 3] = [SortKey::Score, SortKey::Class, SortKey::Area];
+
+    fn label(self) -> &'static str {
+        match self {
+            SortKey::Score => "Score",
+            SortKey::Class => "Class",
+            SortKey::Area => "Area",
+        }
+    }
+}
+
+/// 검출 목록 보기 상태 (정렬, 검색어, 클래스 필터)
+pub struct DetectionListView {
+    pub sort_key: SortKey,
+    pub descending: bool,
+    pub query: String,
+    /// 요약 표에서 고른 클래스 (이름이 정확히 같은 검출만 표시, 검색어와 별개)
+    pub class_filter: Option<String>,
+}
+
+impl Default for DetectionListView {
+    fn default() -> Self {
+        Self {
+            sort_key: SortKey::Score,
+            descending: true,
+            query: String::new(),
+            class_filter: None,
+        }
+    }
+}
+
+impl DetectionListView {
+    /// 정렬 기준/방향 선택 및 검색 상자 렌더링
+    pub fn toolbar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Sort:");
+            for key in SortKey::ALL {
+                if ui
+                    .add(egui::Button::selectable(self.sort_key == key, key.label()))
+                    .clicked()
+                {
+                    if self.sort_key == key {
+                        self.descending = !self.descending;
+                    } else {
+                        self.sort_key = key;
+                        // 클래스는 가나다순, 점수/면적은 큰 순이 기본
+                        self.descending = key != SortKey::Class;
+                    }
+                }
+            }
+            let arrow = if self.descending { "⏷" } else { "⏶" };
+            if ui
+                .small_button(arrow)
+                .on_hover_text("Reverse order")
+                .clicked()
+            {
+                self.descending = !self.descending;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.query)
+                    .hint_text("🔍 Search class or #number")
+                    .desired_width(ui.available_width() - 30.0),
+            );
+            if ui
+                .add_enabled(!self.query.is_empty(), egui::Button::new("✖").small())
+                .clicked()
+            {
+                self.query.clear();
+            }
+        });
+        if let Some(class_name) = &self.class_filter {
+            let mut clear = false;
+            ui.horizontal(|ui| {
+                ui.label(format!("Class: {}", class_name));
+                clear = ui
+                    .small_button("✖")
+                    .on_hover_text("Show all classes")
+                    .clicked();
+            });
+            if clear {
+                self.class_filter = None;
+            }
+        }
+    }
+
+    /// 요약 표에서 클릭한 클래스로 필터 전환 (같은 클래스를 다시 누르면 해제)
+    pub fn toggle_class_filter(&mut self, class_name: String) {
+        if self.class_filter.as_deref() == Some(class_name.as_str()) {
+            self.class_filter = None;
+        } else {
+            self.class_filter = Some(class_name);
+        }
+    }
+
+    /// 검색어에 맞는 검출의 인덱스 (정렬 순서)
+    ///
+    /// 검색어는 클래스 이름 부분 일치(대소문자 무시) 또는 `#번호`(목록 번호)입니다.
+    /// 클래스 필터가 있으면 이름이 정확히 같은 검출로 추가로 제한합니다.
+    pub fn visible_indices(&self, detections: &[Detection]) -> Vec<usize> {
+        let query = self.query.trim().to_lowercase();
+        let number = query
+            .strip_prefix('#')
+            .and_then(|n| n.parse::<usize>().ok());
+        let mut indices: Vec<usize> = (0..detections.len())
+            .filter(|&i| {
+                self.class_filter
+                    .as_ref()
+                    .is_none_or(|class_name| detections[i].class_name == *class_name)
+            })
+            .filter(|&i| match number {
+                Some(n) => i + 1 == n,
+                None => {
+                    query.is_empty() || detections[i].class_name.to_lowercase().contains(&query)
+                }
+            })
+            .collect();
+
+        indices.sort_by(|&a, &b| {
+            let (da, db) = (&detections[a], &detections[b]);
+            let ordering = match self.sort_key {
+                SortKey::Score => da.confidence.total_cmp(&db.confidence),
+                SortKey::Class => da.class_name.cmp(&db.class_name),
+                SortKey::Area => bbox_area(da).total_cmp(&bbox_area(db)),
+            };
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        indices
+    }
+}
+
+/// 클래스별 요약 표 (클릭한 클래스 이름을 반환하여 클래스 필터로 사용)
+pub fn summary_table(ui: &mut egui::Ui, detections: &[Detection]) -> Option<String> {
+    let mut clicked = None;
+    egui::Grid::new("class_summary_grid")
+        .num_columns(4)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.strong("Class");
+            ui.strong("Count");
+            ui.strong("Mean");
+            ui.strong("Max");
+            ui.end_row();
+            for summary in summarize(detections) {
+                if ui
+                    .link(&summary.class_name)
+                    .on_hover_text("Show only this class in the list")
+                    .clicked()
+                {
+                    clicked = Some(summary.class_name.clone());
+                }
+                ui.label(summary.count.to_string());
+                ui.label(format!("{:.1}%", summary.mean_confidence * 100.0));
+                ui.label(format!("{:.1}%", summary.max_confidence * 100.0));
+                ui.end_row();
+            }
+        });
+    clicked
+}
+
+/// 신뢰도 히스토그램 (후보 전체 분포와 현재 임계값 표시)
+pub fn histogram(ui: &mut egui::Ui, candidates: &[Detection], threshold: f32) {
+    let counts = confidence_histogram(candidates, HISTOGRAM_BINS);
+    let max_count = counts.iter().copied().max().unwrap_or(0).max(1);
+
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), HISTOGRAM_HEIGHT),
+        egui::Sense::hover(),
+    );
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    let bin_width = rect.width() / counts.len() as f32;
+    for (i, &count) in counts.iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        // 적은 개수도 보이도록 로그 스케일
+        let height = (count as f32).ln_1p() / (max_count as f32).ln_1p() * rect.height();
+        let x = rect.left() + i as f32 * bin_width;
+        painter.rect_filled(
+            egui::Rect::from_min_max(
+                egui::pos2(x + 1.0, rect.bottom() - height),
+                egui::pos2(x + bin_width - 1.0, rect.bottom()),
+            ),
+            0.0,
+            HISTOGRAM_COLOR,
+        );
+    }
+
+    let threshold_x = rect.left() + threshold.clamp(0.0, 1.0) * rect.width();
+    painter.vline(
+        threshold_x,
+        rect.y_range(),
+        egui::Stroke::new(1.5, THRESHOLD_LINE_COLOR),
+    );
+
+    if let Some(pos) = response.hover_pos() {
+        let bin = (((pos.x - rect.left()) / bin_width) as usize).min(counts.len() - 1);
+        let step = 100.0 / counts.len() as f32;
+        response.on_hover_text_at_pointer(format!(
+            "{:.0}–{:.0}%: {} candidates",
+            bin as f32 * step,
+            (bin + 1) as f32 * step,
+            counts[bin]
+        ));
+    }
+    ui.horizontal(|ui| {
+        ui.small("0%");
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            ui.small("100%");
+        });
+    });
+}
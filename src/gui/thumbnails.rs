@@ -0,0 +1,98 @@
+use eframe::egui;
+use rf_detr_onnx_test_lib::decode_image;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+
+// 상수 정의
+pub const THUMBNAIL_SIZE: u32 = 96;
+
+/// 썸네일 로딩 상태
+enum ThumbnailState {
+    Requested,
+    Ready(egui::TextureHandle),
+    Failed,
+}
+
+/// 백그라운드 썸네일 로더
+///
+/// 추론 워커와 별도 스레드에서 디코딩/축소하여 검출 작업을 지연시키지 않습니다.
+pub struct ThumbnailLoader {
+    request_tx: Sender<PathBuf>,
+    result_rx: Receiver<(PathBuf, Option<image::RgbImage>)>,
+    states: HashMap<PathBuf, ThumbnailState>,
+}
+
+impl ThumbnailLoader {
+    /// 로더 스레드 시작
+    pub fn spawn(ctx: egui::Context) -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<PathBuf>();
+        let (result_tx, result_rx) = mpsc::channel();
+
+        std::thread::Builder::new()
+            .name("thumbnail-loader".to_string())
+            .spawn(move || {
+                for path in request_rx {
+                    // 본 이미지와 같은 방향으로 보이도록 EXIF 방향 적용
+                    let thumbnail = std::fs::read(&path)
+                        .ok()
+                        .and_then(|data| decode_image(&data).ok())
+                        .map(|img| {
+                            image::DynamicImage::ImageRgb8(img)
+                                .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+                                .to_rgb8()
+                        });
+                    if result_tx.send((path, thumbnail)).is_err() {
+                        break;
+                    }
+                    ctx.request_repaint();
+                }
+            })
+            .expect("failed to spawn thumbnail loader thread");
+
+        Self {
+            request_tx,
+            result_rx,
+            states: HashMap::new(),
+        }
+    }
+
+    /// 완료된 썸네일을 텍스처로 업로드
+    pub fn poll(&mut self, ctx: &egui::Context) {
+        for (path, thumbnail) in self.result_rx.try_iter() {
+            let state = match thumbnail {
+                Some(img) => {
+                    let size = [img.width() as usize, img.height() as usize];
+                    let color_image = egui::ColorImage::from_rgb(size, img.as_raw());
+                    let name = format!("thumbnail:{}", path.display());
+                    ThumbnailState::Ready(ctx.load_texture(name, color_image, Default::default()))
+                }
+                None => ThumbnailState::Failed,
+            };
+            self.states.insert(path, state);
+        }
+    }
+
+    /// 썸네일 텍스처 가져오기 (없으면 로딩 요청)
+    pub fn get_or_request(&mut self, path: &Path) -> Option<&egui::TextureHandle> {
+        if !self.states.contains_key(path) {
+            let _ = self.request_tx.send(path.to_path_buf());
+            self.states
+                .insert(path.to_path_buf(), ThumbnailState::Requested);
+        }
+        match self.states.get(path) {
+            Some(ThumbnailState::Ready(texture)) => Some(texture),
+            _ => None,
+        }
+    }
+
+    /// 로딩 실패 여부
+    pub fn is_failed(&self, path: &Path) -> bool {
+        matches!(self.states.get(path), Some(ThumbnailState::Failed))
+    }
+
+    /// 캐시된 썸네일 모두 제거 (폴더 변경 시)
+    pub fn clear(&mut self) {
+        self.states.clear();
+    }
+}
@@ -0,0 +1,214 @@
+use eframe::egui;
+
+// 상수 정의
+const MIN_ZOOM: f32 = 0.02;
+const MAX_ZOOM: f32 = 32.0;
+const WHEEL_ZOOM_SPEED: f32 = 0.002;
+const MINIMAP_MAX_SIDE: f32 = 160.0;
+const MINIMAP_MARGIN: f32 = 10.0;
+
+/// 이미지 표시 모드
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewMode {
+    /// 이미지 전체가 보이도록 맞춤
+    Fit,
+    /// 패널을 빈틈없이 채움 (일부 잘림)
+    Fill,
+    /// 원본 크기 (100%)
+    Actual,
+    /// 사용자가 확대/이동한 상태
+    Custom,
+}
+
+/// 이미지 패널의 확대/이동 상태
+///
+/// `pan`은 패널 중심 대비 이미지 중심의 화면 좌표 오프셋입니다.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageView {
+    pub mode: ViewMode,
+    zoom: f32,
+    pan: egui::Vec2,
+    /// 마지막으로 그려진 패널 영역 (툴바 버튼 확대 기준)
+    last_viewport: egui::Rect,
+}
+
+impl Default for ImageView {
+    fn default() -> Self {
+        Self {
+            mode: ViewMode::Fit,
+            zoom: 1.0,
+            pan: egui::Vec2::ZERO,
+            last_viewport: egui::Rect::NOTHING,
+        }
+    }
+}
+
+impl ImageView {
+    /// 표시 모드 변경 (이동 상태 초기화)
+    pub fn set_mode(&mut self, mode: ViewMode) {
+        self.mode = mode;
+        self.pan = egui::Vec2::ZERO;
+    }
+
+    /// 현재 모드 기준 확대 배율
+    pub fn zoom(&self, viewport: egui::Rect, image_size: egui::Vec2) -> f32 {
+        if image_size.x <= 0.0 || image_size.y <= 0.0 {
+            return 1.0;
+        }
+        let sx = viewport.width() / image_size.x;
+        let sy = viewport.height() / image_size.y;
+        match self.mode {
+            ViewMode::Fit => sx.min(sy),
+            ViewMode::Fill => sx.max(sy),
+            ViewMode::Actual => 1.0,
+            ViewMode::Custom => self.zoom,
+        }
+    }
+
+    /// 이미지가 그려질 화면 좌표 사각형
+    pub fn image_rect(&self, viewport: egui::Rect, image_size: egui::Vec2) -> egui::Rect {
+        let zoom = self.zoom(viewport, image_size);
+        egui::Rect::from_center_size(viewport.center() + self.pan, image_size * zoom)
+    }
+
+    /// 현재 상태를 사용자 지정 모드로 고정
+    fn enter_custom_mode(&mut self, viewport: egui::Rect, image_size: egui::Vec2) {
+        if self.mode != ViewMode::Custom {
+            self.zoom = self.zoom(viewport, image_size);
+            self.mode = ViewMode::Custom;
+        }
+    }
+
+    /// 화면 좌표 `anchor` 아래의 이미지 지점을 고정한 채 확대/축소
+    pub fn zoom_at(
+        &mut self,
+        factor: f32,
+        anchor: egui::Pos2,
+        viewport: egui::Rect,
+        image_size: egui::Vec2,
+    ) {
+        self.enter_custom_mode(viewport, image_size);
+        let new_zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        let factor = new_zoom / self.zoom;
+        let to_anchor = anchor - viewport.center();
+        self.pan = to_anchor - (to_anchor - self.pan) * factor;
+        self.zoom = new_zoom;
+    }
+
+    /// 화면 좌표 기준 이동
+    pub fn pan_by(&mut self, delta: egui::Vec2, viewport: egui::Rect, image_size: egui::Vec2) {
+        self.enter_custom_mode(viewport, image_size);
+        self.pan += delta;
+    }
+
+    /// 이미지의 정규화 좌표(0-1) 지점이 패널 중앙에 오도록 이동
+    pub fn center_on(&mut self, uv: egui::Vec2, viewport: egui::Rect, image_size: egui::Vec2) {
+        self.enter_custom_mode(viewport, image_size);
+        self.pan = (egui::vec2(0.5, 0.5) - uv) * image_size * self.zoom;
+    }
+
+    /// 마지막 패널 기준 현재 확대 배율
+    pub fn current_zoom(&self, image_size: egui::Vec2) -> f32 {
+        self.zoom(self.last_viewport, image_size)
+    }
+
+    /// 패널 중앙 기준 단계 확대/축소 (툴바 버튼)
+    pub fn zoom_step(&mut self, factor: f32, image_size: egui::Vec2) {
+        let viewport = self.last_viewport;
+        if viewport.is_positive() {
+            self.zoom_at(factor, viewport.center(), viewport, image_size);
+        }
+    }
+
+    /// 입력 처리: 휠 확대(커서 기준), 드래그 이동
+    ///
+    /// 가운데/오른쪽 버튼 드래그는 항상 이동하며, `primary_drag_pans`가 false이면
+    /// 왼쪽 버튼 드래그는 이동에 사용하지 않습니다 (편집 모드).
+    pub fn handle_input(
+        &mut self,
+        ui: &egui::Ui,
+        response: &egui::Response,
+        image_size: egui::Vec2,
+        primary_drag_pans: bool,
+    ) {
+        let viewport = response.rect;
+        self.last_viewport = viewport;
+
+        if let Some(pointer) = response.hover_pos() {
+            let (scroll, pinch) = ui.input(|i| (i.smooth_scroll_delta.y, i.zoom_delta()));
+            let factor = (scroll * WHEEL_ZOOM_SPEED).exp() * pinch;
+            if (factor - 1.0).abs() > f32::EPSILON {
+                self.zoom_at(factor, pointer, viewport, image_size);
+            }
+        }
+
+        let panning = response.dragged_by(egui::PointerButton::Middle)
+            || response.dragged_by(egui::PointerButton::Secondary)
+            || (primary_drag_pans && response.dragged_by(egui::PointerButton::Primary));
+        if panning {
+            self.pan_by(response.drag_delta(), viewport, image_size);
+        }
+    }
+
+    /// 이미지가 패널보다 클 때 우측 하단에 미니맵 표시 (클릭/드래그로 이동)
+    pub fn render_minimap(
+        &mut self,
+        ui: &mut egui::Ui,
+        texture: &egui::TextureHandle,
+        viewport: egui::Rect,
+        image_size: egui::Vec2,
+    ) {
+        let image_rect = self.image_rect(viewport, image_size);
+        if viewport.contains_rect(image_rect) {
+            return;
+        }
+
+        let scale = MINIMAP_MAX_SIDE / image_size.x.max(image_size.y);
+        let map_size = image_size * scale;
+        let map_rect = egui::Rect::from_min_size(
+            viewport.max - map_size - egui::vec2(MINIMAP_MARGIN, MINIMAP_MARGIN),
+            map_size,
+        );
+
+        let response = ui.interact(
+            map_rect,
+            ui.id().with("image_minimap"),
+            egui::Sense::click_and_drag(),
+        );
+
+        let painter = ui.painter_at(viewport);
+        painter.rect_filled(
+            map_rect.expand(2.0),
+            2.0,
+            egui::Color32::from_black_alpha(180),
+        );
+        painter.image(
+            texture.id(),
+            map_rect,
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            egui::Color32::from_white_alpha(220),
+        );
+
+        // 현재 보이는 영역 표시
+        let visible = viewport.intersect(image_rect);
+        let to_map = |p: egui::Pos2| {
+            let uv = (p - image_rect.min) / image_rect.size();
+            map_rect.lerp_inside(uv)
+        };
+        painter.rect_stroke(
+            egui::Rect::from_min_max(to_map(visible.min), to_map(visible.max)),
+            0.0,
+            egui::Stroke::new(1.5, egui::Color32::YELLOW),
+            egui::StrokeKind::Inside,
+        );
+
+        if let Some(pos) = response.interact_pointer_pos() {
+            let uv = (pos - map_rect.min) / map_rect.size();
+            self.center_on(
+                uv.clamp(egui::Vec2::ZERO, egui::Vec2::splat(1.0)),
+                viewport,
+                image_size,
+            );
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use super::settings::ModelConfig;
+use super::ImageInput;
+use eframe::egui;
+use image::RgbImage;
+use rf_detr_onnx_test_lib::mask::MASK_MIN_CONFIDENCE;
+use rf_detr_onnx_test_lib::{
+    decode_image_with_orientation, run_inference_with_threshold, ClassifierStages, Detection,
+    ModelCache, ModelInfo, PipelineConfig,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+
+/// 처리 단계
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Reading,
+    LoadingModel,
+    Decoding,
+    Inference,
+    Classifying,
+}
+
+impl Stage {
+    /// 표시용 이름
+    pub fn label(&self) -> &'static str {
+        match self {
+            Stage::Reading => "Reading file...",
+            Stage::LoadingModel => "Loading model (first run)...",
+            Stage::Decoding => "Decoding image...",
+            Stage::Inference => "Running inference...",
+            Stage::Classifying => "Classifying detections...",
+        }
+    }
+
+    /// 진행률 표시용 비율 (단계 시작 시점 기준)
+    pub fn fraction(&self) -> f32 {
+        match self {
+            Stage::Reading => 0.0,
+            Stage::LoadingModel => 0.1,
+            Stage::Decoding => 0.3,
+            Stage::Inference => 0.4,
+            Stage::Classifying => 0.8,
+        }
+    }
+}
+
+/// 이미지별로 캐시되는 검출 결과
+#[derive(Debug, Clone)]
+pub struct CachedDetection {
+    /// 쿼리별 최고 점수 후보 (임계값 미적용)
+    pub candidates: Vec<Detection>,
+    pub inference_time_ms: f64,
+}
+
+/// 작업 결과
+pub struct InferenceOutput {
+    /// 박스를 그리지 않은 원본 이미지
+    pub image: RgbImage,
+    /// 검출 결과 (캐시된 결과가 있어 디코딩만 요청한 경우 None)
+    pub detection: Option<CachedDetection>,
+}
+
+/// 워커로 보내는 요청
+enum WorkerRequest {
+    Detect {
+        job_id: u64,
+        input: ImageInput,
+        run_detection: bool,
+    },
+    /// 모델/세션 옵션/입력 크기 변경
+    Configure(ModelConfig),
+}
+
+/// 워커가 보내는 이벤트
+pub enum WorkerEvent {
+    Stage {
+        job_id: u64,
+        stage: Stage,
+    },
+    ModelLoaded(Box<ModelInfo>),
+    /// 설정 변경으로 세션을 다시 만들다 실패
+    ModelFailed(String),
+    Finished {
+        job_id: u64,
+        output: InferenceOutput,
+    },
+    Failed {
+        job_id: u64,
+        error: String,
+    },
+    Cancelled {
+        job_id: u64,
+    },
+}
+
+/// 백그라운드 추론 스레드 핸들
+///
+/// `ModelCache`는 워커 스레드가 소유하며, 결과는 채널로 UI 스레드에 전달됩니다.
+/// 스레드는 분리(detach)되어 있어 핸들을 버려도 진행 중인 추론을 기다리지 않습니다.
+pub struct InferenceWorker {
+    request_tx: Option<Sender<WorkerRequest>>,
+    event_rx: Receiver<WorkerEvent>,
+    /// 이 ID보다 작은 작업은 취소된 것으로 봄 (새 작업 제출 또는 취소 시 증가, 종료 시 `u64::MAX`)
+    stale_below: Arc<AtomicU64>,
+    next_job_id: u64,
+}
+
+impl InferenceWorker {
+    /// 워커 스레드 시작
+    pub fn spawn(ctx: egui::Context, config: ModelConfig) -> Self {
+        let (request_tx, request_rx) = mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel();
+        let stale_below = Arc::new(AtomicU64::new(0));
+
+        let thread_stale_below = Arc::clone(&stale_below);
+        std::thread::Builder::new()
+            .name("rf-detr-inference".to_string())
+            .spawn(move || worker_loop(request_rx, event_tx, thread_stale_below, ctx, config))
+            .expect("failed to spawn inference worker thread");
+
+        Self {
+            request_tx: Some(request_tx),
+            event_rx,
+            stale_below,
+            next_job_id: 1,
+        }
+    }
+
+    /// 작업 요청 (작업 ID 반환)
+    ///
+    /// `run_detection`이 false이면 이미지 디코딩만 수행합니다.
+    /// 새 작업을 제출하면 아직 끝나지 않은 이전 작업은 모두 취소됩니다.
+    pub fn submit(&mut self, input: ImageInput, run_detection: bool) -> anyhow::Result<u64> {
+        let job_id = self.next_job_id;
+        self.next_job_id += 1;
+        self.stale_below.fetch_max(job_id, Ordering::SeqCst);
+        self.request_tx
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Inference worker is shut down"))?
+            .send(WorkerRequest::Detect {
+                job_id,
+                input,
+                run_detection,
+            })
+            .map_err(|_| anyhow::anyhow!("Inference worker stopped unexpectedly"))?;
+        Ok(job_id)
+    }
+
+    /// 모델 구성 변경 (이미 로드된 세션은 즉시 다시 만듦)
+    pub fn configure(&self, config: ModelConfig) -> anyhow::Result<()> {
+        self.request_tx
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Inference worker is shut down"))?
+            .send(WorkerRequest::Configure(config))
+            .map_err(|_| anyhow::anyhow!("Inference worker stopped unexpectedly"))
+    }
+
+    /// 작업 취소 요청 (해당 작업과 그 이전 작업은 다음 단계 경계에서 중단)
+    pub fn cancel(&self, job_id: u64) {
+        self.stale_below.fetch_max(job_id + 1, Ordering::SeqCst);
+    }
+
+    /// 도착한 이벤트 모두 가져오기
+    pub fn poll(&self) -> Vec<WorkerEvent> {
+        self.event_rx.try_iter().collect()
+    }
+}
+
+impl Drop for InferenceWorker {
+    fn drop(&mut self) {
+        // 대기 중인 작업을 모두 취소하고 요청 채널을 닫아 워커 루프 종료 (스레드는 기다리지 않음)
+        self.stale_below.store(u64::MAX, Ordering::SeqCst);
+        self.request_tx.take();
+    }
+}
+
+/// 워커 스레드 본체
+fn worker_loop(
+    requests: Receiver<WorkerRequest>,
+    events: Sender<WorkerEvent>,
+    stale_below: Arc<AtomicU64>,
+    ctx: egui::Context,
+    mut config: ModelConfig,
+) {
+    let mut cache: Option<ModelCache> = None;
+    // 설정의 파이프라인 파일에서 만든 분류 단계 (첫 검출 때 로드)
+    let mut classifier_stages: Option<ClassifierStages> = None;
+
+    let send = |event: WorkerEvent| {
+        let _ = events.send(event);
+        ctx.request_repaint();
+    };
+
+    for request in requests {
+        // 핸들이 버려졌으면 남은 요청(세션 재구성 포함)은 처리하지 않음
+        if stale_below.load(Ordering::SeqCst) == u64::MAX {
+            break;
+        }
+        let (job_id, input, run_detection) = match request {
+            WorkerRequest::Detect {
+                job_id,
+                input,
+                run_detection,
+            } => (job_id, input, run_detection),
+            WorkerRequest::Configure(new_config) => {
+                // 세션이 이미 로드되어 있었다면 새 설정으로 바로 다시 로드
+                let reload = cache.as_mut().is_some_and(|c| {
+                    let was_loaded = c.is_loaded();
+                    c.configure(new_config.source.clone(), new_config.options) && was_loaded
+                });
+                if new_config.pipeline != config.pipeline {
+                    classifier_stages = None;
+                }
+                config = new_config;
+                if reload {
+                    if let Some(cache) = cache.as_mut() {
+                        match cache.preload_model().and_then(|()| cache.info()) {
+                            Ok(info) => send(WorkerEvent::ModelLoaded(Box::new(info))),
+                            Err(e) => send(WorkerEvent::ModelFailed(e.to_string())),
+                        }
+                    }
+                }
+                continue;
+            }
+        };
+        // 대기열에 쌓인 이전 작업(빠른 이미지 전환 등)은 시작하지 않고 건너뜀
+        let is_cancelled = || job_id < stale_below.load(Ordering::SeqCst);
+        let stage = |stage: Stage| send(WorkerEvent::Stage { job_id, stage });
+
+        let result = (|| -> anyhow::Result<Option<InferenceOutput>> {
+            if is_cancelled() {
+                return Ok(None);
+            }
+            stage(Stage::Reading);
+            let image_data = match input {
+                ImageInput::File(path) => std::fs::read(&path)
+                    .map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?,
+                ImageInput::Bytes { data, .. } => data,
+            };
+
+            if !run_detection {
+                stage(Stage::Decoding);
+                let img = decode_image_with_orientation(&image_data, config.orientation)?;
+                return Ok(Some(InferenceOutput {
+                    image: img,
+                    detection: None,
+                }));
+            }
+
+            // 모델 캐시 초기화 및 첫 로드
+            if cache.is_none() {
+                cache = Some(
+                    ModelCache::with_options(config.source.clone(), config.options)
+                        .map_err(|e| anyhow::anyhow!("Failed to initialize model cache: {}", e))?,
+                );
+            }
+            let cache = cache.as_mut().expect("model cache initialized above");
+            if !cache.is_loaded() {
+                stage(Stage::LoadingModel);
+                cache.preload_model()?;
+                if let Ok(info) = cache.info() {
+                    send(WorkerEvent::ModelLoaded(Box::new(info)));
+                }
+            }
+            if is_cancelled() {
+                return Ok(None);
+            }
+
+            stage(Stage::Decoding);
+            let img = decode_image_with_orientation(&image_data, config.orientation)?;
+            if is_cancelled() {
+                return Ok(None);
+            }
+
+            // 임계값 0으로 모든 후보를 받아 UI에서 재추론 없이 필터링
+            stage(Stage::Inference);
+            let (mut candidates, inference_time_ms) =
+                run_inference_with_threshold(&img, cache, config.input_size, 0.0)?;
+            if is_cancelled() {
+                return Ok(None);
+            }
+
+            if let Some(path) = &config.pipeline {
+                stage(Stage::Classifying);
+                if classifier_stages.is_none() {
+                    let pipeline = PipelineConfig::from_json_file(path)
+                        .and_then(|pipeline| ClassifierStages::new(pipeline.stages))
+                        .map_err(|e| {
+                            anyhow::anyhow!("Failed to load classifier pipeline: {}", e)
+                        })?;
+                    classifier_stages = Some(pipeline);
+                }
+                let stages = classifier_stages
+                    .as_mut()
+                    .expect("classifier stages loaded above");
+                // 슬라이더로 볼 수 없는 후보까지 분류하지 않도록 최소 신뢰도 이상만 분류
+                let (mut classified, rest): (Vec<Detection>, Vec<Detection>) = candidates
+                    .into_iter()
+                    .partition(|d| d.confidence >= MASK_MIN_CONFIDENCE);
+                stages.classify(&img, &mut classified)?;
+                classified.extend(rest);
+                candidates = classified;
+                if is_cancelled() {
+                    return Ok(None);
+                }
+            }
+
+            Ok(Some(InferenceOutput {
+                image: img,
+                detection: Some(CachedDetection {
+                    candidates,
+                    inference_time_ms,
+                }),
+            }))
+        })();
+
+        match result {
+            Ok(Some(output)) => send(WorkerEvent::Finished { job_id, output }),
+            Ok(None) => send(WorkerEvent::Cancelled { job_id }),
+            Err(e) => send(WorkerEvent::Failed {
+                job_id,
+                error: e.to_string(),
+            }),
+        }
+    }
+}
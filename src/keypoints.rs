@@ -0,0 +1,376 @@
+use crate::{letterbox_params, sigmoid};
+use image::{Rgb, RgbImage};
+use imageproc::drawing::{draw_filled_circle_mut, draw_line_segment_mut};
+use ndarray::ArrayView2;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::Path;
+
+// 상수 정의
+/// COCO 사람 키포인트 이름 (모델 출력 순서)
+pub const COCO_KEYPOINT_NAMES: [&str; 17] = [
+    "nose",
+    "left_eye",
+    "right_eye",
+    "left_ear",
+    "right_ear",
+    "left_shoulder",
+    "right_shoulder",
+    "left_elbow",
+    "right_elbow",
+    "left_wrist",
+    "right_wrist",
+    "left_hip",
+    "right_hip",
+    "left_knee",
+    "right_knee",
+    "left_ankle",
+    "right_ankle",
+];
+/// COCO 스켈레톤 연결 (0부터 시작하는 인덱스)
+const COCO_SKELETON: [(usize, usize); 19] = [
+    (15, 13),
+    (13, 11),
+    (16, 14),
+    (14, 12),
+    (11, 12),
+    (5, 11),
+    (6, 12),
+    (5, 6),
+    (5, 7),
+    (6, 8),
+    (7, 9),
+    (8, 10),
+    (1, 2),
+    (0, 1),
+    (0, 2),
+    (1, 3),
+    (2, 4),
+    (3, 5),
+    (4, 6),
+];
+/// 이 가시성 이상이면 보이는 키포인트로 간주
+pub const DEFAULT_MIN_VISIBILITY: f32 = 0.5;
+const KEYPOINT_COLOR: Rgb<u8> = Rgb([0, 255, 0]); // 초록색
+const SKELETON_COLOR: Rgb<u8> = Rgb([0, 200, 255]); // 하늘색
+
+/// 검출에 딸린 키포인트 (원본 이미지 기준 정규화 좌표)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Keypoint {
+    pub x: f32,
+    pub y: f32,
+    /// 가시성 점수 (0~1)
+    pub visibility: f32,
+}
+
+impl Keypoint {
+    /// 여러 검출의 키포인트를 점별로 가중 평균 (TTA 융합용)
+    ///
+    /// 위치는 검출 가중치 × 가시성으로 평균해 가려진 점이 위치를 끌어당기지 않게 하고,
+    /// 가시성은 검출 가중치로 평균합니다. 키포인트 개수가 다르면 융합하지 않습니다.
+    pub fn weighted_average(sets: &[(&[Keypoint], f32)]) -> Option<Vec<Keypoint>> {
+        let (first, _) = sets.first()?;
+        let total: f32 = sets.iter().map(|(_, w)| w).sum();
+        if total <= 0.0 || sets.iter().any(|(points, _)| points.len() != first.len()) {
+            return None;
+        }
+
+        let fused = (0..first.len())
+            .map(|i| {
+                let visible_weight: f32 = sets.iter().map(|(p, w)| w * p[i].visibility).sum();
+                // 모든 패스에서 보이지 않는 점은 검출 가중치만으로 위치 평균
+                let position_weight = |point: &Keypoint, weight: f32| {
+                    if visible_weight > 0.0 {
+                        weight * point.visibility / visible_weight
+                    } else {
+                        weight / total
+                    }
+                };
+                let mut fused = Keypoint {
+                    x: 0.0,
+                    y: 0.0,
+                    visibility: 0.0,
+                };
+                for (points, weight) in sets {
+                    let point = &points[i];
+                    let position = position_weight(point, *weight);
+                    fused.x += point.x * position;
+                    fused.y += point.y * position;
+                    fused.visibility += point.visibility * weight / total;
+                }
+                fused
+            })
+            .collect();
+        Some(fused)
+    }
+}
+
+/// 키포인트 이름과 연결선 정의
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Skeleton {
+    /// 키포인트 이름 (모델 출력 순서)
+    pub names: Vec<String>,
+    /// 연결할 키포인트 인덱스 쌍 (0부터 시작)
+    pub edges: Vec<(usize, usize)>,
+    /// 좌우 반전 시 서로 바뀌는 키포인트 쌍
+    #[serde(default)]
+    pub flip_pairs: Vec<(usize, usize)>,
+}
+
+impl Default for Skeleton {
+    fn default() -> Self {
+        Self::coco()
+    }
+}
+
+impl Skeleton {
+    /// COCO 사람 17 키포인트 스켈레톤
+    pub fn coco() -> Self {
+        let names: Vec<String> = COCO_KEYPOINT_NAMES.iter().map(|n| n.to_string()).collect();
+        // left_*/right_* 이름 쌍을 반전 쌍으로 사용
+        let flip_pairs = names
+            .iter()
+            .enumerate()
+            .filter_map(|(i, name)| {
+                let right = name
+                    .strip_prefix("left_")
+                    .map(|rest| format!("right_{}", rest))?;
+                names.iter().position(|n| *n == right).map(|j| (i, j))
+            })
+            .collect();
+        Self {
+            names,
+            edges: COCO_SKELETON.to_vec(),
+            flip_pairs,
+        }
+    }
+
+    /// JSON 파일에서 로드 (`{"names": [...], "edges": [[0, 1], ...], "flip_pairs": [...]}`)
+    pub fn from_json_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let skeleton: Self = serde_json::from_str(&text)?;
+        let count = skeleton.names.len();
+        if let Some((a, b)) = skeleton
+            .edges
+            .iter()
+            .chain(&skeleton.flip_pairs)
+            .find(|(a, b)| *a >= count || *b >= count)
+        {
+            anyhow::bail!(
+                "키포인트 인덱스가 범위를 벗어났습니다: ({}, {}), 키포인트 수 {}",
+                a,
+                b,
+                count
+            );
+        }
+        Ok(skeleton)
+    }
+
+    /// 키포인트 수에 맞는 기본 스켈레톤 (17개면 COCO, 그 외에는 연결선 없음)
+    pub fn for_count(count: usize) -> Self {
+        if count == COCO_KEYPOINT_NAMES.len() {
+            Self::coco()
+        } else {
+            Self {
+                names: (0..count).map(|i| format!("kp{}", i)).collect(),
+                edges: Vec::new(),
+                flip_pairs: Vec::new(),
+            }
+        }
+    }
+
+    /// 사용자 정의 스켈레톤의 키포인트 수가 맞으면 그대로, 아니면 `for_count` 기본값
+    pub fn resolve(custom: Option<&Skeleton>, count: usize) -> Cow<'_, Skeleton> {
+        match custom {
+            Some(skeleton) if skeleton.names.len() == count => Cow::Borrowed(skeleton),
+            _ => Cow::Owned(Self::for_count(count)),
+        }
+    }
+}
+
+/// 쿼리 하나의 키포인트 출력 파싱 ([키포인트 수, 2 또는 3], 레터박스 정규화 좌표와 가시성 로짓)
+///
+/// 박스와 같은 레터박스 역변환을 적용하며, 가시성 열이 없으면 1.0으로 둡니다.
+pub fn parse_keypoints(
+    tensor: &ArrayView2<f32>,
+    original_width: u32,
+    original_height: u32,
+    input_size: u32,
+) -> Vec<Keypoint> {
+    let (scale, offset_x, offset_y) = letterbox_params(original_width, original_height, input_size);
+    let input = input_size as f32;
+    let (w, h) = (original_width as f32, original_height as f32);
+    tensor
+        .rows()
+        .into_iter()
+        .map(|row| {
+            let x = ((row[0] * input - offset_x) / scale).clamp(0.0, w) / w;
+            let y = ((row[1] * input - offset_y) / scale).clamp(0.0, h) / h;
+            let visibility = row.get(2).map(|&logit| sigmoid(logit)).unwrap_or(1.0);
+            Keypoint { x, y, visibility }
+        })
+        .collect()
+}
+
+/// 좌우 반전 이미지의 키포인트를 원본 기준으로 되돌림 (좌우 쌍은 서로 교환)
+pub fn unflip_keypoints(keypoints: &[Keypoint], skeleton: &Skeleton) -> Vec<Keypoint> {
+    let mut unflipped: Vec<Keypoint> = keypoints
+        .iter()
+        .map(|k| Keypoint { x: 1.0 - k.x, ..*k })
+        .collect();
+    for &(a, b) in &skeleton.flip_pairs {
+        if a < unflipped.len() && b < unflipped.len() {
+            unflipped.swap(a, b);
+        }
+    }
+    unflipped
+}
+
+/// 키포인트와 스켈레톤 그리기 (가시성이 `min_visibility` 미만인 점과 그 연결선은 생략)
+pub fn draw_keypoints(
+    image: &mut RgbImage,
+    keypoints: &[Keypoint],
+    skeleton: &Skeleton,
+    radius: u32,
+    min_visibility: f32,
+) {
+    let (w, h) = (image.width() as f32, image.height() as f32);
+    let to_pixel = |k: &Keypoint| (k.x * w, k.y * h);
+    let visible = |i: usize| keypoints.get(i).filter(|k| k.visibility >= min_visibility);
+
+    for &(a, b) in &skeleton.edges {
+        if let (Some(ka), Some(kb)) = (visible(a), visible(b)) {
+            draw_line_segment_mut(image, to_pixel(ka), to_pixel(kb), SKELETON_COLOR);
+        }
+    }
+    for keypoint in keypoints.iter().filter(|k| k.visibility >= min_visibility) {
+        let (x, y) = to_pixel(keypoint);
+        draw_filled_circle_mut(
+            image,
+            (x as i32, y as i32),
+            radius.max(1) as i32,
+            KEYPOINT_COLOR,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_skeleton() -> Skeleton {
+        Skeleton {
+            names: vec!["head".into(), "left_fin".into(), "right_fin".into()],
+            edges: vec![(0, 1), (0, 2)],
+            flip_pairs: vec![(1, 2)],
+        }
+    }
+
+    #[test]
+    fn resolve_uses_custom_skeleton_only_when_counts_match() {
+        let custom = custom_skeleton();
+        assert_eq!(*Skeleton::resolve(Some(&custom), 3), custom);
+        assert_eq!(*Skeleton::resolve(Some(&custom), 17), Skeleton::coco());
+        assert_eq!(*Skeleton::resolve(None, 3), Skeleton::for_count(3));
+    }
+
+    #[test]
+    fn unflip_swaps_custom_flip_pairs() {
+        let points = [
+            Keypoint {
+                x: 0.5,
+                y: 0.1,
+                visibility: 1.0,
+            },
+            Keypoint {
+                x: 0.2,
+                y: 0.5,
+                visibility: 0.9,
+            },
+            Keypoint {
+                x: 0.8,
+                y: 0.5,
+                visibility: 0.3,
+            },
+        ];
+        let unflipped = unflip_keypoints(&points, &custom_skeleton());
+        assert_eq!(unflipped[0].x, 0.5);
+        // 반전 이미지의 왼쪽 지느러미(x=0.2)는 원본의 오른쪽 지느러미(x=0.8)
+        assert_eq!((unflipped[1].x, unflipped[1].visibility), (0.2, 0.3));
+        assert_eq!((unflipped[2].x, unflipped[2].visibility), (0.8, 0.9));
+
+        // 기본 스켈레톤(반전 쌍 없음)은 좌표만 뒤집음
+        let plain = unflip_keypoints(&points, &Skeleton::for_count(3));
+        assert_eq!(plain[1].x, 0.8);
+    }
+
+    #[test]
+    fn weighted_average_ignores_hidden_points_for_position() {
+        let a = [
+            Keypoint {
+                x: 0.2,
+                y: 0.2,
+                visibility: 1.0,
+            },
+            Keypoint {
+                x: 0.5,
+                y: 0.5,
+                visibility: 0.0,
+            },
+        ];
+        let b = [
+            Keypoint {
+                x: 0.4,
+                y: 0.2,
+                visibility: 1.0,
+            },
+            Keypoint {
+                x: 0.7,
+                y: 0.7,
+                visibility: 1.0,
+            },
+        ];
+        let fused = Keypoint::weighted_average(&[(&a, 0.75), (&b, 0.25)]).unwrap();
+
+        assert!((fused[0].x - 0.25).abs() < 1e-6);
+        assert!((fused[0].y - 0.2).abs() < 1e-6);
+        assert!((fused[0].visibility - 1.0).abs() < 1e-6);
+        // 첫 검출에서 가려진 점은 두 번째 검출 위치를 그대로 사용
+        assert!((fused[1].x - 0.7).abs() < 1e-6);
+        assert!((fused[1].visibility - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn weighted_average_of_hidden_points_uses_detection_weight() {
+        let a = [Keypoint {
+            x: 0.0,
+            y: 0.0,
+            visibility: 0.0,
+        }];
+        let b = [Keypoint {
+            x: 1.0,
+            y: 1.0,
+            visibility: 0.0,
+        }];
+        let fused = Keypoint::weighted_average(&[(&a, 0.5), (&b, 0.5)]).unwrap();
+        assert_eq!(
+            fused[0],
+            Keypoint {
+                x: 0.5,
+                y: 0.5,
+                visibility: 0.0
+            }
+        );
+    }
+
+    #[test]
+    fn weighted_average_rejects_mismatched_counts() {
+        let a = [Keypoint {
+            x: 0.0,
+            y: 0.0,
+            visibility: 1.0,
+        }];
+        assert_eq!(Keypoint::weighted_average(&[(&a, 0.5), (&[], 0.5)]), None);
+        assert_eq!(Keypoint::weighted_average(&[]), None);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{rf_detr_id_to_label, Detection};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// 클래스 ID → 표시 이름 재정의
+///
+/// 맵에 없는 ID는 기본 RF-DETR(COCO) 이름을 사용합니다.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LabelMap {
+    names: BTreeMap<u32, String>,
+}
+
+impl LabelMap {
+    /// JSON 객체(`{"1": "person", ...}`) 파싱
+    pub fn from_json_str(json: &str) -> anyhow::Result<Self> {
+        let raw: BTreeMap<String, String> = serde_json::from_str(json)?;
+        let mut names = BTreeMap::new();
+        for (id, name) in raw {
+            let id: u32 = id
+                .trim()
+                .parse()
+                .map_err(|_| anyhow::anyhow!("잘못된 클래스 ID: {}", id))?;
+            names.insert(id, name);
+        }
+        Ok(Self { names })
+    }
+
+    /// 텍스트 파싱 (N번째 줄이 클래스 ID N, 빈 줄은 건너뜀)
+    pub fn from_lines(text: &str) -> Self {
+        let names = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| (i as u32 + 1, line.trim().to_string()))
+            .collect();
+        Self { names }
+    }
+
+    /// 파일에서 로드 (`.json`은 JSON 객체, 그 외는 줄 단위 텍스트)
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let is_json = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+        if is_json {
+            Self::from_json_str(&text)
+        } else {
+            Ok(Self::from_lines(&text))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// 클래스 ID의 표시 이름 (재정의 → 기본 이름 → `class_<id>`)
+    pub fn name(&self, class_id: u32) -> String {
+        self.names
+            .get(&class_id)
+            .cloned()
+            .or_else(|| rf_detr_id_to_label(class_id).map(str::to_string))
+            .unwrap_or_else(|| format!("class_{}", class_id))
+    }
+
+    /// 검출 결과의 클래스 이름을 맵 기준으로 다시 설정
+    pub fn apply(&self, detections: &mut [Detection]) {
+        for detection in detections {
+            detection.class_name = self.name(detection.class_id);
+        }
+    }
+}
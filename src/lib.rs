@@ -1,27 +1,85 @@
-use image::{ImageReader, Rgb, RgbImage};
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageReader, Rgb, RgbImage};
 use imageproc::drawing::draw_hollow_rect_mut;
 use imageproc::rect::Rect;
 use ndarray::CowArray;
-use ndarray::{ArrayD, IxDyn};
+use ndarray::{ArrayD, Axis, Ix2, IxDyn};
 use ort::execution_providers::{CPUExecutionProviderOptions, CoreMLExecutionProviderOptions};
 use ort::{Environment, ExecutionProvider, SessionBuilder, Value};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::sync::Arc;
 
+pub mod analytics;
+pub mod batch;
+pub mod compare;
+pub mod crops;
+pub mod export;
+pub mod keypoints;
+pub mod labels;
+pub mod live;
+pub mod mask;
+pub mod model_info;
+pub mod obb;
+pub mod pipeline;
+pub mod redact;
+pub mod remap;
+pub mod tta;
+pub mod zones;
+
+pub use analytics::{Analytics, AnalyticsReport, CountingLine, IouTracker, TrackedDetection};
+pub use batch::{run_batch, run_folder, BatchConfig, BatchProgress, BatchReport};
+pub use compare::{diff_detections, DetectionDiff, MatchedPair};
+pub use crops::{export_crops, CropConfig};
+pub use export::{export_detections, export_detections_with_classes, ExportFormat};
+pub use keypoints::{Keypoint, Skeleton};
+pub use labels::LabelMap;
+pub use live::{FolderLoopSource, FpsMeter, Frame, FrameSource, LatestFrame, TestPatternSource};
+pub use mask::{Mask, Rle};
+pub use model_info::{ModelInfo, TensorInfo};
+pub use obb::OrientedBox;
+pub use pipeline::{
+    ClassifierCache, ClassifierStages, Pipeline, PipelineConfig, SecondaryLabel, StageConfig,
+};
+pub use redact::{
+    redact_detections, redact_folder, redact_image_file, RedactBatchConfig, RedactionConfig,
+    RedactionMode,
+};
+pub use remap::{ClassRemap, ClassSelector, RemapTarget};
+pub use tta::TtaConfig;
+pub use zones::{OverlapCriterion, RoiFilter, Zone, ZoneMode};
+
 // 상수 정의
-const MODEL_INPUT_SIZE: u32 = 560;
-const CONFIDENCE_THRESHOLD: f32 = 0.5;
+pub const MODEL_INPUT_SIZE: u32 = 560;
+pub const CONFIDENCE_THRESHOLD: f32 = 0.5;
 const BBOX_COLOR: Rgb<u8> = Rgb([255, 0, 0]); // 빨간색
+const MASK_ALPHA: f32 = 0.4;
 
 // 임베디드 리소스 (원본 모델만)
 static RF_DETR_ORIGINAL_ONNX: &[u8] = include_bytes!("../assets/models/model.onnx");
 
 /// 객체 검출 결과를 나타내는 구조체
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Detection {
     pub bbox: [f32; 4], // [x1, y1, x2, y2] in normalized coordinates (0-1)
     pub confidence: f32,
     pub class_id: u32,
     pub class_name: String,
+    /// 인스턴스 마스크 (세그멘테이션 모델에서만, JSON에는 COCO RLE로 기록)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mask: Option<Mask>,
+    /// 키포인트 (포즈 모델에서만, 원본 이미지 기준 정규화 좌표)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keypoints: Option<Vec<Keypoint>>,
+    /// 회전 박스 (각도를 출력하는 모델에서만, 이때 `bbox`는 이를 감싸는 축 정렬 박스)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub obb: Option<OrientedBox>,
+    /// 2단계 분류기가 붙인 상위 보조 라벨 (`Pipeline` 사용 시)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secondary: Vec<SecondaryLabel>,
+    /// 클래스 재매핑 전 원래 클래스 ID (`ClassRemap` 적용 시)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_class_id: Option<u32>,
 }
 
 /// 검출 결과를 나타내는 구조체 (추론 시간 포함)
@@ -37,8 +95,27 @@ pub fn sigmoid(x: f32) -> f32 {
     1.0 / (1.0 + (-x).exp())
 }
 
+/// 두 바운딩 박스 [x1, y1, x2, y2]의 IoU 계산
+pub fn iou(a: &[f32; 4], b: &[f32; 4]) -> f32 {
+    let ix1 = a[0].max(b[0]);
+    let iy1 = a[1].max(b[1]);
+    let ix2 = a[2].min(b[2]);
+    let iy2 = a[3].min(b[3]);
+
+    let inter = (ix2 - ix1).max(0.0) * (iy2 - iy1).max(0.0);
+    let area_a = (a[2] - a[0]).max(0.0) * (a[3] - a[1]).max(0.0);
+    let area_b = (b[2] - b[0]).max(0.0) * (b[3] - b[1]).max(0.0);
+    let union = area_a + area_b - inter;
+
+    if union > 0.0 {
+        inter / union
+    } else {
+        0.0
+    }
+}
+
 /// RF-DETR 클래스 ID를 클래스 이름으로 변환
-fn rf_detr_id_to_label(class_id: u32) -> Option<&'static str> {
+pub fn rf_detr_id_to_label(class_id: u32) -> Option<&'static str> {
     match class_id {
         1 => Some("person"),
         2 => Some("bicycle"),
@@ -124,33 +201,50 @@ fn rf_detr_id_to_label(class_id: u32) -> Option<&'static str> {
     }
 }
 
-/// 레터박싱 좌표를 원본 이미지 좌표로 변환
-fn letterbox_to_original_coords(
-    bbox: [f32; 4], // [x1, y1, x2, y2] in letterboxed coordinates (0-1)
+/// 전체 라벨 맵 (클래스 ID 순서)
+pub fn class_label_map() -> Vec<(u32, &'static str)> {
+    (1..=90)
+        .filter_map(|id| rf_detr_id_to_label(id).map(|name| (id, name)))
+        .collect()
+}
+
+/// 레터박싱 변환 (원본 픽셀 → 입력 픽셀 배율, x 오프셋, y 오프셋)
+pub(crate) fn letterbox_params(
     original_width: u32,
     original_height: u32,
-) -> [f32; 4] {
+    input_size: u32,
+) -> (f32, f32, f32) {
     let aspect_ratio = original_width as f32 / original_height as f32;
 
-    let (scale, offset_x, offset_y) = if aspect_ratio > 1.0 {
+    if aspect_ratio > 1.0 {
         // 가로가 더 긴 경우
-        let scale = MODEL_INPUT_SIZE as f32 / original_width as f32;
+        let scale = input_size as f32 / original_width as f32;
         let offset_x = 0.0;
-        let offset_y = (MODEL_INPUT_SIZE as f32 - MODEL_INPUT_SIZE as f32 / aspect_ratio) / 2.0;
+        let offset_y = (input_size as f32 - input_size as f32 / aspect_ratio) / 2.0;
         (scale, offset_x, offset_y)
     } else {
         // 세로가 더 긴 경우
-        let scale = MODEL_INPUT_SIZE as f32 / original_height as f32;
-        let offset_x = (MODEL_INPUT_SIZE as f32 - MODEL_INPUT_SIZE as f32 * aspect_ratio) / 2.0;
+        let scale = input_size as f32 / original_height as f32;
+        let offset_x = (input_size as f32 - input_size as f32 * aspect_ratio) / 2.0;
         let offset_y = 0.0;
         (scale, offset_x, offset_y)
-    };
+    }
+}
+
+/// 레터박싱 좌표를 원본 이미지 좌표로 변환
+fn letterbox_to_original_coords(
+    bbox: [f32; 4], // [x1, y1, x2, y2] in letterboxed coordinates (0-1)
+    original_width: u32,
+    original_height: u32,
+    input_size: u32,
+) -> [f32; 4] {
+    let (scale, offset_x, offset_y) = letterbox_params(original_width, original_height, input_size);
 
     // 레터박싱 좌표를 픽셀 좌표로 변환
-    let x1_pixel = bbox[0] * MODEL_INPUT_SIZE as f32;
-    let y1_pixel = bbox[1] * MODEL_INPUT_SIZE as f32;
-    let x2_pixel = bbox[2] * MODEL_INPUT_SIZE as f32;
-    let y2_pixel = bbox[3] * MODEL_INPUT_SIZE as f32;
+    let x1_pixel = bbox[0] * input_size as f32;
+    let y1_pixel = bbox[1] * input_size as f32;
+    let x2_pixel = bbox[2] * input_size as f32;
+    let y2_pixel = bbox[3] * input_size as f32;
 
     // 패딩 제거
     let x1_unpadded = (x1_pixel - offset_x) / scale;
@@ -175,6 +269,14 @@ fn letterbox_to_original_coords(
 
 /// 이미지 전처리: 리사이징, 레터박싱, 정규화
 pub fn preprocess_image(image: &RgbImage) -> anyhow::Result<ArrayD<f32>> {
+    preprocess_image_with_size(image, MODEL_INPUT_SIZE)
+}
+
+/// 지정한 입력 크기로 이미지 전처리 (멀티 스케일 추론용)
+pub fn preprocess_image_with_size(
+    image: &RgbImage,
+    input_size: u32,
+) -> anyhow::Result<ArrayD<f32>> {
     let original_width = image.width() as f32;
     let original_height = image.height() as f32;
 
@@ -183,10 +285,10 @@ pub fn preprocess_image(image: &RgbImage) -> anyhow::Result<ArrayD<f32>> {
 
     let (new_width, new_height, offset_x, offset_y) = if aspect_ratio > 1.0 {
         // 가로가 더 긴 경우
-        let new_width = MODEL_INPUT_SIZE as f32;
+        let new_width = input_size as f32;
         let new_height = new_width / aspect_ratio;
         let offset_x = 0.0;
-        let offset_y = (MODEL_INPUT_SIZE as f32 - new_height) / 2.0;
+        let offset_y = (input_size as f32 - new_height) / 2.0;
         (
             new_width as u32,
             new_height as u32,
@@ -195,9 +297,9 @@ pub fn preprocess_image(image: &RgbImage) -> anyhow::Result<ArrayD<f32>> {
         )
     } else {
         // 세로가 더 긴 경우
-        let new_height = MODEL_INPUT_SIZE as f32;
+        let new_height = input_size as f32;
         let new_width = new_height * aspect_ratio;
-        let offset_x = (MODEL_INPUT_SIZE as f32 - new_width) / 2.0;
+        let offset_x = (input_size as f32 - new_width) / 2.0;
         let offset_y = 0.0;
         (
             new_width as u32,
@@ -216,7 +318,7 @@ pub fn preprocess_image(image: &RgbImage) -> anyhow::Result<ArrayD<f32>> {
     );
 
     // 정사각형 캔버스 생성 (회색 배경)
-    let mut canvas = RgbImage::new(MODEL_INPUT_SIZE, MODEL_INPUT_SIZE);
+    let mut canvas = RgbImage::new(input_size, input_size);
     let padding_color = Rgb([114, 114, 114]); // 회색 패딩
 
     // 캔버스를 패딩 색상으로 채우기
@@ -229,18 +331,17 @@ pub fn preprocess_image(image: &RgbImage) -> anyhow::Result<ArrayD<f32>> {
         for x in 0..new_width {
             let canvas_x = x + offset_x;
             let canvas_y = y + offset_y;
-            if canvas_x < MODEL_INPUT_SIZE && canvas_y < MODEL_INPUT_SIZE {
+            if canvas_x < input_size && canvas_y < input_size {
                 canvas.put_pixel(canvas_x, canvas_y, *resized.get_pixel(x, y));
             }
         }
     }
 
     // HWC -> CHW 변환 및 정규화 (0~1)
-    let mut input_data =
-        Vec::with_capacity(1 * 3 * MODEL_INPUT_SIZE as usize * MODEL_INPUT_SIZE as usize);
+    let mut input_data = Vec::with_capacity(1 * 3 * input_size as usize * input_size as usize);
     for c in 0..3 {
-        for y in 0..MODEL_INPUT_SIZE {
-            for x in 0..MODEL_INPUT_SIZE {
+        for y in 0..input_size {
+            for x in 0..input_size {
                 let pixel_value = canvas.get_pixel(x, y)[c as usize] as f32 / 255.0;
                 input_data.push(pixel_value);
             }
@@ -249,7 +350,7 @@ pub fn preprocess_image(image: &RgbImage) -> anyhow::Result<ArrayD<f32>> {
 
     // 텐서 생성
     Ok(ArrayD::from_shape_vec(
-        IxDyn(&[1, 3, MODEL_INPUT_SIZE as usize, MODEL_INPUT_SIZE as usize]),
+        IxDyn(&[1, 3, input_size as usize, input_size as usize]),
         input_data,
     )?)
 }
@@ -261,11 +362,154 @@ pub fn parse_rf_detr_outputs(
     original_width: u32,
     original_height: u32,
 ) -> anyhow::Result<Vec<Detection>> {
+    parse_rf_detr_outputs_with_size(
+        bbox_tensor,
+        class_tensor,
+        original_width,
+        original_height,
+        MODEL_INPUT_SIZE,
+    )
+}
+
+/// 지정한 입력 크기 기준으로 RF-DETR 모델 출력 파싱
+pub fn parse_rf_detr_outputs_with_size(
+    bbox_tensor: &ndarray::ArrayViewD<f32>,  // 바운딩 박스 좌표
+    class_tensor: &ndarray::ArrayViewD<f32>, // 클래스 로짓
+    original_width: u32,
+    original_height: u32,
+    input_size: u32,
+) -> anyhow::Result<Vec<Detection>> {
+    parse_rf_detr_outputs_with_threshold(
+        bbox_tensor,
+        class_tensor,
+        original_width,
+        original_height,
+        input_size,
+        CONFIDENCE_THRESHOLD,
+    )
+}
+
+/// 지정한 신뢰도 임계값으로 RF-DETR 모델 출력 파싱
+///
+/// 임계값 0.0을 주면 쿼리별 최고 점수 후보를 모두 반환하므로,
+/// 이후 `filter_by_confidence`로 재추론 없이 다시 필터링할 수 있습니다.
+pub fn parse_rf_detr_outputs_with_threshold(
+    bbox_tensor: &ndarray::ArrayViewD<f32>,  // 바운딩 박스 좌표
+    class_tensor: &ndarray::ArrayViewD<f32>, // 클래스 로짓
+    original_width: u32,
+    original_height: u32,
+    input_size: u32,
+    confidence_threshold: f32,
+) -> anyhow::Result<Vec<Detection>> {
+    parse_rf_detr_outputs_with_masks(
+        bbox_tensor,
+        class_tensor,
+        None,
+        original_width,
+        original_height,
+        input_size,
+        confidence_threshold,
+    )
+}
+
+/// RF-DETR-Seg 출력 파싱 (마스크 로짓 텐서가 있으면 검출마다 마스크 디코딩)
+///
+/// 마스크 텐서는 [1, 쿼리 수, 마스크 높이, 마스크 너비] 형태이며 레터박스된 입력 전체를 덮습니다.
+/// 신뢰도가 `MASK_MIN_CONFIDENCE` 미만인 후보는 마스크를 만들지 않습니다.
+pub fn parse_rf_detr_outputs_with_masks(
+    bbox_tensor: &ndarray::ArrayViewD<f32>,  // 바운딩 박스 좌표
+    class_tensor: &ndarray::ArrayViewD<f32>, // 클래스 로짓
+    mask_tensor: Option<&ndarray::ArrayViewD<f32>>, // 마스크 로짓
+    original_width: u32,
+    original_height: u32,
+    input_size: u32,
+    confidence_threshold: f32,
+) -> anyhow::Result<Vec<Detection>> {
+    parse_rf_detr_outputs_with_extras(
+        bbox_tensor,
+        class_tensor,
+        ExtraOutputs {
+            masks: mask_tensor,
+            keypoints: None,
+        },
+        original_width,
+        original_height,
+        input_size,
+        confidence_threshold,
+    )
+}
+
+/// 박스/클래스 외의 쿼리별 추가 출력
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExtraOutputs<'a> {
+    /// 마스크 로짓 [1, 쿼리 수, 마스크 높이, 마스크 너비]
+    pub masks: Option<&'a ndarray::ArrayViewD<'a, f32>>,
+    /// 키포인트 [1, 쿼리 수, 키포인트 수, 3] (레터박스 정규화 x, y와 가시성 로짓)
+    pub keypoints: Option<&'a ndarray::ArrayViewD<'a, f32>>,
+}
+
+/// 추가 출력 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExtraOutputKind {
+    Masks,
+    Keypoints,
+}
+
+/// 추가 출력 분류 (이름 우선, 이름으로 알 수 없으면 마지막 차원이 3 이하일 때 키포인트)
+///
+/// 쿼리 축 길이가 클래스 출력의 쿼리 수와 다른 출력은 쿼리별 출력이 아니므로 무시합니다.
+fn classify_extra_output(
+    name: &str,
+    shape: &[usize],
+    num_queries: usize,
+) -> Option<ExtraOutputKind> {
+    if shape.len() != 4 || shape[0] != 1 || shape[1] != num_queries {
+        return None;
+    }
+    let name = name.to_lowercase();
+    if ["keypoint", "kpt", "pose"].iter().any(|k| name.contains(k)) {
+        Some(ExtraOutputKind::Keypoints)
+    } else if name.contains("mask") {
+        Some(ExtraOutputKind::Masks)
+    } else if shape[3] <= 3 {
+        Some(ExtraOutputKind::Keypoints)
+    } else {
+        Some(ExtraOutputKind::Masks)
+    }
+}
+
+/// 추가 출력(마스크, 키포인트)까지 포함하여 RF-DETR 출력 파싱
+///
+/// 키포인트는 박스와 같은 레터박스 역변환으로 원본 이미지 정규화 좌표에 맞춥니다.
+pub fn parse_rf_detr_outputs_with_extras(
+    bbox_tensor: &ndarray::ArrayViewD<f32>,  // 바운딩 박스 좌표
+    class_tensor: &ndarray::ArrayViewD<f32>, // 클래스 로짓
+    extras: ExtraOutputs,
+    original_width: u32,
+    original_height: u32,
+    input_size: u32,
+    confidence_threshold: f32,
+) -> anyhow::Result<Vec<Detection>> {
+    let mask_tensor = extras.masks;
     const MAX_DETECTIONS: usize = 100;
 
     let mut detections = Vec::new();
     let num_queries = class_tensor.shape()[1];
 
+    // 쿼리 축이 짧은 추가 출력은 index_axis에서 패닉하므로 미리 검사
+    for (kind, tensor) in [("마스크", extras.masks), ("키포인트", extras.keypoints)] {
+        if let Some(tensor) = tensor {
+            if tensor.ndim() != 4 || tensor.shape()[1] != num_queries {
+                anyhow::bail!(
+                    "{} 출력 형태 {:?}가 쿼리 수({})와 맞지 않습니다",
+                    kind,
+                    tensor.shape(),
+                    num_queries
+                );
+            }
+        }
+    }
+
     for q in 0..num_queries.min(MAX_DETECTIONS) {
         // 클래스 확률 계산
         let mut max_conf = 0.0;
@@ -282,7 +526,7 @@ pub fn parse_rf_detr_outputs(
         }
 
         // 신뢰도 임계값 확인
-        if max_conf > CONFIDENCE_THRESHOLD {
+        if max_conf > confidence_threshold {
             // 바운딩 박스 좌표 추출 (레터박싱된 이미지 기준)
             let cx = bbox_tensor[[0, q, 0]];
             let cy = bbox_tensor[[0, q, 1]];
@@ -297,47 +541,318 @@ pub fn parse_rf_detr_outputs(
                 let y2 = (cy + h / 2.0).max(0.0).min(1.0);
 
                 // 레터박싱 좌표를 원본 이미지 좌표로 변환
-                let original_bbox =
-                    letterbox_to_original_coords([x1, y1, x2, y2], original_width, original_height);
+                let mut original_bbox = letterbox_to_original_coords(
+                    [x1, y1, x2, y2],
+                    original_width,
+                    original_height,
+                    input_size,
+                );
+
+                // 다섯 번째 값이 있으면 회전 박스 (cx, cy, w, h, 각도)
+                let obb = (bbox_tensor.shape()[2] >= 5).then(|| {
+                    OrientedBox::from_letterbox(
+                        [cx, cy, w, h],
+                        bbox_tensor[[0, q, 4]],
+                        original_width,
+                        original_height,
+                        input_size,
+                    )
+                });
+                if let Some(obb) = &obb {
+                    original_bbox = obb.enclosing_bbox(original_width, original_height);
+                }
 
                 if let Some(class_name) = rf_detr_id_to_label(best_class as u32) {
+                    let mask = match mask_tensor {
+                        Some(masks) if max_conf >= mask::MASK_MIN_CONFIDENCE => {
+                            let logits = masks
+                                .index_axis(Axis(0), 0)
+                                .index_axis_move(Axis(0), q)
+                                .into_dimensionality::<Ix2>()?;
+                            Some(mask::decode_mask(
+                                &logits,
+                                &original_bbox,
+                                original_width,
+                                original_height,
+                                input_size,
+                            ))
+                        }
+                        _ => None,
+                    };
+                    let keypoints = match extras.keypoints {
+                        Some(tensor) => {
+                            let points = tensor
+                                .index_axis(Axis(0), 0)
+                                .index_axis_move(Axis(0), q)
+                                .into_dimensionality::<Ix2>()?;
+                            Some(keypoints::parse_keypoints(
+                                &points,
+                                original_width,
+                                original_height,
+                                input_size,
+                            ))
+                        }
+                        None => None,
+                    };
                     detections.push(Detection {
                         bbox: original_bbox,
                         confidence: max_conf,
                         class_id: best_class as u32,
                         class_name: class_name.to_string(),
+                        mask,
+                        keypoints,
+                        obb,
+                        secondary: Vec::new(),
+                        original_class_id: None,
                     });
                 }
             }
         }
     }
 
+    // DETR 계열은 NMS가 필요 없지만, 회전 박스 모델은 겹치는 쿼리를 회전 IoU로 정리
+    if detections.iter().any(|d| d.obb.is_some()) {
+        detections = obb::nms(detections, obb::OBB_NMS_IOU);
+    }
+
     Ok(detections)
 }
 
+/// 신뢰도 임계값을 넘는 검출만 남김
+pub fn filter_by_confidence(detections: &[Detection], threshold: f32) -> Vec<Detection> {
+    detections
+        .iter()
+        .filter(|d| d.confidence > threshold)
+        .cloned()
+        .collect()
+}
+
+/// 바운딩 박스 그리기 스타일
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DrawStyle {
+    /// 박스 색상 (RGB)
+    pub color: [u8; 3],
+    /// 선 두께 (픽셀)
+    pub line_width: u32,
+    /// 인스턴스 마스크 불투명도 (0이면 마스크를 그리지 않음)
+    pub mask_alpha: f32,
+    /// 키포인트 반지름 (픽셀)
+    pub keypoint_radius: u32,
+    /// 이 가시성 미만인 키포인트는 그리지 않음
+    pub min_keypoint_visibility: f32,
+}
+
+impl Default for DrawStyle {
+    fn default() -> Self {
+        Self {
+            color: BBOX_COLOR.0,
+            line_width: 1,
+            mask_alpha: MASK_ALPHA,
+            keypoint_radius: 3,
+            min_keypoint_visibility: keypoints::DEFAULT_MIN_VISIBILITY,
+        }
+    }
+}
+
 /// 검출된 객체에 바운딩 박스 그리기
 pub fn draw_detections(image: &mut RgbImage, detections: &[Detection]) {
+    draw_detections_styled(image, detections, &DrawStyle::default());
+}
+
+/// 지정한 스타일로 바운딩 박스 그리기 (두께만큼 안쪽으로 겹쳐 그림)
+///
+/// 마스크가 있는 검출은 박스보다 먼저 반투명 마스크를 칠하고, 키포인트는 박스 위에 그립니다.
+/// 키포인트 연결선은 키포인트 수에 맞는 기본 스켈레톤(`Skeleton::for_count`)을 사용합니다.
+pub fn draw_detections_styled(image: &mut RgbImage, detections: &[Detection], style: &DrawStyle) {
+    draw_detections_with_skeleton(image, detections, style, None);
+}
+
+/// 지정한 스켈레톤으로 키포인트 연결선을 그리는 `draw_detections_styled`
+///
+/// 스켈레톤의 키포인트 수가 검출과 다르면 그 검출에는 기본 스켈레톤을 사용합니다.
+pub fn draw_detections_with_skeleton(
+    image: &mut RgbImage,
+    detections: &[Detection],
+    style: &DrawStyle,
+    skeleton: Option<&Skeleton>,
+) {
+    let color = Rgb(style.color);
+    if style.mask_alpha > 0.0 {
+        for mask in detections.iter().filter_map(|d| d.mask.as_ref()) {
+            draw_mask(image, mask, style.color, style.mask_alpha);
+        }
+    }
     for detection in detections {
+        if let Some(obb) = &detection.obb {
+            obb::draw_oriented_box(image, obb, color, style.line_width);
+            continue;
+        }
         let [x1, y1, x2, y2] = detection.bbox;
         let x1 = (x1 * image.width() as f32) as i32;
         let y1 = (y1 * image.height() as f32) as i32;
         let x2 = (x2 * image.width() as f32) as i32;
         let y2 = (y2 * image.height() as f32) as i32;
 
-        let rect = Rect::at(x1, y1).of_size((x2 - x1).max(1) as u32, (y2 - y1).max(1) as u32);
-        draw_hollow_rect_mut(image, rect, BBOX_COLOR);
+        for inset in 0..style.line_width.max(1) as i32 {
+            let (w, h) = (x2 - x1 - 2 * inset, y2 - y1 - 2 * inset);
+            if w < 1 || h < 1 {
+                break;
+            }
+            let rect = Rect::at(x1 + inset, y1 + inset).of_size(w as u32, h as u32);
+            draw_hollow_rect_mut(image, rect, color);
+        }
+    }
+
+    for points in detections.iter().filter_map(|d| d.keypoints.as_deref()) {
+        let skeleton = Skeleton::resolve(skeleton, points.len());
+        keypoints::draw_keypoints(
+            image,
+            points,
+            &skeleton,
+            style.keypoint_radius,
+            style.min_keypoint_visibility,
+        );
+    }
+}
+
+/// 인스턴스 마스크를 반투명하게 칠하기 (이미지 크기가 다르면 비율에 맞춰 샘플링)
+pub fn draw_mask(image: &mut RgbImage, mask: &Mask, color: [u8; 3], alpha: f32) {
+    let alpha = alpha.clamp(0.0, 1.0);
+    let (mask_width, mask_height) = mask.image_size();
+    let (width, height) = image.dimensions();
+    if mask_width == 0 || mask_height == 0 {
+        return;
+    }
+    let scale_x = width as f32 / mask_width as f32;
+    let scale_y = height as f32 / mask_height as f32;
+    let [rx, ry, rw, rh] = mask.region();
+
+    // 마스크 영역에 해당하는 이미지 픽셀만 순회
+    let x_start = (rx as f32 * scale_x).floor() as u32;
+    let y_start = (ry as f32 * scale_y).floor() as u32;
+    let x_end = (((rx + rw) as f32 * scale_x).ceil() as u32).min(width);
+    let y_end = (((ry + rh) as f32 * scale_y).ceil() as u32).min(height);
+    for y in y_start..y_end {
+        let my = ((y as f32 + 0.5) / scale_y) as u32;
+        for x in x_start..x_end {
+            let mx = ((x as f32 + 0.5) / scale_x) as u32;
+            if mask.contains(mx, my) {
+                let pixel = image.get_pixel_mut(x, y);
+                for (channel, &c) in pixel.0.iter_mut().zip(color.iter()) {
+                    *channel = (*channel as f32 * (1.0 - alpha) + c as f32 * alpha).round() as u8;
+                }
+            }
+        }
+    }
+}
+
+/// 관심 영역을 반투명하게 오버레이한 뒤 바운딩 박스 그리기
+pub fn draw_detections_with_zones(image: &mut RgbImage, detections: &[Detection], zones: &[Zone]) {
+    zones::draw_zones(image, zones);
+    draw_detections(image, detections);
+}
+
+/// 추론에 사용할 ONNX 모델 위치
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ModelSource {
+    /// 바이너리에 포함된 원본 모델
+    #[default]
+    Embedded,
+    /// 디스크의 ONNX 파일 (예: `model_fp16.onnx`)
+    File(PathBuf),
+}
+
+impl ModelSource {
+    /// 표시용 이름
+    pub fn label(&self) -> String {
+        match self {
+            ModelSource::Embedded => "Embedded (model.onnx)".to_string(),
+            ModelSource::File(path) => path
+                .file_name()
+                .map(|f| f.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string()),
+        }
+    }
+
+    /// 모델 파일 바이트 (메타데이터 파싱용)
+    fn bytes(&self) -> anyhow::Result<std::borrow::Cow<'static, [u8]>> {
+        match self {
+            ModelSource::Embedded => Ok(std::borrow::Cow::Borrowed(RF_DETR_ORIGINAL_ONNX)),
+            ModelSource::File(path) => Ok(std::borrow::Cow::Owned(std::fs::read(path)?)),
+        }
+    }
+}
+
+/// 세션 스레드 설정
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionOptions {
+    /// 연산자 내부 병렬 스레드 수
+    pub intra_threads: usize,
+    /// 연산자 간 병렬 스레드 수
+    pub inter_threads: usize,
+}
+
+impl Default for SessionOptions {
+    fn default() -> Self {
+        // M4 Mac 기준 (성능 코어 4개), 그 외 플랫폼은 넉넉하게
+        if cfg!(target_os = "macos") {
+            Self {
+                intra_threads: 4,
+                inter_threads: 2,
+            }
+        } else {
+            Self {
+                intra_threads: 16,
+                inter_threads: 8,
+            }
+        }
+    }
+}
+
+/// ort 스레드 수 인자로 변환
+fn thread_count(threads: usize) -> i16 {
+    threads.clamp(1, i16::MAX as usize) as i16
+}
+
+/// 로드된 세션 (임베디드 모델은 메모리에서, 외부 모델은 파일에서 로드)
+enum LoadedSession {
+    Embedded(ort::InMemorySession<'static>),
+    File(ort::Session),
+}
+
+impl std::ops::Deref for LoadedSession {
+    type Target = ort::Session;
+
+    fn deref(&self) -> &ort::Session {
+        match self {
+            LoadedSession::Embedded(session) => session,
+            LoadedSession::File(session) => session,
+        }
     }
 }
 
 /// 모델 세션을 캐시하는 구조체
 pub struct ModelCache {
     environment: Arc<Environment>,
-    session: Option<ort::InMemorySession<'static>>,
+    source: ModelSource,
+    options: SessionOptions,
+    session: Option<LoadedSession>,
 }
 
 impl ModelCache {
-    /// 새로운 모델 캐시 생성
+    /// 새로운 모델 캐시 생성 (임베디드 모델)
     pub fn new() -> anyhow::Result<Self> {
+        Self::with_source(ModelSource::Embedded)
+    }
+
+    /// 지정한 모델을 사용하는 캐시 생성 (세션은 첫 사용 시 로드)
+    pub fn with_source(source: ModelSource) -> anyhow::Result<Self> {
+        Self::with_options(source, SessionOptions::default())
+    }
+
+    /// 모델과 세션 옵션을 지정하여 캐시 생성
+    pub fn with_options(source: ModelSource, options: SessionOptions) -> anyhow::Result<Self> {
         let environment = Arc::new(
             Environment::builder()
                 .with_name("rf-detr-embedded")
@@ -347,81 +862,184 @@ impl ModelCache {
 
         Ok(Self {
             environment,
+            source,
+            options,
             session: None,
         })
     }
 
-    pub fn get_session(&mut self) -> anyhow::Result<&ort::InMemorySession<'static>> {
+    /// 사용 중인 모델 위치
+    pub fn source(&self) -> &ModelSource {
+        &self.source
+    }
+
+    /// 사용 중인 세션 옵션
+    pub fn options(&self) -> SessionOptions {
+        self.options
+    }
+
+    /// 모델 또는 세션 옵션 변경 (바뀐 경우 기존 세션을 폐기하고 true 반환)
+    pub fn configure(&mut self, source: ModelSource, options: SessionOptions) -> bool {
+        if self.source == source && self.options == options {
+            return false;
+        }
+        self.source = source;
+        self.options = options;
+        self.session = None;
+        true
+    }
+
+    /// 실행 공급자/스레드/메모리 옵션이 적용된 세션 빌더
+    fn session_builder(&self) -> anyhow::Result<SessionBuilder> {
+        #[cfg(target_os = "macos")]
+        let builder = SessionBuilder::new(&self.environment)?
+            .with_execution_providers([
+                ExecutionProvider::CoreML(CoreMLExecutionProviderOptions {
+                    use_cpu_only: false,
+                    enable_on_subgraph: true,
+                    only_enable_device_with_ane: true, // M4 ANE 활용
+                }),
+                ExecutionProvider::CPU(CPUExecutionProviderOptions::default()),
+            ])?
+            // 1. 최적화 레벨 조정 (성능 vs 초기화 시간)
+            .with_optimization_level(ort::GraphOptimizationLevel::Level1)?
+            // 2. 스레드 설정 (SessionOptions, 기본값은 M4 Mac 기준)
+            .with_intra_threads(thread_count(self.options.intra_threads))?
+            .with_inter_threads(thread_count(self.options.inter_threads))?
+            .with_parallel_execution(false)? // RF-DETR는 순차 실행이 더 빠름
+            // 3. 메모리 최적화
+            .with_memory_pattern(true)? // 고정 입력 크기라면 활성화
+            .with_allocator(ort::AllocatorType::Device)?; // GPU 메모리 사용
+        #[cfg(not(target_os = "macos"))]
+        let builder = SessionBuilder::new(&self.environment)?
+            .with_execution_providers([ExecutionProvider::CPU(
+                CPUExecutionProviderOptions::default(),
+            )])?
+            // 1. 최적화 레벨 조정 (성능 vs 초기화 시간)
+            .with_optimization_level(ort::GraphOptimizationLevel::Level1)?
+            // 2. 스레드 설정 (SessionOptions)
+            .with_intra_threads(thread_count(self.options.intra_threads))?
+            .with_inter_threads(thread_count(self.options.inter_threads))?
+            .with_parallel_execution(false)? // RF-DETR는 순차 실행이 더 빠름
+            // 3. 메모리 최적화
+            .with_memory_pattern(true)? // 고정 입력 크기라면 활성화
+            .with_allocator(ort::AllocatorType::Device)?; // GPU 메모리 사용
+        Ok(builder)
+    }
+
+    pub fn get_session(&mut self) -> anyhow::Result<&ort::Session> {
         if self.session.is_none() {
-            #[cfg(target_os = "macos")]
-            let session = SessionBuilder::new(&self.environment)?
-                .with_execution_providers([
-                    ExecutionProvider::CoreML(CoreMLExecutionProviderOptions {
-                        use_cpu_only: false,
-                        enable_on_subgraph: true,
-                        only_enable_device_with_ane: true, // M4 ANE 활용
-                    }),
-                    ExecutionProvider::CPU(CPUExecutionProviderOptions::default()),
-                ])?
-                // 1. 최적화 레벨 조정 (성능 vs 초기화 시간)
-                .with_optimization_level(ort::GraphOptimizationLevel::Level1)?
-                // 2. 스레드 설정 최적화 (M4 Mac 기준)
-                .with_intra_threads(4)? // M4 성능 코어 개수
-                .with_inter_threads(2)? // 병렬 실행용
-                .with_parallel_execution(false)? // RF-DETR는 순차 실행이 더 빠름
-                // 3. 메모리 최적화
-                .with_memory_pattern(true)? // 고정 입력 크기라면 활성화
-                .with_allocator(ort::AllocatorType::Device)? // GPU 메모리 사용
-                .with_model_from_memory(RF_DETR_ORIGINAL_ONNX)?;
-            #[cfg(not(target_os = "macos"))]
-            let session = SessionBuilder::new(&self.environment)?
-                .with_execution_providers([ExecutionProvider::CPU(
-                    CPUExecutionProviderOptions::default(),
-                )])?
-                // 1. 최적화 레벨 조정 (성능 vs 초기화 시간)
-                .with_optimization_level(ort::GraphOptimizationLevel::Level1)?
-                // 2. 스레드 설정 최적화 (M4 Mac 기준)
-                .with_intra_threads(16)? // M4 성능 코어 개수
-                .with_inter_threads(8)? // 병렬 실행용
-                .with_parallel_execution(false)? // RF-DETR는 순차 실행이 더 빠름
-                // 3. 메모리 최적화
-                .with_memory_pattern(true)? // 고정 입력 크기라면 활성화
-                .with_allocator(ort::AllocatorType::Device)? // GPU 메모리 사용
-                .with_model_from_memory(RF_DETR_ORIGINAL_ONNX)?;
+            let builder = self.session_builder()?;
+            let session = match &self.source {
+                ModelSource::Embedded => {
+                    LoadedSession::Embedded(builder.with_model_from_memory(RF_DETR_ORIGINAL_ONNX)?)
+                }
+                ModelSource::File(path) => LoadedSession::File(builder.with_model_from_file(path)?),
+            };
 
             self.session = Some(session);
-            println!("Loading model: RF-DETR Original (108 MB) - Optimized for M4");
+            println!("Loading model: {} - Optimized for M4", self.source.label());
         }
 
-        match self.session.as_ref() {
+        match self.session.as_deref() {
             Some(session) => Ok(session),
             None => Err(anyhow::anyhow!("Model session is not initialized")),
         }
     }
 
+    /// 세션이 이미 로드되었는지 확인
+    pub fn is_loaded(&self) -> bool {
+        self.session.is_some()
+    }
+
     /// 모델을 미리 로드
     pub fn preload_model(&mut self) -> anyhow::Result<()> {
         self.get_session()?;
         Ok(())
     }
+
+    /// 로드된 모델의 입출력 및 메타데이터 정보 (필요 시 모델 로드)
+    pub fn info(&mut self) -> anyhow::Result<ModelInfo> {
+        let session = self.get_session()?;
+        let tensor_info = |name: &str, dtype: String, dims: &[Option<u32>]| TensorInfo {
+            name: name.to_string(),
+            shape: dims.to_vec(),
+            dtype,
+        };
+
+        let mut info = ModelInfo {
+            inputs: session
+                .inputs
+                .iter()
+                .map(|i| tensor_info(&i.name, format!("{:?}", i.input_type), &i.dimensions))
+                .collect(),
+            outputs: session
+                .outputs
+                .iter()
+                .map(|o| tensor_info(&o.name, format!("{:?}", o.output_type), &o.dimensions))
+                .collect(),
+            ..Default::default()
+        };
+        model_info::parse_model_proto(&self.source.bytes()?, &mut info)?;
+        Ok(info)
+    }
 }
 
-/// 메인 객체 검출 함수 (캐시 사용)
-pub fn detect_objects_with_cache(
+/// EXIF 방향 태그 처리 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OrientationMode {
+    /// 방향 태그대로 회전/반전 (다른 뷰어에서 보이는 방향과 같음)
+    #[default]
+    Apply,
+    /// 파일에 저장된 픽셀 방향 그대로 사용
+    Raw,
+}
+
+/// 이미지 바이트 디코딩 (EXIF 방향 적용)
+pub fn decode_image(image_data: &[u8]) -> anyhow::Result<RgbImage> {
+    decode_image_with_orientation(image_data, OrientationMode::Apply)
+}
+
+/// EXIF 방향 처리 방식을 지정한 이미지 디코딩
+///
+/// 방향 태그가 없거나 읽을 수 없으면 저장된 방향 그대로 디코딩합니다.
+pub fn decode_image_with_orientation(
     image_data: &[u8],
-    cache: &mut ModelCache,
-) -> anyhow::Result<DetectionResult> {
-    // 이미지 로드
-    let img = ImageReader::new(std::io::Cursor::new(image_data))
+    mode: OrientationMode,
+) -> anyhow::Result<RgbImage> {
+    let mut decoder = ImageReader::new(std::io::Cursor::new(image_data))
         .with_guessed_format()?
-        .decode()?
-        .to_rgb8();
+        .into_decoder()?;
+    let orientation = match mode {
+        OrientationMode::Apply => decoder.orientation().unwrap_or(Orientation::NoTransforms),
+        OrientationMode::Raw => Orientation::NoTransforms,
+    };
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+    Ok(image.to_rgb8())
+}
+
+/// 디코딩된 이미지에 대해 단일 추론 패스 실행 (검출 결과, 추론 시간 ms)
+pub fn run_inference(
+    img: &RgbImage,
+    cache: &mut ModelCache,
+    input_size: u32,
+) -> anyhow::Result<(Vec<Detection>, f64)> {
+    run_inference_with_threshold(img, cache, input_size, CONFIDENCE_THRESHOLD)
+}
 
+/// 지정한 신뢰도 임계값으로 단일 추론 패스 실행
+pub fn run_inference_with_threshold(
+    img: &RgbImage,
+    cache: &mut ModelCache,
+    input_size: u32,
+    confidence_threshold: f32,
+) -> anyhow::Result<(Vec<Detection>, f64)> {
     // 캐시된 세션 가져오기
     let session = cache.get_session()?;
 
     // 이미지 전처리
-    let input_array = preprocess_image(&img)?;
+    let input_array = preprocess_image_with_size(img, input_size)?;
     let cow_array = CowArray::from(&input_array);
     let input_value = Value::from_array(session.allocator(), &cow_array)?;
 
@@ -444,13 +1062,95 @@ pub fn detect_objects_with_cache(
         let logits_view = logits_tensor.view();
         let boxes_view = boxes_tensor.view();
 
+        // 세그멘테이션/포즈 모델의 추가 출력 (마스크 로짓, 키포인트)
+        let extra_tensors = outputs[2..]
+            .iter()
+            .map(|output| output.try_extract::<f32>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let num_queries = boxes_view.shape().get(1).copied().unwrap_or(0);
+        let extra_views: Vec<_> = extra_tensors
+            .iter()
+            .enumerate()
+            .filter_map(|(i, tensor)| {
+                let view = tensor.view();
+                let name = session
+                    .outputs
+                    .get(i + 2)
+                    .map(|o| o.name.as_str())
+                    .unwrap_or("");
+                classify_extra_output(name, view.shape(), num_queries).map(|kind| (kind, view))
+            })
+            .collect();
+        let find = |kind: ExtraOutputKind| {
+            extra_views
+                .iter()
+                .find(|(k, _)| *k == kind)
+                .map(|(_, view)| view)
+        };
+        let extras = ExtraOutputs {
+            masks: find(ExtraOutputKind::Masks),
+            keypoints: find(ExtraOutputKind::Keypoints),
+        };
+
         // RF-DETR 출력 파싱
-        detections = parse_rf_detr_outputs(&logits_view, &boxes_view, img.width(), img.height())?;
+        detections = parse_rf_detr_outputs_with_extras(
+            &logits_view,
+            &boxes_view,
+            extras,
+            img.width(),
+            img.height(),
+            input_size,
+            confidence_threshold,
+        )?;
     }
 
+    Ok((detections, inference_time_ms))
+}
+
+/// 메인 객체 검출 함수 (캐시 사용)
+pub fn detect_objects_with_cache(
+    image_data: &[u8],
+    cache: &mut ModelCache,
+) -> anyhow::Result<DetectionResult> {
+    detect_objects_with_tta(image_data, cache, &TtaConfig::default())
+}
+
+/// TTA 설정을 적용한 객체 검출 함수 (캐시 사용)
+pub fn detect_objects_with_tta(
+    image_data: &[u8],
+    cache: &mut ModelCache,
+    tta: &TtaConfig,
+) -> anyhow::Result<DetectionResult> {
+    detect_objects_with_orientation(image_data, cache, tta, OrientationMode::Apply)
+}
+
+/// EXIF 방향 처리 방식을 지정한 `detect_objects_with_tta`
+///
+/// 박스 좌표는 방향을 적용한(또는 유지한) 디코딩 결과 이미지 기준입니다.
+pub fn detect_objects_with_orientation(
+    image_data: &[u8],
+    cache: &mut ModelCache,
+    tta: &TtaConfig,
+    orientation: OrientationMode,
+) -> anyhow::Result<DetectionResult> {
+    // 이미지 로드 (전처리 전에 EXIF 방향 처리)
+    let img = decode_image_with_orientation(image_data, orientation)?;
+
+    // 추론 실행 (TTA 비활성화 시 단일 패스)
+    let (detections, inference_time_ms) = if tta.is_enabled() {
+        tta::run_tta(&img, cache, tta)?
+    } else {
+        run_inference(&img, cache, MODEL_INPUT_SIZE)?
+    };
+
     // 바운딩 박스가 포함된 이미지 생성
     let mut result_image = img.clone();
-    draw_detections(&mut result_image, &detections);
+    draw_detections_with_skeleton(
+        &mut result_image,
+        &detections,
+        &DrawStyle::default(),
+        tta.skeleton.as_ref(),
+    );
 
     Ok(DetectionResult {
         detections,
@@ -465,3 +1165,58 @@ pub fn detect_objects(image_data: &[u8]) -> anyhow::Result<DetectionResult> {
     let mut cache = ModelCache::new()?;
     detect_objects_with_cache(image_data, &mut cache)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extra_outputs_must_match_query_count() {
+        assert_eq!(
+            classify_extra_output("pred_masks", &[1, 300, 108, 108], 300),
+            Some(ExtraOutputKind::Masks)
+        );
+        assert_eq!(
+            classify_extra_output("", &[1, 300, 17, 3], 300),
+            Some(ExtraOutputKind::Keypoints)
+        );
+        // 쿼리 축이 다르거나 배치가 1이 아닌 4차원 출력은 무시
+        assert_eq!(
+            classify_extra_output("pred_masks", &[1, 100, 108, 108], 300),
+            None
+        );
+        assert_eq!(classify_extra_output("", &[2, 300, 17, 3], 300), None);
+        assert_eq!(
+            classify_extra_output("pred_masks", &[1, 300, 108], 300),
+            None
+        );
+    }
+
+    #[test]
+    fn short_extra_output_is_an_error_not_a_panic() {
+        // 쿼리 4개 중 마지막 쿼리만 검출되지만 마스크 출력은 쿼리 2개분
+        let mut boxes = ArrayD::<f32>::zeros(IxDyn(&[1, 4, 4]));
+        boxes[[0, 3, 0]] = 0.5;
+        boxes[[0, 3, 1]] = 0.5;
+        boxes[[0, 3, 2]] = 0.2;
+        boxes[[0, 3, 3]] = 0.2;
+        let mut logits = ArrayD::<f32>::zeros(IxDyn(&[1, 4, 91]));
+        logits[[0, 3, 1]] = 10.0;
+        let masks = ArrayD::<f32>::zeros(IxDyn(&[1, 2, 8, 8]));
+        let masks_view = masks.view();
+        let extras = ExtraOutputs {
+            masks: Some(&masks_view),
+            keypoints: None,
+        };
+        let result = parse_rf_detr_outputs_with_extras(
+            &boxes.view(),
+            &logits.view(),
+            extras,
+            100,
+            100,
+            MODEL_INPUT_SIZE,
+            CONFIDENCE_THRESHOLD,
+        );
+        assert!(result.is_err());
+    }
+}
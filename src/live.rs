@@ -0,0 +1,302 @@
+use crate::batch::{collect_images, DEFAULT_EXTENSIONS};
+use crate::{decode_image_with_orientation, OrientationMode};
+use image::{Rgb, RgbImage};
+use imageproc::drawing::{draw_filled_circle_mut, draw_filled_rect_mut};
+use imageproc::rect::Rect;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+// 상수 정의
+const FPS_WINDOW: Duration = Duration::from_secs(2);
+const TEST_PATTERN_SIZE: (u32, u32) = (960, 540);
+const DEFAULT_SOURCE_FPS: f32 = 30.0;
+
+/// 소스에서 읽은 프레임
+pub struct Frame {
+    pub image: RgbImage,
+    /// 소스 기준 프레임 번호 (0부터)
+    pub index: u64,
+    /// 캡처 시각 (지연 시간 계산용)
+    pub captured_at: Instant,
+}
+
+/// 라이브 모드 프레임 공급원
+///
+/// 카메라, 비디오, 합성 패턴 등을 같은 방식으로 다루기 위한 트레이트입니다.
+/// 캡처 스레드에서 호출되므로 `Send`가 필요합니다.
+pub trait FrameSource: Send {
+    /// 표시용 이름
+    fn name(&self) -> String;
+
+    /// 다음 프레임 (소스가 끝나면 None)
+    fn next_frame(&mut self) -> anyhow::Result<Option<RgbImage>>;
+
+    /// 프레임 간격 (None이면 가능한 빨리 읽음)
+    fn frame_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// 세션을 멈출 정도는 아닌 문제 (건너뛴 입력 등), 가져가면 비워짐
+    fn take_warnings(&mut self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// 카메라 없이 동작 확인용 합성 테스트 패턴 (그라데이션 배경 위로 움직이는 도형)
+pub struct TestPatternSource {
+    width: u32,
+    height: u32,
+    fps: f32,
+    frame_index: u64,
+}
+
+impl Default for TestPatternSource {
+    fn default() -> Self {
+        Self::new(TEST_PATTERN_SIZE.0, TEST_PATTERN_SIZE.1, DEFAULT_SOURCE_FPS)
+    }
+}
+
+impl TestPatternSource {
+    pub fn new(width: u32, height: u32, fps: f32) -> Self {
+        Self {
+            width: width.max(16),
+            height: height.max(16),
+            fps,
+            frame_index: 0,
+        }
+    }
+
+    /// 프레임 번호에 해당하는 패턴 생성
+    fn render(&self, frame_index: u64) -> RgbImage {
+        let (w, h) = (self.width, self.height);
+        let t = frame_index as f32 / self.fps.max(1.0);
+        let mut image = RgbImage::from_fn(w, h, |x, y| {
+            let u = x as f32 / w as f32;
+            let v = y as f32 / h as f32;
+            Rgb([
+                (u * 160.0) as u8 + 40,
+                (v * 160.0) as u8 + 40,
+                (((t * 0.5).sin() * 0.5 + 0.5) * 120.0) as u8 + 60,
+            ])
+        });
+
+        // 좌우로 움직이는 사각형
+        let rect_w = w / 5;
+        let rect_h = h / 3;
+        let travel = (w - rect_w) as f32;
+        let x = ((t * 0.8).sin() * 0.5 + 0.5) * travel;
+        draw_filled_rect_mut(
+            &mut image,
+            Rect::at(x as i32, (h / 6) as i32).of_size(rect_w, rect_h),
+            Rgb([220, 40, 40]),
+        );
+
+        // 원 궤도를 도는 원
+        let radius = (h / 8) as i32;
+        let cx = w as f32 / 2.0 + (t * 1.3).cos() * w as f32 * 0.3;
+        let cy = h as f32 * 0.65 + (t * 1.3).sin() * h as f32 * 0.2;
+        draw_filled_circle_mut(
+            &mut image,
+            (cx as i32, cy as i32),
+            radius,
+            Rgb([40, 200, 60]),
+        );
+
+        image
+    }
+}
+
+impl FrameSource for TestPatternSource {
+    fn name(&self) -> String {
+        format!("Test pattern {}x{}", self.width, self.height)
+    }
+
+    fn next_frame(&mut self) -> anyhow::Result<Option<RgbImage>> {
+        let image = self.render(self.frame_index);
+        self.frame_index += 1;
+        Ok(Some(image))
+    }
+
+    fn frame_interval(&self) -> Option<Duration> {
+        (self.fps > 0.0).then(|| Duration::from_secs_f32(1.0 / self.fps))
+    }
+}
+
+/// 폴더의 이미지를 순서대로 반복 재생하는 소스
+///
+/// 읽거나 디코딩할 수 없는 파일은 목록에서 빼고 경고로 보고한 뒤 다음 이미지로 넘어갑니다.
+pub struct FolderLoopSource {
+    dir: PathBuf,
+    images: Vec<PathBuf>,
+    fps: f32,
+    position: usize,
+    orientation: OrientationMode,
+    warnings: Vec<String>,
+}
+
+impl FolderLoopSource {
+    /// 폴더 이미지 목록 수집 (하위 폴더 포함, 정렬된 순서, EXIF 방향 적용)
+    pub fn new(dir: impl AsRef<Path>, fps: f32) -> anyhow::Result<Self> {
+        Self::with_orientation(dir, fps, OrientationMode::Apply)
+    }
+
+    /// EXIF 방향 처리 방식을 지정한 `new`
+    pub fn with_orientation(
+        dir: impl AsRef<Path>,
+        fps: f32,
+        orientation: OrientationMode,
+    ) -> anyhow::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        let extensions: Vec<String> = DEFAULT_EXTENSIONS.iter().map(|e| e.to_string()).collect();
+        let images = collect_images(&dir, &extensions)?;
+        if images.is_empty() {
+            anyhow::bail!("No images found in {}", dir.display());
+        }
+        Ok(Self {
+            dir,
+            images,
+            fps,
+            position: 0,
+            orientation,
+            warnings: Vec::new(),
+        })
+    }
+
+    fn read_frame(&self, path: &Path) -> anyhow::Result<RgbImage> {
+        let data = std::fs::read(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        decode_image_with_orientation(&data, self.orientation)
+            .map_err(|e| anyhow::anyhow!("Failed to decode {}: {}", path.display(), e))
+    }
+}
+
+impl FrameSource for FolderLoopSource {
+    fn name(&self) -> String {
+        format!(
+            "Folder loop {} ({} images)",
+            self.dir.display(),
+            self.images.len()
+        )
+    }
+
+    fn next_frame(&mut self) -> anyhow::Result<Option<RgbImage>> {
+        while !self.images.is_empty() {
+            let index = self.position % self.images.len();
+            match self.read_frame(&self.images[index]) {
+                Ok(image) => {
+                    self.position = (index + 1) % self.images.len();
+                    return Ok(Some(image));
+                }
+                Err(e) => {
+                    // 다음 반복에서 다시 시도하지 않도록 제외 (같은 위치의 다음 이미지로 진행)
+                    self.images.remove(index);
+                    self.position = index;
+                    self.warnings.push(format!("Skipped {}", e));
+                }
+            }
+        }
+        anyhow::bail!("No readable images left in {}", self.dir.display())
+    }
+
+    fn frame_interval(&self) -> Option<Duration> {
+        (self.fps > 0.0).then(|| Duration::from_secs_f32(1.0 / self.fps))
+    }
+
+    fn take_warnings(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.warnings)
+    }
+}
+
+/// 가장 최근 프레임 하나만 보관하는 슬롯
+///
+/// 추론이 캡처를 따라가지 못하면 소비되지 않은 이전 프레임을 버리고 최신 프레임으로 교체하여
+/// 지연이 누적되지 않게 합니다.
+#[derive(Default)]
+pub struct LatestFrame {
+    slot: Mutex<Option<Frame>>,
+    ready: Condvar,
+    dropped: AtomicU64,
+    closed: AtomicBool,
+}
+
+impl LatestFrame {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 새 프레임 게시 (이전 프레임이 남아 있으면 버림)
+    pub fn publish(&self, frame: Frame) {
+        let mut slot = self.slot.lock().unwrap_or_else(|e| e.into_inner());
+        if slot.replace(frame).is_some() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        self.ready.notify_one();
+    }
+
+    /// 프레임을 꺼냄 (`timeout` 동안 대기, 닫혔거나 시간 초과 시 None)
+    pub fn take(&self, timeout: Duration) -> Option<Frame> {
+        let slot = self.slot.lock().unwrap_or_else(|e| e.into_inner());
+        let (mut slot, _) = self
+            .ready
+            .wait_timeout_while(slot, timeout, |s| s.is_none() && !self.is_closed())
+            .unwrap_or_else(|e| e.into_inner());
+        slot.take()
+    }
+
+    /// 슬롯 닫기 (대기 중인 소비자 깨움)
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.ready.notify_all();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// 추론 지연으로 버려진 프레임 수
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// 최근 구간 기준 초당 처리 프레임 측정
+#[derive(Debug, Clone, Default)]
+pub struct FpsMeter {
+    ticks: VecDeque<Instant>,
+}
+
+impl FpsMeter {
+    /// 프레임 처리 완료 기록
+    pub fn tick(&mut self, now: Instant) {
+        self.ticks.push_back(now);
+        while self
+            .ticks
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > FPS_WINDOW)
+        {
+            self.ticks.pop_front();
+        }
+    }
+
+    /// 초당 프레임 수 (기록이 2개 미만이면 0)
+    pub fn fps(&self) -> f32 {
+        match (self.ticks.front(), self.ticks.back()) {
+            (Some(first), Some(last)) if self.ticks.len() >= 2 => {
+                let span = last.duration_since(*first).as_secs_f32();
+                if span > 0.0 {
+                    (self.ticks.len() - 1) as f32 / span
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.ticks.clear();
+    }
+}
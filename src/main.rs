@@ -1,5 +1,17 @@
+mod cli;
 mod gui;
 
 fn main() {
-    gui::run_gui();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // 인자가 없으면 GUI 실행
+    if args.is_empty() {
+        gui::run_gui();
+        return;
+    }
+
+    if let Err(e) = cli::run(&args) {
+        eprintln!("오류: {e}");
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,359 @@
+use crate::letterbox_params;
+use ndarray::ArrayView2;
+use serde::{Deserialize, Serialize};
+
+// 상수 정의
+/// 이보다 신뢰도가 낮은 쿼리는 마스크를 디코딩하지 않음 (GUI 신뢰도 슬라이더 최솟값과 같음)
+pub const MASK_MIN_CONFIDENCE: f32 = 0.05;
+
+/// 원본 이미지 해상도의 이진 인스턴스 마스크
+///
+/// 메모리를 줄이기 위해 박스 영역만 저장하며, 직렬화할 때는 이미지 전체 기준
+/// COCO 비압축 RLE(`Rle`)로 변환됩니다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "Rle", try_from = "Rle")]
+pub struct Mask {
+    image_width: u32,
+    image_height: u32,
+    /// 저장 영역 [x, y, 너비, 높이] (원본 픽셀)
+    region: [u32; 4],
+    /// 영역 내부 픽셀 (행 우선, 0 또는 1)
+    data: Vec<u8>,
+}
+
+/// COCO 비압축 RLE (열 우선, 0의 길이부터 교대로 기록)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rle {
+    /// [높이, 너비]
+    pub size: [u32; 2],
+    pub counts: Vec<u32>,
+}
+
+impl Mask {
+    /// 영역의 각 픽셀에 대해 `f(x, y)`(원본 픽셀 좌표)로 마스크 생성 (영역은 이미지 안으로 잘라냄)
+    pub fn from_fn(
+        image_width: u32,
+        image_height: u32,
+        region: [u32; 4],
+        f: impl Fn(u32, u32) -> bool,
+    ) -> Self {
+        let x = region[0].min(image_width);
+        let y = region[1].min(image_height);
+        let width = region[2].min(image_width - x);
+        let height = region[3].min(image_height - y);
+        let mut data = Vec::with_capacity((width * height) as usize);
+        for py in y..y + height {
+            for px in x..x + width {
+                data.push(f(px, py) as u8);
+            }
+        }
+        Self {
+            image_width,
+            image_height,
+            region: [x, y, width, height],
+            data,
+        }
+    }
+
+    /// 마스크가 속한 원본 이미지 크기 (너비, 높이)
+    pub fn image_size(&self) -> (u32, u32) {
+        (self.image_width, self.image_height)
+    }
+
+    /// 저장 영역 [x, y, 너비, 높이] (영역 밖은 모두 0)
+    pub fn region(&self) -> [u32; 4] {
+        self.region
+    }
+
+    /// 원본 픽셀 (x, y)가 마스크에 속하는지 여부
+    pub fn contains(&self, x: u32, y: u32) -> bool {
+        let [rx, ry, rw, rh] = self.region;
+        if x < rx || y < ry || x >= rx + rw || y >= ry + rh {
+            return false;
+        }
+        self.data[((y - ry) * rw + (x - rx)) as usize] != 0
+    }
+
+    /// 마스크 픽셀 수
+    pub fn area(&self) -> u64 {
+        self.data.iter().map(|&v| v as u64).sum()
+    }
+
+    /// 좌우 반전 (TTA 반전 패스 역변환용)
+    pub fn flipped_horizontal(&self) -> Self {
+        let [x, y, width, height] = self.region;
+        let mut data = Vec::with_capacity(self.data.len());
+        for row in self.data.chunks(width.max(1) as usize) {
+            data.extend(row.iter().rev());
+        }
+        Self {
+            image_width: self.image_width,
+            image_height: self.image_height,
+            region: [self.image_width - x - width, y, width, height],
+            data,
+        }
+    }
+
+    /// 신뢰도 가중 다수결로 여러 마스크 병합 (TTA 융합용)
+    ///
+    /// 가중치 합의 절반 이상을 받은 픽셀만 남깁니다. 마스크가 없거나 원본 이미지 크기가
+    /// 서로 다르면 None입니다.
+    pub fn weighted_vote(masks: &[(&Mask, f32)]) -> Option<Self> {
+        let (first, _) = masks.first()?;
+        let (image_width, image_height) = first.image_size();
+        if masks
+            .iter()
+            .any(|(m, _)| m.image_size() != first.image_size())
+        {
+            return None;
+        }
+        let total: f32 = masks.iter().map(|(_, w)| w).sum();
+
+        // 모든 저장 영역을 감싸는 영역 밖은 어느 마스크에도 속하지 않음
+        let x1 = masks.iter().map(|(m, _)| m.region[0]).min()?;
+        let y1 = masks.iter().map(|(m, _)| m.region[1]).min()?;
+        let x2 = masks.iter().map(|(m, _)| m.region[0] + m.region[2]).max()?;
+        let y2 = masks.iter().map(|(m, _)| m.region[1] + m.region[3]).max()?;
+        Some(Self::from_fn(
+            image_width,
+            image_height,
+            [x1, y1, x2 - x1, y2 - y1],
+            |x, y| {
+                let votes: f32 = masks
+                    .iter()
+                    .filter(|(m, _)| m.contains(x, y))
+                    .map(|(_, w)| w)
+                    .sum();
+                votes * 2.0 >= total
+            },
+        ))
+    }
+
+    /// COCO 비압축 RLE로 인코딩 (이미지 전체, 열 우선)
+    pub fn to_rle(&self) -> Rle {
+        let mut counts = Vec::new();
+        let mut current = false;
+        let mut run = 0u32;
+        for x in 0..self.image_width {
+            for y in 0..self.image_height {
+                let value = self.contains(x, y);
+                if value != current {
+                    counts.push(run);
+                    current = value;
+                    run = 0;
+                }
+                run += 1;
+            }
+        }
+        counts.push(run);
+        Rle {
+            size: [self.image_height, self.image_width],
+            counts,
+        }
+    }
+
+    /// COCO 비압축 RLE 디코딩 (설정된 픽셀을 감싸는 영역만 저장)
+    pub fn from_rle(rle: &Rle) -> anyhow::Result<Self> {
+        let [height, width] = rle.size;
+        let total: u64 = rle.counts.iter().map(|&c| c as u64).sum();
+        if total != width as u64 * height as u64 {
+            anyhow::bail!(
+                "RLE 길이({})가 이미지 크기({}x{})와 맞지 않습니다",
+                total,
+                width,
+                height
+            );
+        }
+
+        // 열 우선 인덱스를 (x, y)로 풀어 설정된 픽셀 수집
+        let mut pixels = Vec::new();
+        let mut index = 0u64;
+        for (i, &count) in rle.counts.iter().enumerate() {
+            if i % 2 == 1 {
+                for p in index..index + count as u64 {
+                    pixels.push(((p / height as u64) as u32, (p % height as u64) as u32));
+                }
+            }
+            index += count as u64;
+        }
+
+        let Some(x1) = pixels.iter().map(|p| p.0).min() else {
+            return Ok(Self::from_fn(width, height, [0, 0, 0, 0], |_, _| false));
+        };
+        let x2 = pixels.iter().map(|p| p.0).max().unwrap_or(x1);
+        let y1 = pixels.iter().map(|p| p.1).min().unwrap_or(0);
+        let y2 = pixels.iter().map(|p| p.1).max().unwrap_or(y1);
+        let region_width = x2 - x1 + 1;
+        let mut data = vec![0u8; (region_width * (y2 - y1 + 1)) as usize];
+        for (x, y) in pixels {
+            data[((y - y1) * region_width + (x - x1)) as usize] = 1;
+        }
+        Ok(Self {
+            image_width: width,
+            image_height: height,
+            region: [x1, y1, region_width, y2 - y1 + 1],
+            data,
+        })
+    }
+}
+
+impl From<Mask> for Rle {
+    fn from(mask: Mask) -> Self {
+        mask.to_rle()
+    }
+}
+
+impl TryFrom<Rle> for Mask {
+    type Error = anyhow::Error;
+
+    fn try_from(rle: Rle) -> anyhow::Result<Self> {
+        Mask::from_rle(&rle)
+    }
+}
+
+/// 저해상도 마스크 로짓을 원본 이미지 해상도로 디코딩
+///
+/// 로짓은 레터박스된 입력 전체를 덮으므로, 박스 영역의 각 원본 픽셀을 레터박스 좌표로
+/// 옮겨 이중선형 보간한 뒤 0(시그모이드 0.5)을 기준으로 이진화합니다.
+pub fn decode_mask(
+    logits: &ArrayView2<f32>,
+    bbox: &[f32; 4],
+    image_width: u32,
+    image_height: u32,
+    input_size: u32,
+) -> Mask {
+    let (mask_height, mask_width) = logits.dim();
+    let (scale, offset_x, offset_y) = letterbox_params(image_width, image_height, input_size);
+    let (w, h) = (image_width as f32, image_height as f32);
+    let x1 = (bbox[0] * w).floor().clamp(0.0, w) as u32;
+    let y1 = (bbox[1] * h).floor().clamp(0.0, h) as u32;
+    let x2 = (bbox[2] * w).ceil().clamp(0.0, w) as u32;
+    let y2 = (bbox[3] * h).ceil().clamp(0.0, h) as u32;
+    if mask_width == 0 || mask_height == 0 {
+        return Mask::from_fn(image_width, image_height, [x1, y1, 0, 0], |_, _| false);
+    }
+
+    let to_mask_x = mask_width as f32 / input_size as f32;
+    let to_mask_y = mask_height as f32 / input_size as f32;
+    let sample = |mx: f32, my: f32| -> f32 {
+        let mx = mx.clamp(0.0, (mask_width - 1) as f32);
+        let my = my.clamp(0.0, (mask_height - 1) as f32);
+        let (x0, y0) = (mx.floor() as usize, my.floor() as usize);
+        let (xn, yn) = ((x0 + 1).min(mask_width - 1), (y0 + 1).min(mask_height - 1));
+        let (fx, fy) = (mx - x0 as f32, my - y0 as f32);
+        let top = logits[[y0, x0]] * (1.0 - fx) + logits[[y0, xn]] * fx;
+        let bottom = logits[[yn, x0]] * (1.0 - fx) + logits[[yn, xn]] * fx;
+        top * (1.0 - fy) + bottom * fy
+    };
+
+    Mask::from_fn(
+        image_width,
+        image_height,
+        [x1, y1, x2.saturating_sub(x1), y2.saturating_sub(y1)],
+        |x, y| {
+            // 픽셀 중심 → 레터박스 입력 픽셀 → 마스크 셀 좌표
+            let lx = (x as f32 + 0.5) * scale + offset_x;
+            let ly = (y as f32 + 0.5) * scale + offset_y;
+            sample(lx * to_mask_x - 0.5, ly * to_mask_y - 0.5) > 0.0
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn disk(width: u32, height: u32, cx: f32, cy: f32, radius: f32) -> Mask {
+        Mask::from_fn(width, height, [0, 0, width, height], |x, y| {
+            (x as f32 - cx).powi(2) + (y as f32 - cy).powi(2) <= radius * radius
+        })
+    }
+
+    fn pixels(mask: &Mask) -> Vec<(u32, u32)> {
+        let (width, height) = mask.image_size();
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| mask.contains(x, y))
+            .collect()
+    }
+
+    #[test]
+    fn rle_round_trip_keeps_pixels() {
+        let mask = disk(13, 9, 6.0, 4.0, 3.5);
+        let decoded = Mask::from_rle(&mask.to_rle()).unwrap();
+        assert_eq!(decoded.image_size(), (13, 9));
+        assert_eq!(pixels(&decoded), pixels(&mask));
+        assert_eq!(decoded.area(), mask.area());
+    }
+
+    #[test]
+    fn rle_is_column_major_and_starts_with_zeros() {
+        // 2x2 이미지에서 (1, 0) 픽셀만 설정: 열 우선 순서는 (0,0) (0,1) (1,0) (1,1)
+        let mask = Mask::from_fn(2, 2, [0, 0, 2, 2], |x, y| (x, y) == (1, 0));
+        let rle = mask.to_rle();
+        assert_eq!(rle.size, [2, 2]);
+        assert_eq!(rle.counts, vec![2, 1, 1]);
+
+        // 첫 픽셀이 설정되어 있으면 0 길이로 시작
+        let mask = Mask::from_fn(2, 2, [0, 0, 2, 2], |x, y| (x, y) == (0, 0));
+        assert_eq!(mask.to_rle().counts, vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn rle_round_trip_handles_empty_and_full_masks() {
+        let empty = Mask::from_fn(4, 3, [1, 1, 2, 1], |_, _| false);
+        let decoded = Mask::from_rle(&empty.to_rle()).unwrap();
+        assert_eq!(decoded.area(), 0);
+        assert_eq!(decoded.image_size(), (4, 3));
+
+        let full = Mask::from_fn(4, 3, [0, 0, 4, 3], |_, _| true);
+        assert_eq!(full.to_rle().counts, vec![0, 12]);
+        let decoded = Mask::from_rle(&full.to_rle()).unwrap();
+        assert_eq!(decoded.area(), 12);
+        assert_eq!(decoded.region(), [0, 0, 4, 3]);
+    }
+
+    #[test]
+    fn rle_serde_round_trip() {
+        let mask = disk(10, 10, 3.0, 6.0, 2.0);
+        let json = serde_json::to_string(&mask).unwrap();
+        let decoded: Mask = serde_json::from_str(&json).unwrap();
+        assert_eq!(pixels(&decoded), pixels(&mask));
+    }
+
+    #[test]
+    fn rle_with_wrong_length_is_rejected() {
+        let rle = Rle {
+            size: [2, 2],
+            counts: vec![1, 2],
+        };
+        assert!(Mask::from_rle(&rle).is_err());
+    }
+
+    #[test]
+    fn weighted_vote_keeps_majority_pixels() {
+        let left = Mask::from_fn(6, 2, [0, 0, 4, 2], |_, _| true);
+        let right = Mask::from_fn(6, 2, [2, 0, 4, 2], |_, _| true);
+        let middle = Mask::from_fn(6, 2, [1, 0, 4, 2], |_, _| true);
+
+        let fused = Mask::weighted_vote(&[(&left, 1.0), (&right, 1.0), (&middle, 1.0)]).unwrap();
+        let columns: Vec<u32> = pixels(&fused)
+            .into_iter()
+            .filter(|&(_, y)| y == 0)
+            .map(|(x, _)| x)
+            .collect();
+        assert_eq!(columns, vec![1, 2, 3, 4]);
+
+        // 가중치가 큰 마스크가 다수결을 좌우
+        let fused = Mask::weighted_vote(&[(&left, 3.0), (&right, 1.0)]).unwrap();
+        assert_eq!(pixels(&fused), pixels(&left));
+    }
+
+    #[test]
+    fn weighted_vote_rejects_mismatched_sizes() {
+        let a = Mask::from_fn(4, 4, [0, 0, 4, 4], |_, _| true);
+        let b = Mask::from_fn(5, 4, [0, 0, 5, 4], |_, _| true);
+        assert!(Mask::weighted_vote(&[(&a, 1.0), (&b, 1.0)]).is_none());
+        assert!(Mask::weighted_vote(&[]).is_none());
+    }
+}
@@ -0,0 +1,410 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// 입출력 텐서 정보
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TensorInfo {
+    pub name: String,
+    /// 차원 크기 (동적 차원은 None)
+    pub shape: Vec<Option<u32>>,
+    pub dtype: String,
+}
+
+impl TensorInfo {
+    /// "[1, 3, 560, 560]" 형태의 shape 문자열 (동적 차원은 "?")
+    pub fn shape_string(&self) -> String {
+        let dims: Vec<String> = self
+            .shape
+            .iter()
+            .map(|d| d.map(|v| v.to_string()).unwrap_or_else(|| "?".to_string()))
+            .collect();
+        format!("[{}]", dims.join(", "))
+    }
+}
+
+/// 로드된 모델의 메타데이터
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelInfo {
+    pub inputs: Vec<TensorInfo>,
+    pub outputs: Vec<TensorInfo>,
+    pub ir_version: Option<i64>,
+    /// (도메인, 버전) 목록 (기본 도메인은 빈 문자열)
+    pub opsets: Vec<(String, i64)>,
+    pub producer_name: String,
+    pub producer_version: String,
+    pub domain: String,
+    pub model_version: Option<i64>,
+    pub doc_string: String,
+    /// metadata_props에 저장된 사용자 정의 속성
+    pub metadata: BTreeMap<String, String>,
+    pub size_bytes: usize,
+}
+
+impl ModelInfo {
+    /// 기본 ONNX 도메인의 opset 버전
+    pub fn opset(&self) -> Option<i64> {
+        self.opsets
+            .iter()
+            .find(|(domain, _)| domain.is_empty() || domain == "ai.onnx")
+            .map(|(_, version)| *version)
+    }
+
+    /// 첫 번째 입력의 정사각형 공간 크기 (NCHW, 고정 크기인 경우)
+    pub fn input_size(&self) -> Option<u32> {
+        let shape = &self.inputs.first()?.shape;
+        match shape.as_slice() {
+            [_, _, Some(h), Some(w)] if h == w => Some(*h),
+            _ => None,
+        }
+    }
+
+    /// 메타데이터에 포함된 클래스 이름 목록 (JSON 배열/객체 또는 쉼표 구분 문자열)
+    pub fn class_names(&self) -> Option<Vec<String>> {
+        let raw = ["class_names", "names", "labels"]
+            .iter()
+            .find_map(|key| self.metadata.get(*key))?;
+
+        if let Ok(list) = serde_json::from_str::<Vec<String>>(raw) {
+            return Some(list);
+        }
+        if let Ok(map) = serde_json::from_str::<BTreeMap<String, String>>(raw) {
+            // {"0": "person", ...} 형태는 숫자 키 순서로 정렬
+            let mut entries: Vec<(i64, String)> = map
+                .into_iter()
+                .filter_map(|(k, v)| k.parse().ok().map(|k| (k, v)))
+                .collect();
+            entries.sort_by_key(|(k, _)| *k);
+            return Some(entries.into_iter().map(|(_, v)| v).collect());
+        }
+        Some(raw.split(',').map(|s| s.trim().to_string()).collect())
+    }
+
+    /// 한 줄 요약 (GUI 헤더용)
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        let name = if self.producer_name.is_empty() {
+            "ONNX model".to_string()
+        } else {
+            format!("{} {}", self.producer_name, self.producer_version)
+                .trim()
+                .to_string()
+        };
+        parts.push(format!("{:.0} MB", self.size_bytes as f64 / 1_000_000.0));
+        if let Some(opset) = self.opset() {
+            parts.push(format!("opset {opset}"));
+        }
+        if let Some(input) = self.inputs.first() {
+            parts.push(format!("input {}", input.shape_string()));
+        }
+        format!("{name} ({})", parts.join(", "))
+    }
+
+    /// 여러 줄 상세 설명 (CLI inspect용)
+    pub fn describe(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "Size: {:.1} MB", self.size_bytes as f64 / 1_000_000.0);
+        if let Some(ir) = self.ir_version {
+            let _ = writeln!(out, "IR version: {ir}");
+        }
+        for (domain, version) in &self.opsets {
+            let domain = if domain.is_empty() { "ai.onnx" } else { domain };
+            let _ = writeln!(out, "Opset: {domain} v{version}");
+        }
+        let _ = writeln!(
+            out,
+            "Producer: {} {}",
+            self.producer_name, self.producer_version
+        );
+        if !self.domain.is_empty() {
+            let _ = writeln!(out, "Domain: {}", self.domain);
+        }
+        if let Some(version) = self.model_version {
+            let _ = writeln!(out, "Model version: {version}");
+        }
+        if !self.doc_string.is_empty() {
+            let _ = writeln!(out, "Description: {}", self.doc_string);
+        }
+
+        let _ = writeln!(out, "Inputs:");
+        for t in &self.inputs {
+            let _ = writeln!(out, "  {}: {} {}", t.name, t.dtype, t.shape_string());
+        }
+        let _ = writeln!(out, "Outputs:");
+        for t in &self.outputs {
+            let _ = writeln!(out, "  {}: {} {}", t.name, t.dtype, t.shape_string());
+        }
+
+        if !self.metadata.is_empty() {
+            let _ = writeln!(out, "Metadata:");
+            for (key, value) in &self.metadata {
+                let _ = writeln!(out, "  {key}: {value}");
+            }
+        }
+        out
+    }
+}
+
+/// ONNX ModelProto의 최상위 필드를 읽어 메타데이터 채우기
+///
+/// onnxruntime은 opset과 metadata_props 키 목록을 노출하지 않으므로
+/// 모델 바이트의 protobuf 최상위 필드만 직접 해석합니다 (graph는 건너뜀).
+pub(crate) fn parse_model_proto(bytes: &[u8], info: &mut ModelInfo) -> anyhow::Result<()> {
+    info.size_bytes = bytes.len();
+    let mut reader = ProtoReader::new(bytes);
+
+    while let Some((field, value)) = reader.next_field()? {
+        match (field, value) {
+            (1, ProtoValue::Varint(v)) => info.ir_version = Some(v as i64),
+            (2, ProtoValue::Bytes(b)) => info.producer_name = String::from_utf8_lossy(b).into(),
+            (3, ProtoValue::Bytes(b)) => info.producer_version = String::from_utf8_lossy(b).into(),
+            (4, ProtoValue::Bytes(b)) => info.domain = String::from_utf8_lossy(b).into(),
+            (5, ProtoValue::Varint(v)) => info.model_version = Some(v as i64),
+            (6, ProtoValue::Bytes(b)) => info.doc_string = String::from_utf8_lossy(b).into(),
+            (8, ProtoValue::Bytes(b)) => {
+                // OperatorSetIdProto { domain = 1, version = 2 }
+                let mut domain = String::new();
+                let mut version = 0;
+                let mut sub = ProtoReader::new(b);
+                while let Some((f, v)) = sub.next_field()? {
+                    match (f, v) {
+                        (1, ProtoValue::Bytes(d)) => domain = String::from_utf8_lossy(d).into(),
+                        (2, ProtoValue::Varint(v)) => version = v as i64,
+                        _ => {}
+                    }
+                }
+                info.opsets.push((domain, version));
+            }
+            (14, ProtoValue::Bytes(b)) => {
+                // StringStringEntryProto { key = 1, value = 2 }
+                let mut key = String::new();
+                let mut value = String::new();
+                let mut sub = ProtoReader::new(b);
+                while let Some((f, v)) = sub.next_field()? {
+                    match (f, v) {
+                        (1, ProtoValue::Bytes(k)) => key = String::from_utf8_lossy(k).into(),
+                        (2, ProtoValue::Bytes(v)) => value = String::from_utf8_lossy(v).into(),
+                        _ => {}
+                    }
+                }
+                info.metadata.insert(key, value);
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+enum ProtoValue<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    Fixed,
+}
+
+/// 최소한의 protobuf wire format 리더
+struct ProtoReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ProtoReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn read_varint(&mut self) -> anyhow::Result<u64> {
+        let mut result = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self
+                .data
+                .get(self.pos)
+                .ok_or_else(|| anyhow::anyhow!("Truncated protobuf varint"))?;
+            self.pos += 1;
+            result |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+        Err(anyhow::anyhow!("Invalid protobuf varint"))
+    }
+
+    fn skip(&mut self, len: usize) -> anyhow::Result<()> {
+        // `len`은 파일에서 읽은 값이므로 덧셈 오버플로 없이 남은 길이와 비교
+        if len > self.data.len() - self.pos {
+            return Err(anyhow::anyhow!("Truncated protobuf field"));
+        }
+        self.pos += len;
+        Ok(())
+    }
+
+    fn next_field(&mut self) -> anyhow::Result<Option<(u64, ProtoValue<'a>)>> {
+        if self.pos >= self.data.len() {
+            return Ok(None);
+        }
+        let key = self.read_varint()?;
+        let field = key >> 3;
+        let value = match key & 0x7 {
+            0 => ProtoValue::Varint(self.read_varint()?),
+            1 => {
+                self.skip(8)?;
+                ProtoValue::Fixed
+            }
+            2 => {
+                let len = usize::try_from(self.read_varint()?)
+                    .map_err(|_| anyhow::anyhow!("Truncated protobuf field"))?;
+                let start = self.pos;
+                self.skip(len)?;
+                ProtoValue::Bytes(&self.data[start..self.pos])
+            }
+            5 => {
+                self.skip(4)?;
+                ProtoValue::Fixed
+            }
+            wire_type => {
+                return Err(anyhow::anyhow!(
+                    "Unsupported protobuf wire type {wire_type}"
+                ))
+            }
+        };
+        Ok(Some((field, value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn varint(mut value: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                return out;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    fn varint_field(field: u64, value: u64) -> Vec<u8> {
+        let mut out = varint(field << 3);
+        out.extend(varint(value));
+        out
+    }
+
+    fn bytes_field(field: u64, data: &[u8]) -> Vec<u8> {
+        let mut out = varint((field << 3) | 2);
+        out.extend(varint(data.len() as u64));
+        out.extend_from_slice(data);
+        out
+    }
+
+    fn parse(bytes: &[u8]) -> anyhow::Result<ModelInfo> {
+        let mut info = ModelInfo::default();
+        parse_model_proto(bytes, &mut info)?;
+        Ok(info)
+    }
+
+    #[test]
+    fn parses_minimal_model_proto() {
+        let opset = [bytes_field(1, b""), varint_field(2, 17)].concat();
+        let custom_opset = [bytes_field(1, b"com.microsoft"), varint_field(2, 1)].concat();
+        let metadata = [
+            bytes_field(1, b"names"),
+            bytes_field(2, br#"["cat","dog"]"#),
+        ]
+        .concat();
+        let bytes = [
+            varint_field(1, 8),
+            bytes_field(2, b"pytorch"),
+            bytes_field(3, b"2.1"),
+            varint_field(5, 3),
+            // graph는 내용을 해석하지 않고 건너뜀
+            bytes_field(7, &[0xff, 0xff, 0xff]),
+            bytes_field(8, &opset),
+            bytes_field(8, &custom_opset),
+            bytes_field(14, &metadata),
+            // fixed64 / fixed32 필드도 건너뜀
+            [vec![(20 << 3) | 1], vec![0; 8]].concat(),
+            [vec![(21 << 3) | 5], vec![0; 4]].concat(),
+        ]
+        .concat();
+
+        let info = parse(&bytes).unwrap();
+        assert_eq!(info.size_bytes, bytes.len());
+        assert_eq!(info.ir_version, Some(8));
+        assert_eq!(info.producer_name, "pytorch");
+        assert_eq!(info.producer_version, "2.1");
+        assert_eq!(info.model_version, Some(3));
+        assert_eq!(
+            info.opsets,
+            vec![(String::new(), 17), ("com.microsoft".to_string(), 1)]
+        );
+        assert_eq!(info.opset(), Some(17));
+        assert_eq!(
+            info.class_names(),
+            Some(vec!["cat".to_string(), "dog".to_string()])
+        );
+    }
+
+    #[test]
+    fn empty_buffer_is_an_empty_model() {
+        assert_eq!(parse(&[]).unwrap().ir_version, None);
+    }
+
+    #[test]
+    fn rejects_truncated_varint() {
+        // 계속 비트가 켜진 채로 끝나는 값
+        assert!(parse(&[0x08, 0x80]).is_err());
+        // 키 자체가 잘린 경우
+        assert!(parse(&[0x80]).is_err());
+    }
+
+    #[test]
+    fn rejects_overlong_varint() {
+        let mut bytes = vec![0x08];
+        bytes.extend([0xff; 10]);
+        bytes.push(0x01);
+        assert!(parse(&bytes).is_err());
+    }
+
+    #[test]
+    fn rejects_length_beyond_buffer() {
+        let mut bytes = bytes_field(2, b"pytorch");
+        bytes.truncate(bytes.len() - 1);
+        assert!(parse(&bytes).is_err());
+
+        // 중첩 메시지 안의 잘린 필드도 오류
+        let opset = [bytes_field(1, b"ai.onnx"), varint_field(2, 17)].concat();
+        let mut broken = opset.clone();
+        broken[1] = 100;
+        assert!(parse(&bytes_field(8, &broken)).is_err());
+    }
+
+    #[test]
+    fn rejects_max_length_without_overflow() {
+        let mut bytes = varint((2 << 3) | 2);
+        bytes.extend(varint(u64::MAX));
+        bytes.extend_from_slice(b"abc");
+        assert!(parse(&bytes).is_err());
+
+        // 앞 필드를 읽은 뒤 (pos > 0) 에도 동일
+        let mut bytes = varint_field(1, 8);
+        bytes.extend(varint((6 << 3) | 2));
+        bytes.extend(varint(u64::MAX - 1));
+        assert!(parse(&bytes).is_err());
+    }
+
+    #[test]
+    fn rejects_fixed_field_beyond_buffer() {
+        assert!(parse(&[(20 << 3) | 1, 0, 0, 0]).is_err());
+        assert!(parse(&[(21 << 3) | 5, 0]).is_err());
+    }
+
+    #[test]
+    fn rejects_unsupported_wire_type() {
+        // 3 = start group (deprecated)
+        assert!(parse(&[(1 << 3) | 3]).is_err());
+    }
+}
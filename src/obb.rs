@@ -0,0 +1,393 @@
+use crate::{iou, letterbox_params, Detection};
+use image::{Rgb, RgbImage};
+use imageproc::drawing::draw_line_segment_mut;
+use serde::{Deserialize, Serialize};
+
+// 상수 정의
+/// 회전 박스 검출에 적용하는 NMS IoU 임계값
+pub const OBB_NMS_IOU: f32 = 0.5;
+
+/// 회전된 바운딩 박스 (원본 이미지 픽셀 좌표)
+///
+/// 정규화 좌표는 가로/세로 배율이 달라 각도가 왜곡되므로 픽셀 좌표로 저장합니다.
+/// 각도는 라디안이며, y축이 아래를 향하는 이미지 좌표계에서 x축 기준 시계 방향입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OrientedBox {
+    pub cx: f32,
+    pub cy: f32,
+    pub width: f32,
+    pub height: f32,
+    pub angle: f32,
+}
+
+impl OrientedBox {
+    /// 레터박스 입력 기준 정규화 (cx, cy, w, h)와 각도를 원본 픽셀 좌표로 변환
+    ///
+    /// 레터박싱은 가로/세로에 같은 배율을 쓰므로 각도는 그대로 유지됩니다.
+    pub fn from_letterbox(
+        [cx, cy, w, h]: [f32; 4],
+        angle: f32,
+        original_width: u32,
+        original_height: u32,
+        input_size: u32,
+    ) -> Self {
+        let (scale, offset_x, offset_y) =
+            letterbox_params(original_width, original_height, input_size);
+        let input = input_size as f32;
+        Self {
+            cx: (cx * input - offset_x) / scale,
+            cy: (cy * input - offset_y) / scale,
+            width: w * input / scale,
+            height: h * input / scale,
+            angle,
+        }
+    }
+
+    /// 네 꼭짓점 (픽셀 좌표, 회전 방향 순서)
+    pub fn corners(&self) -> [(f32, f32); 4] {
+        let (sin, cos) = self.angle.sin_cos();
+        let (hw, hh) = (self.width / 2.0, self.height / 2.0);
+        [(-hw, -hh), (hw, -hh), (hw, hh), (-hw, hh)]
+            .map(|(dx, dy)| (self.cx + dx * cos - dy * sin, self.cy + dx * sin + dy * cos))
+    }
+
+    /// 감싸는 축 정렬 박스 (이미지 크기로 정규화, 0~1로 잘라냄)
+    pub fn enclosing_bbox(&self, image_width: u32, image_height: u32) -> [f32; 4] {
+        let corners = self.corners();
+        let (w, h) = (image_width as f32, image_height as f32);
+        let min_x = corners.iter().map(|c| c.0).fold(f32::INFINITY, f32::min);
+        let max_x = corners
+            .iter()
+            .map(|c| c.0)
+            .fold(f32::NEG_INFINITY, f32::max);
+        let min_y = corners.iter().map(|c| c.1).fold(f32::INFINITY, f32::min);
+        let max_y = corners
+            .iter()
+            .map(|c| c.1)
+            .fold(f32::NEG_INFINITY, f32::max);
+        [
+            (min_x / w).clamp(0.0, 1.0),
+            (min_y / h).clamp(0.0, 1.0),
+            (max_x / w).clamp(0.0, 1.0),
+            (max_y / h).clamp(0.0, 1.0),
+        ]
+    }
+
+    /// 좌우 반전 (TTA 반전 패스 역변환용)
+    pub fn flipped_horizontal(&self, image_width: u32) -> Self {
+        Self {
+            cx: image_width as f32 - self.cx,
+            angle: -self.angle,
+            ..*self
+        }
+    }
+
+    pub fn area(&self) -> f32 {
+        self.width.max(0.0) * self.height.max(0.0)
+    }
+
+    /// 같은 박스를 나타내는 표현 중 각도가 `reference`에 가장 가까운 것
+    ///
+    /// 회전 박스는 각도가 π 차이 나도 같고, π/2 차이 나면 너비/높이를 바꾼 것과 같으므로
+    /// 각도 차이를 [-π/4, π/4]로 맞춥니다.
+    fn aligned_to(&self, reference: f32) -> Self {
+        use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+        let mut aligned = *self;
+        let mut diff = (self.angle - reference).rem_euclid(std::f32::consts::PI);
+        if diff > FRAC_PI_2 {
+            diff -= std::f32::consts::PI;
+        }
+        if diff.abs() > FRAC_PI_4 {
+            diff -= FRAC_PI_2 * diff.signum();
+            std::mem::swap(&mut aligned.width, &mut aligned.height);
+        }
+        aligned.angle = reference + diff;
+        aligned
+    }
+
+    /// 가중 평균 회전 박스 (TTA 융합용, 각도는 첫 박스 기준으로 표현을 맞춘 뒤 평균)
+    pub fn weighted_average(boxes: &[(&OrientedBox, f32)]) -> Option<Self> {
+        let (first, _) = boxes.first()?;
+        let total: f32 = boxes.iter().map(|(_, w)| w).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut fused = Self {
+            cx: 0.0,
+            cy: 0.0,
+            width: 0.0,
+            height: 0.0,
+            angle: 0.0,
+        };
+        for (obb, weight) in boxes {
+            let aligned = obb.aligned_to(first.angle);
+            fused.cx += aligned.cx * weight;
+            fused.cy += aligned.cy * weight;
+            fused.width += aligned.width * weight;
+            fused.height += aligned.height * weight;
+            fused.angle += aligned.angle * weight;
+        }
+        fused.cx /= total;
+        fused.cy /= total;
+        fused.width /= total;
+        fused.height /= total;
+        fused.angle /= total;
+        Some(fused)
+    }
+}
+
+/// 다각형 면적 (신발끈 공식, 부호 없음)
+fn polygon_area(points: &[(f32, f32)]) -> f32 {
+    let n = points.len();
+    if n < 3 {
+        return 0.0;
+    }
+    let twice: f32 = (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum();
+    twice.abs() / 2.0
+}
+
+/// 볼록 다각형 교집합 (Sutherland–Hodgman, `clip`은 볼록 다각형)
+fn clip_polygon(subject: &[(f32, f32)], clip: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    // 면적이 0인 clip(한 점/선분)과는 겹치는 영역이 없음
+    if clip.len() < 3 || polygon_area(clip) == 0.0 {
+        return Vec::new();
+    }
+    // 꼭짓점 순서(시계/반시계)에 관계없이 안쪽 판정이 되도록 방향 부호 사용
+    let orientation = {
+        let n = clip.len();
+        (0..n)
+            .map(|i| {
+                let (a, b) = (clip[i], clip[(i + 1) % n]);
+                a.0 * b.1 - b.0 * a.1
+            })
+            .sum::<f32>()
+            .signum()
+    };
+    let side = |a: (f32, f32), b: (f32, f32), p: (f32, f32)| {
+        ((b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)) * orientation
+    };
+    let intersect = |a: (f32, f32), b: (f32, f32), p: (f32, f32), q: (f32, f32)| {
+        let (sp, sq) = (side(a, b, p), side(a, b, q));
+        let t = sp / (sp - sq);
+        (p.0 + (q.0 - p.0) * t, p.1 + (q.1 - p.1) * t)
+    };
+
+    let mut output = subject.to_vec();
+    for i in 0..clip.len() {
+        let (a, b) = (clip[i], clip[(i + 1) % clip.len()]);
+        let input = std::mem::take(&mut output);
+        for j in 0..input.len() {
+            let (p, q) = (input[j], input[(j + 1) % input.len()]);
+            let (p_in, q_in) = (side(a, b, p) >= 0.0, side(a, b, q) >= 0.0);
+            if p_in {
+                output.push(p);
+                if !q_in {
+                    output.push(intersect(a, b, p, q));
+                }
+            } else if q_in {
+                output.push(intersect(a, b, p, q));
+            }
+        }
+        if output.is_empty() {
+            break;
+        }
+    }
+    output
+}
+
+/// 두 회전 박스의 IoU
+pub fn rotated_iou(a: &OrientedBox, b: &OrientedBox) -> f32 {
+    let intersection = polygon_area(&clip_polygon(&a.corners(), &b.corners()));
+    let union = a.area() + b.area() - intersection;
+    if union <= 0.0 {
+        0.0
+    } else {
+        intersection / union
+    }
+}
+
+/// 신뢰도 순 NMS (둘 다 회전 박스가 있으면 회전 IoU, 아니면 축 정렬 IoU 사용)
+///
+/// 같은 클래스끼리만 억제합니다.
+pub fn nms(mut detections: Vec<Detection>, iou_threshold: f32) -> Vec<Detection> {
+    detections.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    let mut kept: Vec<Detection> = Vec::with_capacity(detections.len());
+    for detection in detections {
+        let suppressed = kept.iter().any(|k| {
+            if k.class_id != detection.class_id {
+                return false;
+            }
+            let overlap = match (&k.obb, &detection.obb) {
+                (Some(a), Some(b)) => rotated_iou(a, b),
+                _ => iou(&k.bbox, &detection.bbox),
+            };
+            overlap > iou_threshold
+        });
+        if !suppressed {
+            kept.push(detection);
+        }
+    }
+    kept
+}
+
+/// 회전 박스 그리기 (두께만큼 중심 쪽으로 줄여 겹쳐 그림)
+pub fn draw_oriented_box(image: &mut RgbImage, obb: &OrientedBox, color: Rgb<u8>, line_width: u32) {
+    for inset in 0..line_width.max(1) {
+        let shrunk = OrientedBox {
+            width: obb.width - 2.0 * inset as f32,
+            height: obb.height - 2.0 * inset as f32,
+            ..*obb
+        };
+        if shrunk.width < 1.0 || shrunk.height < 1.0 {
+            break;
+        }
+        let corners = shrunk.corners();
+        for i in 0..4 {
+            draw_line_segment_mut(image, corners[i], corners[(i + 1) % 4], color);
+        }
+    }
+}
+
+/// DOTA 라벨 (`x1 y1 x2 y2 x3 y3 x4 y4 클래스 difficult`, 픽셀 좌표)
+///
+/// 회전 박스가 없는 검출은 축 정렬 박스의 네 꼭짓점을 사용합니다.
+pub fn to_dota(detections: &[Detection], image_width: u32, image_height: u32) -> String {
+    let (w, h) = (image_width as f32, image_height as f32);
+    let mut out = String::new();
+    for d in detections {
+        let corners = match &d.obb {
+            Some(obb) => obb.corners(),
+            None => {
+                let [x1, y1, x2, y2] = d.bbox;
+                [
+                    (x1 * w, y1 * h),
+                    (x2 * w, y1 * h),
+                    (x2 * w, y2 * h),
+                    (x1 * w, y2 * h),
+                ]
+            }
+        };
+        let coords: Vec<String> = corners
+            .iter()
+            .flat_map(|(x, y)| [format!("{:.1}", x), format!("{:.1}", y)])
+            .collect();
+        // DOTA 클래스 이름에는 공백을 쓰지 않음
+        out.push_str(&format!(
+            "{} {} 0\n",
+            coords.join(" "),
+            d.class_name.replace(' ', "-")
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    fn obb(cx: f32, cy: f32, width: f32, height: f32, angle: f32) -> OrientedBox {
+        OrientedBox {
+            cx,
+            cy,
+            width,
+            height,
+            angle,
+        }
+    }
+
+    fn detection(obb: OrientedBox, confidence: f32, class_id: u32) -> Detection {
+        Detection {
+            bbox: obb.enclosing_bbox(100, 100),
+            confidence,
+            class_id,
+            class_name: format!("class {}", class_id),
+            mask: None,
+            keypoints: None,
+            obb: Some(obb),
+            secondary: Vec::new(),
+            original_class_id: None,
+        }
+    }
+
+    #[test]
+    fn identical_boxes_have_iou_one() {
+        let a = obb(50.0, 50.0, 20.0, 10.0, 0.3);
+        assert!((rotated_iou(&a, &a) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn disjoint_boxes_have_iou_zero() {
+        let a = obb(20.0, 20.0, 10.0, 10.0, 0.2);
+        let b = obb(80.0, 80.0, 10.0, 10.0, -0.4);
+        assert_eq!(rotated_iou(&a, &b), 0.0);
+    }
+
+    #[test]
+    fn square_rotated_45_degrees_overlaps_as_octagon() {
+        // 같은 중심의 정사각형과 45도 회전한 정사각형의 교집합은 정팔각형 (넓이 2(√2-1)s²)
+        let a = obb(50.0, 50.0, 20.0, 20.0, 0.0);
+        let b = obb(50.0, 50.0, 20.0, 20.0, FRAC_PI_4);
+        let intersection = 2.0 * (2f32.sqrt() - 1.0) * 400.0;
+        let expected = intersection / (800.0 - intersection);
+        assert!((rotated_iou(&a, &b) - expected).abs() < 1e-3);
+        assert!((rotated_iou(&b, &a) - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn degenerate_boxes_have_iou_zero() {
+        let a = obb(50.0, 50.0, 20.0, 20.0, 0.0);
+        let line = obb(50.0, 50.0, 20.0, 0.0, 0.5);
+        let point = obb(50.0, 50.0, 0.0, 0.0, 0.0);
+        for degenerate in [line, point] {
+            assert_eq!(rotated_iou(&a, &degenerate), 0.0);
+            assert_eq!(rotated_iou(&degenerate, &a), 0.0);
+            assert_eq!(rotated_iou(&degenerate, &degenerate), 0.0);
+        }
+    }
+
+    #[test]
+    fn clip_polygon_ignores_winding_order() {
+        let square = [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)];
+        let shifted = [(2.0, 2.0), (6.0, 2.0), (6.0, 6.0), (2.0, 6.0)];
+        let mut reversed = shifted;
+        reversed.reverse();
+        assert!((polygon_area(&clip_polygon(&square, &shifted)) - 4.0).abs() < 1e-5);
+        assert!((polygon_area(&clip_polygon(&square, &reversed)) - 4.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn nms_uses_rotated_overlap_per_class() {
+        // 직각으로 교차하는 가는 박스 둘: 감싸는 박스는 같지만 회전 IoU는 작음
+        let horizontal = detection(obb(50.0, 50.0, 60.0, 6.0, 0.0), 0.9, 1);
+        let vertical = detection(obb(50.0, 50.0, 60.0, 6.0, FRAC_PI_2), 0.8, 1);
+        let duplicate = detection(obb(51.0, 50.0, 60.0, 6.0, 0.02), 0.7, 1);
+        let other_class = detection(obb(50.0, 50.0, 60.0, 6.0, 0.0), 0.6, 2);
+
+        let kept = nms(
+            vec![duplicate, other_class, vertical, horizontal],
+            OBB_NMS_IOU,
+        );
+        let kept: Vec<(u32, f32)> = kept.iter().map(|d| (d.class_id, d.confidence)).collect();
+        assert_eq!(kept, vec![(1, 0.9), (1, 0.8), (2, 0.6)]);
+    }
+
+    #[test]
+    fn weighted_average_aligns_equivalent_angles() {
+        // 각도가 π 차이 나거나, π/2 차이 나면서 너비/높이가 바뀐 표현은 같은 박스
+        let a = obb(10.0, 10.0, 20.0, 10.0, 0.1);
+        let b = obb(12.0, 10.0, 20.0, 10.0, 0.1 + PI);
+        let c = obb(11.0, 10.0, 10.0, 20.0, 0.1 + FRAC_PI_2);
+        let fused = OrientedBox::weighted_average(&[(&a, 1.0), (&b, 1.0), (&c, 2.0)]).unwrap();
+        assert!((fused.cx - 11.0).abs() < 1e-4);
+        assert!((fused.width - 20.0).abs() < 1e-4);
+        assert!((fused.height - 10.0).abs() < 1e-4);
+        assert!((fused.angle - 0.1).abs() < 1e-4);
+        assert!(OrientedBox::weighted_average(&[]).is_none());
+    }
+}
@@ -0,0 +1,544 @@
+use crate::{
+    run_inference_with_threshold, Detection, ModelCache, ModelInfo, ModelSource, SessionOptions,
+    CONFIDENCE_THRESHOLD, MODEL_INPUT_SIZE,
+};
+use image::RgbImage;
+use ndarray::{Array4, CowArray};
+use ort::Value;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+// 상수 정의
+/// 분류기 기본 입력 크기 (정사각형)
+pub const DEFAULT_CLASSIFIER_INPUT_SIZE: u32 = 224;
+/// ImageNet 정규화 평균/표준편차 (RGB, 0~1 스케일)
+pub const IMAGENET_MEAN: [f32; 3] = [0.485, 0.456, 0.406];
+pub const IMAGENET_STD: [f32; 3] = [0.229, 0.224, 0.225];
+/// 기본 상위 라벨 수
+pub const DEFAULT_TOP_K: usize = 3;
+/// 한 번에 분류기에 넣는 최대 크롭 수
+pub const DEFAULT_BATCH_SIZE: usize = 16;
+
+/// 2단계 분류기가 붙인 보조 라벨
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecondaryLabel {
+    /// 라벨을 붙인 분류 단계 이름
+    pub stage: String,
+    /// 분류기 출력 인덱스 (0부터 시작)
+    pub class_id: u32,
+    pub label: String,
+    /// 소프트맥스 확률 (`softmax`가 꺼져 있으면 모델 출력 그대로)
+    pub score: f32,
+}
+
+/// 분류 단계 설정 (어떤 검출 클래스를 어떤 분류 모델로 분류할지)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StageConfig {
+    /// 단계 이름 (보조 라벨의 `stage`로 기록)
+    pub name: String,
+    /// 분류할 검출 클래스 이름 (비어 있으면 모든 클래스)
+    pub classes: Vec<String>,
+    /// 분류 모델 ONNX 파일 (입력 [N, 3, H, W], 출력 [N, 클래스 수])
+    pub model: PathBuf,
+    /// 분류기 클래스 이름 (출력 인덱스 순서)
+    pub labels: Vec<String>,
+    /// 클래스 이름 파일 (한 줄에 하나, `labels`가 비어 있을 때 사용)
+    pub labels_file: Option<PathBuf>,
+    /// 입력 크기 (크롭을 정사각형으로 리사이즈)
+    pub input_size: u32,
+    pub mean: [f32; 3],
+    pub std: [f32; 3],
+    /// 출력에 소프트맥스를 적용할지 여부 (모델이 이미 확률을 내면 false)
+    pub softmax: bool,
+    /// 크롭 패딩 (박스 너비/높이 대비 한쪽 여백 비율)
+    pub padding: f32,
+    pub top_k: usize,
+    /// 이보다 낮은 점수의 보조 라벨은 버림
+    pub min_score: f32,
+    /// 이보다 신뢰도가 낮은 검출은 분류하지 않음
+    pub min_detection_confidence: f32,
+    pub batch_size: usize,
+    pub session: SessionOptions,
+}
+
+impl Default for StageConfig {
+    fn default() -> Self {
+        Self {
+            name: "classifier".to_string(),
+            classes: Vec::new(),
+            model: PathBuf::new(),
+            labels: Vec::new(),
+            labels_file: None,
+            input_size: DEFAULT_CLASSIFIER_INPUT_SIZE,
+            mean: IMAGENET_MEAN,
+            std: IMAGENET_STD,
+            softmax: true,
+            padding: 0.0,
+            top_k: DEFAULT_TOP_K,
+            min_score: 0.0,
+            min_detection_confidence: 0.0,
+            batch_size: DEFAULT_BATCH_SIZE,
+            session: SessionOptions::default(),
+        }
+    }
+}
+
+impl StageConfig {
+    /// 이 단계가 해당 검출을 분류하는지 여부
+    pub fn applies_to(&self, detection: &Detection) -> bool {
+        detection.confidence >= self.min_detection_confidence
+            && (self.classes.is_empty() || self.classes.iter().any(|c| *c == detection.class_name))
+    }
+}
+
+/// 2단계 파이프라인 설정 (JSON 파일로 저장/로드)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PipelineConfig {
+    /// 검출 모델
+    pub detector: ModelSource,
+    pub detector_session: SessionOptions,
+    pub input_size: u32,
+    /// 검출 기본 신뢰도 임계값
+    pub confidence_threshold: f32,
+    /// 클래스 이름별 검출 임계값 재정의
+    pub class_thresholds: BTreeMap<String, f32>,
+    /// 분류 단계 (한 검출에 여러 단계가 적용될 수 있음)
+    pub stages: Vec<StageConfig>,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            detector: ModelSource::Embedded,
+            detector_session: SessionOptions::default(),
+            input_size: MODEL_INPUT_SIZE,
+            confidence_threshold: CONFIDENCE_THRESHOLD,
+            class_thresholds: BTreeMap::new(),
+            stages: Vec::new(),
+        }
+    }
+}
+
+impl PipelineConfig {
+    /// JSON 설정 파일 로드 (상대 경로는 설정 파일 위치 기준)
+    pub fn from_json_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            anyhow::anyhow!(
+                "파이프라인 설정을 읽을 수 없습니다 ({}): {}",
+                path.display(),
+                e
+            )
+        })?;
+        let mut config: Self = serde_json::from_str(&text)?;
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        let resolve = |p: &mut PathBuf| {
+            if p.is_relative() {
+                *p = base.join(&*p);
+            }
+        };
+        if let ModelSource::File(model) = &mut config.detector {
+            resolve(model);
+        }
+        for stage in &mut config.stages {
+            resolve(&mut stage.model);
+            if let Some(labels_file) = &mut stage.labels_file {
+                resolve(labels_file);
+            }
+        }
+        Ok(config)
+    }
+
+    /// 클래스의 검출 임계값 (재정의가 없으면 기본값)
+    pub fn threshold_for(&self, class_name: &str) -> f32 {
+        self.class_thresholds
+            .get(class_name)
+            .copied()
+            .unwrap_or(self.confidence_threshold)
+    }
+
+    /// 검출기에 넘길 임계값 (모든 클래스 임계값 중 최솟값)
+    fn min_threshold(&self) -> f32 {
+        self.class_thresholds
+            .values()
+            .copied()
+            .fold(self.confidence_threshold, f32::min)
+    }
+}
+
+/// 분류 모델 세션을 캐시하는 구조체 (`ModelCache`와 같은 지연 로딩)
+pub struct ClassifierCache {
+    cache: ModelCache,
+    labels: Vec<String>,
+}
+
+impl ClassifierCache {
+    /// 분류 모델 캐시 생성 (세션은 첫 사용 시 로드, 라벨 파일은 즉시 읽음)
+    pub fn new(stage: &StageConfig) -> anyhow::Result<Self> {
+        if stage.model.as_os_str().is_empty() {
+            anyhow::bail!("분류 단계 '{}'에 모델 경로가 없습니다", stage.name);
+        }
+        let labels = match (&stage.labels_file, stage.labels.is_empty()) {
+            (Some(path), true) => std::fs::read_to_string(path)
+                .map_err(|e| {
+                    anyhow::anyhow!("라벨 파일을 읽을 수 없습니다 ({}): {}", path.display(), e)
+                })?
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect(),
+            _ => stage.labels.clone(),
+        };
+        Ok(Self {
+            cache: ModelCache::with_options(ModelSource::File(stage.model.clone()), stage.session)?,
+            labels,
+        })
+    }
+
+    /// 분류기 출력 인덱스의 이름 (라벨이 없으면 `class_<id>`)
+    pub fn label(&self, class_id: u32) -> String {
+        self.labels
+            .get(class_id as usize)
+            .cloned()
+            .unwrap_or_else(|| format!("class_{}", class_id))
+    }
+
+    /// 세션이 이미 로드되었는지 확인
+    pub fn is_loaded(&self) -> bool {
+        self.cache.is_loaded()
+    }
+
+    /// 모델을 미리 로드
+    pub fn preload_model(&mut self) -> anyhow::Result<()> {
+        self.cache.preload_model()
+    }
+
+    /// 로드된 분류 모델의 입출력 정보
+    pub fn info(&mut self) -> anyhow::Result<ModelInfo> {
+        self.cache.info()
+    }
+
+    /// 크롭 묶음을 분류하여 크롭마다 (출력 인덱스, 점수) 목록 반환 (점수 내림차순)
+    pub fn classify(
+        &mut self,
+        crops: &[RgbImage],
+        stage: &StageConfig,
+    ) -> anyhow::Result<Vec<Vec<(u32, f32)>>> {
+        let mut results = Vec::with_capacity(crops.len());
+        for chunk in crops.chunks(stage.batch_size.max(1)) {
+            let session = self.cache.get_session()?;
+            let input_array = preprocess_crops(chunk, stage).into_dyn();
+            let cow_array = CowArray::from(&input_array);
+            let input_value = Value::from_array(session.allocator(), &cow_array)?;
+            let outputs = session.run(vec![input_value])?;
+
+            let output = outputs
+                .first()
+                .ok_or_else(|| anyhow::anyhow!("분류 모델에 출력이 없습니다"))?
+                .try_extract::<f32>()?;
+            let view = output.view();
+            let shape = view.shape().to_vec();
+            if shape.first() != Some(&chunk.len()) {
+                anyhow::bail!(
+                    "분류 모델 배치 크기가 맞지 않습니다 (입력 {}, 출력 {:?}), batch_size를 1로 설정해 보세요",
+                    chunk.len(),
+                    shape
+                );
+            }
+            // [N, 클래스 수, 1, 1] 같은 출력도 [N, 클래스 수]로 펼침
+            let classes = view.len() / chunk.len();
+            let scores = view.into_shape((chunk.len(), classes)).map_err(|_| {
+                anyhow::anyhow!("분류 모델 출력 형태가 올바르지 않습니다: {:?}", shape)
+            })?;
+
+            for row in scores.rows() {
+                let mut values = row.to_vec();
+                if stage.softmax {
+                    softmax_in_place(&mut values);
+                }
+                let mut ranked: Vec<(u32, f32)> = values
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, score)| (i as u32, score))
+                    .collect();
+                ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+                results.push(ranked);
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// 크롭을 정사각형으로 리사이즈하고 채널별 정규화하여 [N, 3, H, W] 배치 생성
+pub fn preprocess_crops(crops: &[RgbImage], stage: &StageConfig) -> Array4<f32> {
+    let size = stage.input_size.max(1);
+    let mut batch = Array4::<f32>::zeros((crops.len(), 3, size as usize, size as usize));
+    for (n, crop) in crops.iter().enumerate() {
+        let resized =
+            image::imageops::resize(crop, size, size, image::imageops::FilterType::Triangle);
+        for (x, y, pixel) in resized.enumerate_pixels() {
+            for (c, &channel) in pixel.0.iter().enumerate() {
+                let value = channel as f32 / 255.0;
+                batch[[n, c, y as usize, x as usize]] = (value - stage.mean[c]) / stage.std[c];
+            }
+        }
+    }
+    batch
+}
+
+/// 수치적으로 안정한 소프트맥스
+fn softmax_in_place(values: &mut [f32]) {
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let mut sum = 0.0;
+    for v in values.iter_mut() {
+        *v = (*v - max).exp();
+        sum += *v;
+    }
+    if sum > 0.0 {
+        for v in values.iter_mut() {
+            *v /= sum;
+        }
+    }
+}
+
+/// 분류 단계 묶음 (단계 설정과 단계별 분류 모델 캐시)
+///
+/// 검출을 이미 다른 곳에서 수행하는 경우(GUI 워커 등) 분류 단계만 따로 실행할 때 사용합니다.
+pub struct ClassifierStages {
+    stages: Vec<StageConfig>,
+    classifiers: Vec<ClassifierCache>,
+}
+
+impl ClassifierStages {
+    /// 단계별 분류 모델 캐시 생성 (세션은 첫 분류 시 로드)
+    pub fn new(stages: Vec<StageConfig>) -> anyhow::Result<Self> {
+        let classifiers = stages
+            .iter()
+            .map(ClassifierCache::new)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self {
+            stages,
+            classifiers,
+        })
+    }
+
+    pub fn stages(&self) -> &[StageConfig] {
+        &self.stages
+    }
+
+    /// 모든 분류 모델을 미리 로드
+    pub fn preload_models(&mut self) -> anyhow::Result<()> {
+        for classifier in &mut self.classifiers {
+            classifier.preload_model()?;
+        }
+        Ok(())
+    }
+
+    /// 검출 결과에 보조 라벨 부착 (단계별 대상 검출을 모아 배치 분류)
+    pub fn classify(
+        &mut self,
+        image: &RgbImage,
+        detections: &mut [Detection],
+    ) -> anyhow::Result<()> {
+        for (stage, classifier) in self.stages.iter().zip(&mut self.classifiers) {
+            let (indices, crops): (Vec<usize>, Vec<RgbImage>) = detections
+                .iter()
+                .enumerate()
+                .filter(|(_, d)| stage.applies_to(d))
+                .filter_map(|(i, d)| d.crop(image, stage.padding).map(|crop| (i, crop)))
+                .unzip();
+            if crops.is_empty() {
+                continue;
+            }
+
+            let results = classifier.classify(&crops, stage)?;
+            for (index, ranked) in indices.into_iter().zip(results) {
+                let detection = &mut detections[index];
+                detection.secondary.retain(|s| s.stage != stage.name);
+                detection.secondary.extend(
+                    ranked
+                        .into_iter()
+                        .filter(|(_, score)| *score >= stage.min_score)
+                        .take(stage.top_k)
+                        .map(|(class_id, score)| SecondaryLabel {
+                            stage: stage.name.clone(),
+                            class_id,
+                            label: classifier.label(class_id),
+                            score,
+                        }),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 파이프라인 실행 결과
+#[derive(Debug, Clone)]
+pub struct PipelineResult {
+    pub detections: Vec<Detection>,
+    pub detector_ms: f64,
+    pub classifier_ms: f64,
+}
+
+/// 검출기 → 크롭 → 분류기 2단계 파이프라인
+pub struct Pipeline {
+    config: PipelineConfig,
+    detector: ModelCache,
+    classifiers: ClassifierStages,
+}
+
+impl Pipeline {
+    /// 설정대로 검출/분류 모델 캐시 생성 (세션은 첫 실행 시 로드)
+    pub fn new(config: PipelineConfig) -> anyhow::Result<Self> {
+        let detector = ModelCache::with_options(config.detector.clone(), config.detector_session)?;
+        Self::with_detector(detector, config)
+    }
+
+    /// 이미 만들어 둔 검출기 캐시를 사용 (설정의 `detector`는 무시)
+    pub fn with_detector(detector: ModelCache, config: PipelineConfig) -> anyhow::Result<Self> {
+        let classifiers = ClassifierStages::new(config.stages.clone())?;
+        Ok(Self {
+            config,
+            detector,
+            classifiers,
+        })
+    }
+
+    pub fn config(&self) -> &PipelineConfig {
+        &self.config
+    }
+
+    /// 모든 모델을 미리 로드
+    pub fn preload_models(&mut self) -> anyhow::Result<()> {
+        self.detector.preload_model()?;
+        self.classifiers.preload_models()
+    }
+
+    /// 검출 후 클래스별 임계값을 적용하고 보조 라벨을 붙여 반환
+    pub fn run(&mut self, image: &RgbImage) -> anyhow::Result<PipelineResult> {
+        let (mut detections, detector_ms) = run_inference_with_threshold(
+            image,
+            &mut self.detector,
+            self.config.input_size,
+            self.config.min_threshold(),
+        )?;
+        detections.retain(|d| d.confidence >= self.config.threshold_for(&d.class_name));
+
+        let start_time = std::time::Instant::now();
+        self.classify(image, &mut detections)?;
+        let classifier_ms = start_time.elapsed().as_secs_f64() * 1000.0;
+
+        Ok(PipelineResult {
+            detections,
+            detector_ms,
+            classifier_ms,
+        })
+    }
+
+    /// 기존 검출 결과에 분류 단계만 실행 (단계별 대상 검출을 모아 배치 분류)
+    pub fn classify(
+        &mut self,
+        image: &RgbImage,
+        detections: &mut [Detection],
+    ) -> anyhow::Result<()> {
+        self.classifiers.classify(image, detections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    const EPSILON: f32 = 1e-5;
+
+    #[test]
+    fn softmax_sums_to_one_and_keeps_order() {
+        let mut values = [1.0, 2.0, 3.0, -1.0];
+        softmax_in_place(&mut values);
+        assert!((values.iter().sum::<f32>() - 1.0).abs() < EPSILON);
+        assert!(values.iter().all(|v| *v > 0.0));
+        assert!(values[2] > values[1] && values[1] > values[0] && values[0] > values[3]);
+    }
+
+    #[test]
+    fn softmax_is_stable_for_large_logits() {
+        let mut large = [1000.0, 1001.0, 1002.0];
+        let mut small = [0.0, 1.0, 2.0];
+        softmax_in_place(&mut large);
+        softmax_in_place(&mut small);
+        assert!(large.iter().all(|v| v.is_finite()));
+        for (l, s) in large.iter().zip(small) {
+            assert!((l - s).abs() < EPSILON);
+        }
+
+        let mut negative = [-1000.0, -1000.0];
+        softmax_in_place(&mut negative);
+        assert_eq!(negative, [0.5, 0.5]);
+    }
+
+    #[test]
+    fn preprocess_resizes_every_crop_to_a_square_batch() {
+        let stage = StageConfig {
+            input_size: 8,
+            ..Default::default()
+        };
+        let crops = [
+            RgbImage::from_pixel(20, 10, Rgb([0, 0, 0])),
+            RgbImage::from_pixel(3, 17, Rgb([0, 0, 0])),
+        ];
+        let batch = preprocess_crops(&crops, &stage);
+        assert_eq!(batch.shape(), &[2, 3, 8, 8]);
+
+        assert_eq!(preprocess_crops(&[], &stage).shape(), &[0, 3, 8, 8]);
+    }
+
+    #[test]
+    fn preprocess_normalizes_each_channel() {
+        let stage = StageConfig {
+            input_size: 4,
+            ..Default::default()
+        };
+        let crop = RgbImage::from_pixel(6, 6, Rgb([255, 0, 255]));
+        let batch = preprocess_crops(&[crop], &stage);
+
+        let expected = [
+            (1.0 - IMAGENET_MEAN[0]) / IMAGENET_STD[0],
+            (0.0 - IMAGENET_MEAN[1]) / IMAGENET_STD[1],
+            (1.0 - IMAGENET_MEAN[2]) / IMAGENET_STD[2],
+        ];
+        for (c, expected) in expected.iter().enumerate() {
+            for y in 0..4 {
+                for x in 0..4 {
+                    assert!((batch[[0, c, y, x]] - expected).abs() < EPSILON);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn preprocess_keeps_channel_and_pixel_layout() {
+        // 정규화 없이 [0, 1] 범위로만 변환
+        let stage = StageConfig {
+            input_size: 2,
+            mean: [0.0; 3],
+            std: [1.0; 3],
+            ..Default::default()
+        };
+        let mut crop = RgbImage::new(2, 2);
+        crop.put_pixel(1, 0, Rgb([255, 0, 0]));
+        crop.put_pixel(0, 1, Rgb([0, 0, 255]));
+        let batch = preprocess_crops(&[crop], &stage);
+
+        // 같은 크기는 리사이즈 후에도 픽셀이 그대로이고 (x, y)는 [.., y, x]에 저장
+        assert!((batch[[0, 0, 0, 1]] - 1.0).abs() < EPSILON);
+        assert!((batch[[0, 2, 1, 0]] - 1.0).abs() < EPSILON);
+        assert!(batch[[0, 0, 1, 0]].abs() < EPSILON);
+        assert!(batch[[0, 1, 0, 1]].abs() < EPSILON);
+    }
+}
@@ -0,0 +1,420 @@
+use crate::batch::{run_folder, BatchProgress, BatchReport, DEFAULT_EXTENSIONS};
+use crate::crops::crop_rect;
+use crate::{
+    decode_image_with_orientation, run_inference_with_threshold, ClassRemap, Detection, ModelCache,
+    OrientationMode, CONFIDENCE_THRESHOLD, MODEL_INPUT_SIZE,
+};
+use image::{Rgb, RgbImage};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// 상수 정의
+/// 기본 확장 여백 (박스 너비/높이 대비 한쪽 비율, 머리카락/번호판 테두리까지 가리도록)
+pub const DEFAULT_REDACTION_MARGIN: f32 = 0.1;
+pub const DEFAULT_BLUR_SIGMA: f32 = 12.0;
+pub const DEFAULT_BLOCK_SIZE: u32 = 16;
+/// 모자이크 블록은 영역 짧은 변의 이 분의 1 이상 (큰 얼굴도 짧은 변이 8블록 이하가 되도록)
+pub const PIXELATE_BLOCKS_PER_SIDE: u32 = 8;
+/// 블러 시그마는 영역 짧은 변의 이 분의 1 이상
+pub const BLUR_SIGMA_DIVISOR: f32 = 16.0;
+
+/// 가림 방식
+///
+/// 블러 시그마와 블록 크기는 최솟값입니다. 고정 픽셀 값으로는 큰 영역(가까이 찍힌 얼굴 등)을
+/// 충분히 가리지 못하므로 `scaled_to`로 영역 크기에 비례해 키운 값을 사용합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RedactionMode {
+    /// 가우시안 블러 (`sigma`는 픽셀 단위 표준편차의 최솟값)
+    Blur { sigma: f32 },
+    /// 모자이크 (`block_size` 픽셀 블록의 평균색, 블록 크기의 최솟값)
+    Pixelate { block_size: u32 },
+    /// 단색 채우기
+    Fill { color: [u8; 3] },
+}
+
+impl Default for RedactionMode {
+    fn default() -> Self {
+        RedactionMode::Blur {
+            sigma: DEFAULT_BLUR_SIGMA,
+        }
+    }
+}
+
+impl RedactionMode {
+    /// 이름으로 생성 (`blur`, `pixelate`, `fill`), `strength`는 블러 시그마 또는 블록 크기
+    pub fn from_name(name: &str, strength: Option<f32>) -> anyhow::Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "blur" => Ok(RedactionMode::Blur {
+                sigma: strength.unwrap_or(DEFAULT_BLUR_SIGMA),
+            }),
+            "pixelate" | "mosaic" => Ok(RedactionMode::Pixelate {
+                block_size: strength
+                    .map(|s| s.round() as u32)
+                    .unwrap_or(DEFAULT_BLOCK_SIZE),
+            }),
+            "fill" => Ok(RedactionMode::Fill { color: [0, 0, 0] }),
+            other => Err(anyhow::anyhow!(
+                "알 수 없는 가림 방식: {} (blur, pixelate, fill)",
+                other
+            )),
+        }
+    }
+
+    /// 영역 크기에 맞춘 가림 강도 (짧은 변에 비례한 값과 설정값 중 큰 값)
+    pub fn scaled_to(self, width: u32, height: u32) -> Self {
+        let short_side = width.min(height);
+        match self {
+            RedactionMode::Blur { sigma } => RedactionMode::Blur {
+                sigma: sigma.max(short_side as f32 / BLUR_SIGMA_DIVISOR),
+            },
+            RedactionMode::Pixelate { block_size } => RedactionMode::Pixelate {
+                block_size: block_size.max(short_side / PIXELATE_BLOCKS_PER_SIDE),
+            },
+            RedactionMode::Fill { .. } => self,
+        }
+    }
+}
+
+/// 가림 렌더링 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionConfig {
+    pub mode: RedactionMode,
+    /// 가릴 클래스 이름 (비어 있으면 모든 검출)
+    pub classes: Vec<String>,
+    /// 박스 너비/높이 대비 한쪽 확장 여백 비율
+    pub margin: f32,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            mode: RedactionMode::default(),
+            classes: Vec::new(),
+            margin: DEFAULT_REDACTION_MARGIN,
+        }
+    }
+}
+
+impl RedactionConfig {
+    /// 이 설정이 해당 검출을 가리는지 여부 (클래스 이름은 대소문자 무시)
+    pub fn applies_to(&self, detection: &Detection) -> bool {
+        self.classes.is_empty()
+            || self
+                .classes
+                .iter()
+                .any(|c| c.eq_ignore_ascii_case(&detection.class_name))
+    }
+}
+
+/// 이미지의 픽셀 영역 (x, y, 너비, 높이)을 지정한 방식으로 가림 (강도는 `RedactionMode::scaled_to` 적용)
+pub fn redact_region(
+    image: &mut RgbImage,
+    (x, y, width, height): (u32, u32, u32, u32),
+    mode: RedactionMode,
+) {
+    match mode.scaled_to(width, height) {
+        RedactionMode::Blur { sigma } => {
+            let region = image::imageops::crop_imm(image, x, y, width, height).to_image();
+            let blurred = image::imageops::blur(&region, sigma.max(0.1));
+            image::imageops::replace(image, &blurred, x as i64, y as i64);
+        }
+        RedactionMode::Pixelate { block_size } => {
+            let block = block_size.max(1);
+            for by in (y..y + height).step_by(block as usize) {
+                for bx in (x..x + width).step_by(block as usize) {
+                    let (bw, bh) = (block.min(x + width - bx), block.min(y + height - by));
+                    let mut sum = [0u64; 3];
+                    for py in by..by + bh {
+                        for px in bx..bx + bw {
+                            let pixel = image.get_pixel(px, py);
+                            for (total, &channel) in sum.iter_mut().zip(pixel.0.iter()) {
+                                *total += channel as u64;
+                            }
+                        }
+                    }
+                    let count = (bw * bh) as u64;
+                    let average = Rgb(sum.map(|s| (s / count) as u8));
+                    for py in by..by + bh {
+                        for px in bx..bx + bw {
+                            image.put_pixel(px, py, average);
+                        }
+                    }
+                }
+            }
+        }
+        RedactionMode::Fill { color } => {
+            for py in y..y + height {
+                for px in x..x + width {
+                    image.put_pixel(px, py, Rgb(color));
+                }
+            }
+        }
+    }
+}
+
+/// 박스를 그리는 대신 대상 클래스의 박스 영역(여백 포함)을 가림 (가린 영역 수 반환)
+pub fn redact_detections(
+    image: &mut RgbImage,
+    detections: &[Detection],
+    config: &RedactionConfig,
+) -> usize {
+    let (width, height) = image.dimensions();
+    let mut redacted = 0;
+    for detection in detections.iter().filter(|d| config.applies_to(d)) {
+        if let Some(rect) = crop_rect(&detection.bbox, width, height, config.margin) {
+            redact_region(image, rect, config.mode);
+            redacted += 1;
+        }
+    }
+    redacted
+}
+
+/// 폴더 가림 처리 설정
+#[derive(Debug, Clone)]
+pub struct RedactBatchConfig {
+    pub input_dir: PathBuf,
+    /// 입력과 같은 폴더 구조로 가린 이미지를 저장할 폴더
+    pub output_dir: PathBuf,
+    /// 처리할 확장자 (소문자, 점 제외)
+    pub extensions: Vec<String>,
+    pub confidence_threshold: f32,
+    /// 가리기 전에 적용할 클래스 재매핑 (`RedactionConfig::classes`는 대상 클래스 이름 기준)
+    pub remap: Option<ClassRemap>,
+    /// EXIF 방향 처리 방식 (기본: 적용)
+    pub orientation: OrientationMode,
+    pub redaction: RedactionConfig,
+}
+
+impl RedactBatchConfig {
+    pub fn new(input_dir: impl Into<PathBuf>, output_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            input_dir: input_dir.into(),
+            output_dir: output_dir.into(),
+            extensions: DEFAULT_EXTENSIONS.iter().map(|e| e.to_string()).collect(),
+            confidence_threshold: CONFIDENCE_THRESHOLD,
+            remap: None,
+            orientation: OrientationMode::Apply,
+            redaction: RedactionConfig::default(),
+        }
+    }
+}
+
+/// 이미지 파일 하나를 검출 후 가려서 저장 (가린 영역 수 반환)
+///
+/// `remap`이 있으면 가릴 클래스를 고르기 전에 적용합니다.
+pub fn redact_image_file(
+    input: &Path,
+    output: &Path,
+    cache: &mut ModelCache,
+    confidence_threshold: f32,
+    remap: Option<&ClassRemap>,
+    orientation: OrientationMode,
+    config: &RedactionConfig,
+) -> anyhow::Result<usize> {
+    let mut image = decode_image_with_orientation(&std::fs::read(input)?, orientation)?;
+    let (mut detections, _) =
+        run_inference_with_threshold(&image, cache, MODEL_INPUT_SIZE, confidence_threshold)?;
+    if let Some(remap) = remap {
+        remap.apply(&mut detections);
+    }
+    let redacted = redact_detections(&mut image, &detections, config);
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    image.save(output)?;
+    Ok(redacted)
+}
+
+/// 폴더의 모든 이미지를 가려서 같은 구조로 저장 (공개용 익명화, 가린 영역 수 함께 반환)
+pub fn redact_folder(
+    config: &RedactBatchConfig,
+    on_progress: impl FnMut(&BatchProgress),
+) -> anyhow::Result<(BatchReport, usize)> {
+    run_folder(
+        &config.input_dir,
+        &config.output_dir,
+        &config.extensions,
+        on_progress,
+        |path, cache| {
+            let relative = path.strip_prefix(&config.input_dir).unwrap_or(path);
+            redact_image_file(
+                path,
+                &config.output_dir.join(relative),
+                cache,
+                config.confidence_threshold,
+                config.remap.as_ref(),
+                config.orientation,
+                &config.redaction,
+            )
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: u32 = 64;
+    const FILL: [u8; 3] = [255, 0, 255];
+
+    fn detection(class_name: &str, bbox: [f32; 4]) -> Detection {
+        Detection {
+            bbox,
+            confidence: 0.9,
+            class_id: 1,
+            class_name: class_name.to_string(),
+            mask: None,
+            keypoints: None,
+            obb: None,
+            secondary: Vec::new(),
+            original_class_id: None,
+        }
+    }
+
+    /// 픽셀마다 색이 다른 그라데이션 (가림 전후 비교용)
+    fn gradient() -> RgbImage {
+        RgbImage::from_fn(SIZE, SIZE, |x, y| Rgb([(x * 4) as u8, (y * 4) as u8, 128]))
+    }
+
+    fn fill_config(margin: f32) -> RedactionConfig {
+        RedactionConfig {
+            mode: RedactionMode::Fill { color: FILL },
+            classes: Vec::new(),
+            margin,
+        }
+    }
+
+    /// 영역 밖 픽셀이 원본과 같은지 확인
+    fn assert_outside_unchanged(
+        before: &RgbImage,
+        after: &RgbImage,
+        (x, y, w, h): (u32, u32, u32, u32),
+    ) {
+        for (px, py, pixel) in after.enumerate_pixels() {
+            let inside = px >= x && px < x + w && py >= y && py < y + h;
+            if !inside {
+                assert_eq!(pixel, before.get_pixel(px, py), "({}, {}) changed", px, py);
+            }
+        }
+    }
+
+    #[test]
+    fn fill_clips_boxes_at_the_image_edge() {
+        let before = gradient();
+        let mut image = before.clone();
+        let redacted = redact_detections(
+            &mut image,
+            &[detection("person", [-0.5, -0.5, 0.25, 0.25])],
+            &fill_config(0.0),
+        );
+        assert_eq!(redacted, 1);
+        for py in 0..16 {
+            for px in 0..16 {
+                assert_eq!(image.get_pixel(px, py).0, FILL);
+            }
+        }
+        assert_outside_unchanged(&before, &image, (0, 0, 16, 16));
+    }
+
+    #[test]
+    fn pixelate_clips_partial_blocks_at_the_image_edge() {
+        let before = gradient();
+        let mut image = before.clone();
+        let config = RedactionConfig {
+            mode: RedactionMode::Pixelate { block_size: 5 },
+            classes: Vec::new(),
+            margin: 0.0,
+        };
+        // 오른쪽 아래로 벗어난 박스 → (40, 40, 24, 24), 마지막 블록은 4픽셀
+        let redacted = redact_detections(
+            &mut image,
+            &[detection("person", [0.625, 0.625, 1.5, 1.5])],
+            &config,
+        );
+        assert_eq!(redacted, 1);
+        assert_outside_unchanged(&before, &image, (40, 40, 24, 24));
+        for (bx, bw) in [(40, 5), (45, 5), (50, 5), (55, 5), (60, 4)] {
+            let first = *image.get_pixel(bx, 60);
+            for px in bx..bx + bw {
+                for py in 60..64 {
+                    assert_eq!(*image.get_pixel(px, py), first);
+                }
+            }
+        }
+        assert_ne!(image.get_pixel(40, 40), before.get_pixel(40, 40));
+    }
+
+    #[test]
+    fn margin_expands_the_redacted_area() {
+        let before = gradient();
+        let mut image = before.clone();
+        // 16x16 박스에 50% 여백 → 양쪽 8픽셀씩 넓어진 (8, 8, 32, 32)
+        redact_detections(
+            &mut image,
+            &[detection("person", [0.25, 0.25, 0.5, 0.5])],
+            &fill_config(0.5),
+        );
+        assert_eq!(image.get_pixel(8, 8).0, FILL);
+        assert_eq!(image.get_pixel(39, 39).0, FILL);
+        assert_outside_unchanged(&before, &image, (8, 8, 32, 32));
+    }
+
+    #[test]
+    fn class_filter_is_case_insensitive() {
+        let mut config = fill_config(0.0);
+        config.classes = vec!["Person".to_string()];
+        assert!(config.applies_to(&detection("person", [0.0; 4])));
+        assert!(config.applies_to(&detection("PERSON", [0.0; 4])));
+        assert!(!config.applies_to(&detection("car", [0.0; 4])));
+        assert!(fill_config(0.0).applies_to(&detection("car", [0.0; 4])));
+
+        let before = gradient();
+        let mut image = before.clone();
+        let detections = [
+            detection("person", [0.0, 0.0, 0.25, 0.25]),
+            detection("car", [0.5, 0.5, 0.75, 0.75]),
+        ];
+        assert_eq!(redact_detections(&mut image, &detections, &config), 1);
+        assert_eq!(image.get_pixel(0, 0).0, FILL);
+        assert_outside_unchanged(&before, &image, (0, 0, 16, 16));
+    }
+
+    #[test]
+    fn strength_grows_with_region_size() {
+        let pixelate = RedactionMode::Pixelate {
+            block_size: DEFAULT_BLOCK_SIZE,
+        };
+        assert_eq!(pixelate.scaled_to(40, 40), pixelate);
+        assert_eq!(
+            pixelate.scaled_to(600, 800),
+            RedactionMode::Pixelate { block_size: 75 }
+        );
+
+        let blur = RedactionMode::Blur {
+            sigma: DEFAULT_BLUR_SIGMA,
+        };
+        assert_eq!(blur.scaled_to(100, 100), blur);
+        assert_eq!(
+            blur.scaled_to(640, 960),
+            RedactionMode::Blur { sigma: 40.0 }
+        );
+
+        let fill = RedactionMode::Fill { color: FILL };
+        assert_eq!(fill.scaled_to(600, 600), fill);
+    }
+
+    #[test]
+    fn mode_from_name() {
+        assert_eq!(
+            RedactionMode::from_name("Pixelate", Some(7.6)).unwrap(),
+            RedactionMode::Pixelate { block_size: 8 }
+        );
+        assert_eq!(
+            RedactionMode::from_name("blur", None).unwrap(),
+            RedactionMode::default()
+        );
+        assert!(RedactionMode::from_name("smudge", None).is_err());
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{class_label_map, rf_detr_id_to_label, Detection};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// 원본 클래스 지정 (클래스 ID 또는 이름)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ClassSelector {
+    Id(u32),
+    /// 기본 COCO 이름 또는 라벨 맵 이름 (대소문자 무시)
+    Name(String),
+}
+
+impl ClassSelector {
+    fn matches(&self, class_id: u32, class_name: &str) -> bool {
+        match self {
+            ClassSelector::Id(id) => *id == class_id,
+            ClassSelector::Name(name) => {
+                name.eq_ignore_ascii_case(class_name)
+                    || rf_detr_id_to_label(class_id).is_some_and(|n| name.eq_ignore_ascii_case(n))
+            }
+        }
+    }
+}
+
+/// 재매핑 대상 클래스 (여러 원본 클래스를 하나로 합침)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemapTarget {
+    pub name: String,
+    /// 대상 클래스 ID (생략하면 목록 순서대로 1부터)
+    #[serde(default)]
+    pub id: Option<u32>,
+    pub sources: Vec<ClassSelector>,
+}
+
+/// 앱 분류 체계로의 클래스 재매핑 (`parse_rf_detr_outputs` 및 라벨 맵 적용 이후)
+///
+/// 재매핑된 검출은 `original_class_id`에 원래 클래스 ID를 보존합니다.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClassRemap {
+    pub classes: Vec<RemapTarget>,
+    /// 어떤 대상에도 속하지 않는 클래스를 남길지 여부 (기본값: 제외)
+    ///
+    /// 남긴 클래스는 대상 ID와 겹치지 않도록 `unmapped_id`로 옮겨지며 이름은 그대로입니다.
+    #[serde(default)]
+    pub keep_unmapped: bool,
+}
+
+impl ClassRemap {
+    /// JSON 파싱 (대상 ID와 이름 중복 검사)
+    pub fn from_json_str(json: &str) -> anyhow::Result<Self> {
+        let remap: Self = serde_json::from_str(json)?;
+        let mut ids = BTreeSet::new();
+        let mut names = BTreeSet::new();
+        for (id, name) in remap.target_categories() {
+            if !ids.insert(id) {
+                anyhow::bail!("대상 클래스 ID가 중복되었습니다: {}", id);
+            }
+            if !names.insert(name.clone()) {
+                anyhow::bail!("대상 클래스 이름이 중복되었습니다: {}", name);
+            }
+        }
+        Ok(remap)
+    }
+
+    /// JSON 파일에서 로드
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::from_json_str(&std::fs::read_to_string(path)?)
+    }
+
+    /// 내보내기/편집용 클래스 목록 (ID, 이름)
+    ///
+    /// 대상 클래스(설정 순서) 뒤에, `keep_unmapped`이면 어떤 대상에도 속하지 않는
+    /// RF-DETR 클래스를 `unmapped_id` 순서로 덧붙입니다.
+    pub fn categories(&self) -> Vec<(u32, String)> {
+        let mut categories = self.target_categories();
+        if self.keep_unmapped {
+            categories.extend(
+                class_label_map()
+                    .into_iter()
+                    .filter(|(id, name)| self.resolve(*id, name).is_none())
+                    .map(|(id, name)| (self.unmapped_id(id), name.to_string())),
+            );
+        }
+        categories
+    }
+
+    /// 대상 클래스 목록 (ID, 이름), 설정 순서
+    pub fn target_categories(&self) -> Vec<(u32, String)> {
+        self.classes
+            .iter()
+            .enumerate()
+            .map(|(i, target)| (target.id.unwrap_or(i as u32 + 1), target.name.clone()))
+            .collect()
+    }
+
+    /// 남겨 둔 미매핑 클래스의 ID (가장 큰 대상 ID 뒤로 원본 ID만큼 옮김)
+    pub fn unmapped_id(&self, class_id: u32) -> u32 {
+        let max_target_id = self
+            .target_categories()
+            .iter()
+            .map(|(id, _)| *id)
+            .max()
+            .unwrap_or(0);
+        max_target_id + class_id
+    }
+
+    /// `categories`의 클래스 ID에 해당하는 대표 원본 RF-DETR 클래스 ID
+    ///
+    /// 편집에서 직접 고른 클래스의 `original_class_id`로 사용하여, 다시 적용해도 같은 클래스가 되게 합니다.
+    /// 어떤 RF-DETR 클래스도 해당하지 않으면 None입니다.
+    pub fn source_id(&self, class_id: u32) -> Option<u32> {
+        class_label_map().into_iter().find_map(|(id, name)| {
+            let target = match self.resolve(id, name) {
+                Some((target, _)) => target,
+                None if self.keep_unmapped => self.unmapped_id(id),
+                None => return None,
+            };
+            (target == class_id).then_some(id)
+        })
+    }
+
+    /// 원본 클래스에 해당하는 대상 (ID, 이름), 첫 번째로 일치하는 대상 사용
+    pub fn resolve(&self, class_id: u32, class_name: &str) -> Option<(u32, &str)> {
+        self.classes.iter().enumerate().find_map(|(i, target)| {
+            target
+                .sources
+                .iter()
+                .any(|s| s.matches(class_id, class_name))
+                .then(|| (target.id.unwrap_or(i as u32 + 1), target.name.as_str()))
+        })
+    }
+
+    /// 검출 결과의 클래스를 대상 클래스로 바꾸고 해당 없는 검출은 제외 (`keep_unmapped`이면 ID만 옮김)
+    ///
+    /// 이미 재매핑된 검출은 `original_class_id` 기준으로 다시 판정하므로 여러 번 적용해도 같습니다.
+    pub fn apply(&self, detections: &mut Vec<Detection>) {
+        detections.retain_mut(|d| {
+            let source_id = d.original_class_id.unwrap_or(d.class_id);
+            match self.resolve(source_id, &d.class_name) {
+                Some((id, name)) => {
+                    d.original_class_id = Some(source_id);
+                    d.class_id = id;
+                    d.class_name = name.to_string();
+                    true
+                }
+                None if self.keep_unmapped => {
+                    d.original_class_id = Some(source_id);
+                    d.class_id = self.unmapped_id(source_id);
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detection(class_id: u32) -> Detection {
+        Detection {
+            bbox: [0.1, 0.1, 0.5, 0.5],
+            confidence: 0.9,
+            class_id,
+            class_name: rf_detr_id_to_label(class_id)
+                .unwrap_or("unknown")
+                .to_string(),
+            mask: None,
+            keypoints: None,
+            obb: None,
+            secondary: Vec::new(),
+            original_class_id: None,
+        }
+    }
+
+    fn vehicle_remap(keep_unmapped: bool) -> ClassRemap {
+        ClassRemap::from_json_str(&format!(
+            r#"{{"classes": [
+                {{"name": "vehicle", "sources": ["car", "truck", 6]}},
+                {{"name": "animal", "sources": ["dog", "cat"]}}
+            ], "keep_unmapped": {}}}"#,
+            keep_unmapped
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn maps_sources_and_drops_unmapped_by_default() {
+        let remap = vehicle_remap(false);
+        // 1: person, 3: car, 6: bus, 18: dog
+        let mut detections = vec![detection(1), detection(3), detection(6), detection(18)];
+        remap.apply(&mut detections);
+        let classes: Vec<(u32, &str, Option<u32>)> = detections
+            .iter()
+            .map(|d| (d.class_id, d.class_name.as_str(), d.original_class_id))
+            .collect();
+        assert_eq!(
+            classes,
+            vec![
+                (1, "vehicle", Some(3)),
+                (1, "vehicle", Some(6)),
+                (2, "animal", Some(18))
+            ]
+        );
+    }
+
+    #[test]
+    fn kept_unmapped_ids_do_not_collide_with_targets() {
+        let remap = vehicle_remap(true);
+        // person(1)과 bicycle(2)은 대상 ID 1, 2와 겹치면 안 됨
+        let mut detections = vec![detection(1), detection(2), detection(3)];
+        remap.apply(&mut detections);
+        assert_eq!(detections[0].class_id, remap.unmapped_id(1));
+        assert_eq!(detections[0].class_name, "person");
+        assert_eq!(detections[0].original_class_id, Some(1));
+        assert_eq!(detections[1].class_id, 4);
+        assert_eq!(detections[2].class_id, 1);
+
+        // 다시 적용해도 같음
+        let before = detections.clone();
+        remap.apply(&mut detections);
+        assert_eq!(detections, before);
+    }
+
+    #[test]
+    fn categories_cover_kept_unmapped_classes() {
+        let remap = vehicle_remap(true);
+        let categories = remap.categories();
+        assert_eq!(&categories[..2], &remap.target_categories()[..]);
+        assert!(categories.contains(&(remap.unmapped_id(1), "person".to_string())));
+        // 대상에 합쳐진 클래스는 따로 나오지 않음
+        assert!(!categories
+            .iter()
+            .any(|(_, name)| name == "car" || name == "dog"));
+        let mut ids: Vec<u32> = categories.iter().map(|(id, _)| *id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), categories.len());
+
+        assert_eq!(vehicle_remap(false).categories(), remap.target_categories());
+    }
+
+    #[test]
+    fn source_id_reapplies_to_the_same_class() {
+        let remap = vehicle_remap(true);
+        for (class_id, name) in remap.categories() {
+            let source = remap.source_id(class_id).unwrap();
+            let mut detections = vec![detection(source)];
+            remap.apply(&mut detections);
+            assert_eq!(
+                (detections[0].class_id, detections[0].class_name.as_str()),
+                (class_id, name.as_str())
+            );
+        }
+        assert_eq!(
+            vehicle_remap(false).source_id(vehicle_remap(true).unmapped_id(1)),
+            None
+        );
+    }
+}
@@ -0,0 +1,357 @@
+use crate::{
+    iou, keypoints, run_inference_with_threshold, Detection, Keypoint, Mask, ModelCache,
+    OrientedBox, Skeleton, CONFIDENCE_THRESHOLD, MODEL_INPUT_SIZE,
+};
+use image::RgbImage;
+
+/// 테스트 타임 증강(TTA) 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct TtaConfig {
+    /// 좌우 반전 패스 추가 여부
+    pub horizontal_flip: bool,
+    /// 추가로 실행할 입력 크기 목록 (비어 있으면 기본 입력 크기만 사용)
+    /// 모델이 동적 입력 크기를 지원해야 하며, RF-DETR 패치 크기(56)의 배수 권장
+    pub scales: Vec<u32>,
+    /// WBF 클러스터링 IoU 임계값
+    pub fusion_iou_threshold: f32,
+    /// 융합 전 제외할 최소 신뢰도
+    pub skip_box_threshold: f32,
+    /// 패스별 검출 및 융합 결과에 적용할 신뢰도 임계값
+    pub confidence_threshold: f32,
+    /// 반전 패스의 좌/우 키포인트 교환(`flip_pairs`)과 결과 이미지 그리기에 쓸 스켈레톤
+    /// (None이거나 키포인트 수가 다르면 `Skeleton::for_count`)
+    pub skeleton: Option<Skeleton>,
+}
+
+impl Default for TtaConfig {
+    fn default() -> Self {
+        Self {
+            horizontal_flip: false,
+            scales: Vec::new(),
+            fusion_iou_threshold: 0.55,
+            skip_box_threshold: 0.0,
+            confidence_threshold: CONFIDENCE_THRESHOLD,
+            skeleton: None,
+        }
+    }
+}
+
+impl TtaConfig {
+    /// 좌우 반전 + 멀티 스케일(448, 560, 672) 조합
+    pub fn flip_and_multi_scale() -> Self {
+        Self {
+            horizontal_flip: true,
+            scales: vec![448, MODEL_INPUT_SIZE, 672],
+            ..Default::default()
+        }
+    }
+
+    /// 단일 패스 이외의 증강이 설정되어 있는지 확인
+    pub fn is_enabled(&self) -> bool {
+        self.horizontal_flip || self.scales.iter().any(|&s| s != MODEL_INPUT_SIZE)
+    }
+
+    /// 실행할 입력 크기 목록 (중복 제거)
+    fn input_sizes(&self) -> Vec<u32> {
+        let mut sizes = if self.scales.is_empty() {
+            vec![MODEL_INPUT_SIZE]
+        } else {
+            self.scales.clone()
+        };
+        sizes.sort_unstable();
+        sizes.dedup();
+        sizes
+    }
+}
+
+/// 좌우 반전된 이미지 기준 박스를 원본 기준으로 되돌림
+fn unflip_bbox(bbox: [f32; 4]) -> [f32; 4] {
+    [1.0 - bbox[2], bbox[1], 1.0 - bbox[0], bbox[3]]
+}
+
+/// 모든 TTA 패스를 실행하고 WBF로 결과를 융합 (검출 결과, 총 추론 시간 ms)
+pub fn run_tta(
+    img: &RgbImage,
+    cache: &mut ModelCache,
+    config: &TtaConfig,
+) -> anyhow::Result<(Vec<Detection>, f64)> {
+    let flipped = config
+        .horizontal_flip
+        .then(|| image::imageops::flip_horizontal(img));
+
+    let mut passes = Vec::new();
+    let mut total_time_ms = 0.0;
+
+    for input_size in config.input_sizes() {
+        let (detections, time_ms) =
+            run_inference_with_threshold(img, cache, input_size, config.confidence_threshold)?;
+        passes.push(detections);
+        total_time_ms += time_ms;
+
+        if let Some(flipped) = &flipped {
+            let (detections, time_ms) = run_inference_with_threshold(
+                flipped,
+                cache,
+                input_size,
+                config.confidence_threshold,
+            )?;
+            // 역변환: 반전된 좌표를 원본 좌표로 복원
+            passes.push(
+                detections
+                    .into_iter()
+                    .map(|d| Detection {
+                        bbox: unflip_bbox(d.bbox),
+                        mask: d.mask.as_ref().map(|m| m.flipped_horizontal()),
+                        keypoints: d.keypoints.as_deref().map(|k| {
+                            let skeleton = Skeleton::resolve(config.skeleton.as_ref(), k.len());
+                            keypoints::unflip_keypoints(k, &skeleton)
+                        }),
+                        obb: d.obb.map(|o| o.flipped_horizontal(img.width())),
+                        ..d
+                    })
+                    .collect(),
+            );
+            total_time_ms += time_ms;
+        }
+    }
+
+    let fused = weighted_box_fusion(
+        &passes,
+        config.fusion_iou_threshold,
+        config.skip_box_threshold,
+        config.confidence_threshold,
+    );
+    Ok((fused, total_time_ms))
+}
+
+/// 여러 패스의 검출 결과를 Weighted Box Fusion으로 병합
+///
+/// 같은 클래스끼리 IoU 기준으로 클러스터링한 뒤, 신뢰도 가중 평균 좌표를 사용합니다.
+/// 키포인트도 점별 신뢰도(가시성 반영) 가중 평균으로 융합합니다.
+/// 융합된 신뢰도는 일부 패스에서만 검출된 박스가 불리하도록 패스 수로 보정되며,
+/// 보정 후 `confidence_threshold` 이하인 박스는 제외됩니다.
+pub fn weighted_box_fusion(
+    passes: &[Vec<Detection>],
+    iou_threshold: f32,
+    skip_box_threshold: f32,
+    confidence_threshold: f32,
+) -> Vec<Detection> {
+    let num_passes = passes.len().max(1);
+
+    let mut boxes: Vec<&Detection> = passes
+        .iter()
+        .flatten()
+        .filter(|d| d.confidence >= skip_box_threshold)
+        .collect();
+    boxes.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+
+    // 클러스터별 구성원과 현재 융합 박스
+    let mut clusters: Vec<(Vec<&Detection>, Detection)> = Vec::new();
+
+    for det in boxes {
+        let matched = clusters.iter_mut().find(|(_, fused)| {
+            fused.class_id == det.class_id && iou(&fused.bbox, &det.bbox) > iou_threshold
+        });
+
+        match matched {
+            Some((members, fused)) => {
+                members.push(det);
+                *fused = fuse_cluster(members);
+            }
+            None => clusters.push((vec![det], det.clone())),
+        }
+    }
+
+    clusters
+        .into_iter()
+        .map(|(members, mut fused)| {
+            let weight = members.len().min(num_passes) as f32 / num_passes as f32;
+            fused.confidence *= weight;
+            fused
+        })
+        .filter(|fused| fused.confidence > confidence_threshold)
+        .collect()
+}
+
+/// 클러스터 구성원의 신뢰도 가중 평균 박스 계산
+///
+/// 마스크는 구성원 마스크의 가중 다수결로, 회전 박스와 키포인트는 가중 평균으로 다시 만들며,
+/// 마스크/회전 박스/키포인트가 없는 구성원이 있으면 해당 항목은 제외합니다.
+fn fuse_cluster(members: &[&Detection]) -> Detection {
+    let total_conf: f32 = members.iter().map(|d| d.confidence).sum();
+    let mut bbox = [0.0f32; 4];
+    for det in members {
+        for (acc, v) in bbox.iter_mut().zip(det.bbox.iter()) {
+            *acc += v * det.confidence;
+        }
+    }
+    for v in bbox.iter_mut() {
+        *v /= total_conf;
+    }
+
+    let masks: Option<Vec<(&Mask, f32)>> = members
+        .iter()
+        .map(|d| d.mask.as_ref().map(|m| (m, d.confidence)))
+        .collect();
+    let obbs: Option<Vec<(&OrientedBox, f32)>> = members
+        .iter()
+        .map(|d| d.obb.as_ref().map(|o| (o, d.confidence)))
+        .collect();
+    let keypoints: Option<Vec<(&[Keypoint], f32)>> = members
+        .iter()
+        .map(|d| d.keypoints.as_deref().map(|k| (k, d.confidence)))
+        .collect();
+
+    Detection {
+        bbox,
+        confidence: total_conf / members.len() as f32,
+        mask: masks.and_then(|masks| Mask::weighted_vote(&masks)),
+        obb: obbs.and_then(|obbs| OrientedBox::weighted_average(&obbs)),
+        keypoints: keypoints.and_then(|keypoints| Keypoint::weighted_average(&keypoints)),
+        ..members[0].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detection(bbox: [f32; 4], confidence: f32, class_id: u32) -> Detection {
+        Detection {
+            bbox,
+            confidence,
+            class_id,
+            class_name: format!("class {}", class_id),
+            mask: None,
+            keypoints: None,
+            obb: None,
+            secondary: Vec::new(),
+            original_class_id: None,
+        }
+    }
+
+    fn assert_bbox_eq(actual: [f32; 4], expected: [f32; 4]) {
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 1e-5, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn unflip_bbox_mirrors_x_and_keeps_order() {
+        assert_bbox_eq(unflip_bbox([0.1, 0.2, 0.3, 0.6]), [0.7, 0.2, 0.9, 0.6]);
+        // 두 번 뒤집으면 원래 박스
+        let bbox = [0.05, 0.1, 0.45, 0.9];
+        assert_bbox_eq(unflip_bbox(unflip_bbox(bbox)), bbox);
+    }
+
+    #[test]
+    fn fuses_overlapping_boxes_with_confidence_weighted_average() {
+        let passes = vec![
+            vec![detection([0.1, 0.1, 0.5, 0.5], 0.9, 3)],
+            vec![detection([0.2, 0.1, 0.6, 0.5], 0.6, 3)],
+        ];
+        let fused = weighted_box_fusion(&passes, 0.5, 0.0, 0.5);
+        assert_eq!(fused.len(), 1);
+        assert_bbox_eq(fused[0].bbox, [0.14, 0.1, 0.54, 0.5]);
+        assert!((fused[0].confidence - 0.75).abs() < 1e-5);
+    }
+
+    #[test]
+    fn keeps_different_classes_separate() {
+        let passes = vec![
+            vec![detection([0.1, 0.1, 0.5, 0.5], 0.9, 1)],
+            vec![detection([0.1, 0.1, 0.5, 0.5], 0.9, 2)],
+        ];
+        let fused = weighted_box_fusion(&passes, 0.5, 0.0, 0.0);
+        assert_eq!(fused.len(), 2);
+    }
+
+    #[test]
+    fn drops_boxes_below_threshold_after_pass_weighting() {
+        // 두 패스 중 한 패스에서만 0.9로 검출 → 0.45로 보정되어 0.5 임계값에 걸림
+        let passes = vec![vec![detection([0.1, 0.1, 0.5, 0.5], 0.9, 1)], Vec::new()];
+        assert!(weighted_box_fusion(&passes, 0.5, 0.0, CONFIDENCE_THRESHOLD).is_empty());
+
+        let fused = weighted_box_fusion(&passes, 0.5, 0.0, 0.4);
+        assert_eq!(fused.len(), 1);
+        assert!((fused[0].confidence - 0.45).abs() < 1e-5);
+    }
+
+    #[test]
+    fn skip_box_threshold_excludes_weak_boxes_before_fusion() {
+        let passes = vec![
+            vec![detection([0.1, 0.1, 0.5, 0.5], 0.9, 1)],
+            vec![detection([0.3, 0.1, 0.7, 0.5], 0.2, 1)],
+        ];
+        let fused = weighted_box_fusion(&passes, 0.3, 0.5, 0.0);
+        assert_eq!(fused.len(), 1);
+        assert_bbox_eq(fused[0].bbox, [0.1, 0.1, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn fused_mask_is_rebuilt_from_member_masks() {
+        let mask = |x: u32| Mask::from_fn(10, 10, [x, 0, 6, 10], |_, _| true);
+        let mut a = detection([0.0, 0.0, 0.6, 1.0], 0.9, 1);
+        a.mask = Some(mask(0));
+        let mut b = detection([0.2, 0.0, 0.8, 1.0], 0.9, 1);
+        b.mask = Some(mask(2));
+        let mut c = detection([0.4, 0.0, 1.0, 1.0], 0.9, 1);
+        c.mask = Some(mask(4));
+
+        let passes = vec![vec![a.clone()], vec![b], vec![c]];
+        let fused = weighted_box_fusion(&passes, 0.3, 0.0, 0.0);
+        assert_eq!(fused.len(), 1);
+        // 세 마스크 중 둘 이상이 덮는 열(2..8)만 남음
+        let fused_mask = fused[0].mask.as_ref().unwrap();
+        let columns: Vec<u32> = (0..10).filter(|&x| fused_mask.contains(x, 0)).collect();
+        assert_eq!(columns, (2..8).collect::<Vec<_>>());
+
+        // 마스크가 없는 구성원이 있으면 첫 구성원의 마스크를 그대로 쓰지 않고 제외
+        let passes = vec![vec![a], vec![detection([0.2, 0.0, 0.8, 1.0], 0.8, 1)]];
+        let fused = weighted_box_fusion(&passes, 0.3, 0.0, 0.0);
+        assert_eq!(fused.len(), 1);
+        assert!(fused[0].mask.is_none());
+    }
+
+    #[test]
+    fn fused_obb_is_averaged_with_the_boxes() {
+        let obb = |cx: f32, angle: f32| OrientedBox {
+            cx,
+            cy: 50.0,
+            width: 40.0,
+            height: 20.0,
+            angle,
+        };
+        let mut a = detection([0.3, 0.4, 0.7, 0.6], 0.9, 1);
+        a.obb = Some(obb(50.0, 0.1));
+        let mut b = detection([0.32, 0.4, 0.72, 0.6], 0.9, 1);
+        b.obb = Some(obb(52.0, 0.3));
+
+        let fused = weighted_box_fusion(&[vec![a], vec![b]], 0.5, 0.0, 0.0);
+        assert_eq!(fused.len(), 1);
+        let fused_obb = fused[0].obb.unwrap();
+        assert!((fused_obb.cx - 51.0).abs() < 1e-4);
+        assert!((fused_obb.angle - 0.2).abs() < 1e-4);
+    }
+
+    #[test]
+    fn fuses_keypoints_with_confidence_weighted_average() {
+        let with_keypoint = |bbox, confidence, x| Detection {
+            keypoints: Some(vec![Keypoint {
+                x,
+                y: 0.3,
+                visibility: 1.0,
+            }]),
+            ..detection(bbox, confidence, 0)
+        };
+        let passes = vec![
+            vec![with_keypoint([0.1, 0.1, 0.5, 0.5], 0.9, 0.2)],
+            vec![with_keypoint([0.1, 0.1, 0.5, 0.5], 0.3, 0.4)],
+        ];
+        let fused = weighted_box_fusion(&passes, 0.5, 0.0, 0.0);
+        assert_eq!(fused.len(), 1);
+        let keypoints = fused[0].keypoints.as_ref().unwrap();
+        assert!((keypoints[0].x - 0.25).abs() < 1e-5);
+        assert!((keypoints[0].y - 0.3).abs() < 1e-5);
+    }
+}
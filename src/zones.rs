@@ -0,0 +1,437 @@
+use crate::Detection;
+use image::{Rgb, RgbImage};
+use imageproc::drawing::{draw_hollow_polygon_mut, draw_polygon_mut};
+use imageproc::point::Point;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// 상수 정의
+const INCLUDE_ZONE_COLOR: Rgb<u8> = Rgb([0, 200, 0]); // 초록색
+const EXCLUDE_ZONE_COLOR: Rgb<u8> = Rgb([200, 0, 200]); // 보라색
+const ZONE_FILL_ALPHA: f32 = 0.3;
+
+/// 영역 동작 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZoneMode {
+    /// 영역 안의 검출만 유지
+    Include,
+    /// 영역 안의 검출 제거
+    Exclude,
+}
+
+/// 다각형 관심 영역 (정규화 좌표 0-1)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Zone {
+    pub name: String,
+    pub polygon: Vec<[f32; 2]>,
+    #[serde(default = "default_zone_mode")]
+    pub mode: ZoneMode,
+}
+
+fn default_zone_mode() -> ZoneMode {
+    ZoneMode::Include
+}
+
+/// 검출과 영역의 겹침 판정 기준
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OverlapCriterion {
+    /// 바운딩 박스 중심이 다각형 안에 있는 경우
+    #[default]
+    CenterInPolygon,
+    /// 박스 면적 대비 교집합 면적(IoA)이 임계값 이상인 경우
+    Ioa { threshold: f32 },
+}
+
+impl Zone {
+    /// 점이 다각형 내부에 있는지 확인 (ray casting)
+    pub fn contains_point(&self, x: f32, y: f32) -> bool {
+        let n = self.polygon.len();
+        if n < 3 {
+            return false;
+        }
+
+        let mut inside = false;
+        let mut j = n - 1;
+        for i in 0..n {
+            let [xi, yi] = self.polygon[i];
+            let [xj, yj] = self.polygon[j];
+            if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+                inside = !inside;
+            }
+            j = i;
+        }
+        inside
+    }
+
+    /// 박스 면적 대비 다각형과의 교집합 면적 비율 (Intersection over Area)
+    pub fn ioa(&self, bbox: &[f32; 4]) -> f32 {
+        let box_area = (bbox[2] - bbox[0]).max(0.0) * (bbox[3] - bbox[1]).max(0.0);
+        if box_area <= 0.0 || self.polygon.len() < 3 {
+            return 0.0;
+        }
+        let clipped = clip_polygon_to_rect(&self.polygon, bbox);
+        polygon_area(&clipped) / box_area
+    }
+
+    /// 검출이 영역과 겹치는지 판정
+    pub fn matches(&self, detection: &Detection, criterion: OverlapCriterion) -> bool {
+        match criterion {
+            OverlapCriterion::CenterInPolygon => {
+                let [x1, y1, x2, y2] = detection.bbox;
+                self.contains_point((x1 + x2) / 2.0, (y1 + y2) / 2.0)
+            }
+            OverlapCriterion::Ioa { threshold } => self.ioa(&detection.bbox) >= threshold,
+        }
+    }
+}
+
+/// 다각형 넓이 (shoelace 공식)
+fn polygon_area(points: &[[f32; 2]]) -> f32 {
+    if points.len() < 3 {
+        return 0.0;
+    }
+    let mut area = 0.0;
+    for i in 0..points.len() {
+        let [x1, y1] = points[i];
+        let [x2, y2] = points[(i + 1) % points.len()];
+        area += x1 * y2 - x2 * y1;
+    }
+    (area / 2.0).abs()
+}
+
+/// 다각형을 축 정렬 사각형으로 클리핑 (Sutherland-Hodgman)
+fn clip_polygon_to_rect(polygon: &[[f32; 2]], rect: &[f32; 4]) -> Vec<[f32; 2]> {
+    // (축, 경계값, 경계 안쪽이 큰 값인지)
+    let edges = [
+        (0, rect[0], true),
+        (0, rect[2], false),
+        (1, rect[1], true),
+        (1, rect[3], false),
+    ];
+
+    let mut output = polygon.to_vec();
+    for (axis, bound, keep_greater) in edges {
+        if output.is_empty() {
+            break;
+        }
+        let input = std::mem::take(&mut output);
+        let inside = |p: &[f32; 2]| {
+            if keep_greater {
+                p[axis] >= bound
+            } else {
+                p[axis] <= bound
+            }
+        };
+        let intersect = |a: &[f32; 2], b: &[f32; 2]| {
+            let t = (bound - a[axis]) / (b[axis] - a[axis]);
+            [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])]
+        };
+
+        for i in 0..input.len() {
+            let current = input[i];
+            let prev = input[(i + input.len() - 1) % input.len()];
+            match (inside(&prev), inside(&current)) {
+                (true, true) => output.push(current),
+                (true, false) => output.push(intersect(&prev, &current)),
+                (false, true) => {
+                    output.push(intersect(&prev, &current));
+                    output.push(current);
+                }
+                (false, false) => {}
+            }
+        }
+    }
+    output
+}
+
+/// 영역 기반 검출 필터
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoiFilter {
+    pub zones: Vec<Zone>,
+    #[serde(default)]
+    pub criterion: OverlapCriterion,
+}
+
+impl RoiFilter {
+    /// JSON 문자열에서 필터 로드
+    pub fn from_json_str(json: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// JSON 파일에서 필터 로드
+    pub fn from_json_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Self::from_json_str(&json)
+    }
+
+    /// 검출이 겹치는 영역 이름 목록
+    pub fn zones_for(&self, detection: &Detection) -> Vec<&str> {
+        self.zones
+            .iter()
+            .filter(|zone| zone.matches(detection, self.criterion))
+            .map(|zone| zone.name.as_str())
+            .collect()
+    }
+
+    /// 검출을 유지할지 판정
+    ///
+    /// include 영역이 하나라도 있으면 그중 하나와 겹쳐야 하고,
+    /// 어떤 exclude 영역과도 겹치지 않아야 합니다.
+    pub fn accepts(&self, detection: &Detection) -> bool {
+        let mut has_include = false;
+        let mut in_include = false;
+
+        for zone in &self.zones {
+            let matched = zone.matches(detection, self.criterion);
+            match zone.mode {
+                ZoneMode::Include => {
+                    has_include = true;
+                    in_include |= matched;
+                }
+                ZoneMode::Exclude if matched => return false,
+                ZoneMode::Exclude => {}
+            }
+        }
+
+        !has_include || in_include
+    }
+
+    /// 조건을 만족하는 검출만 남김
+    pub fn filter(&self, detections: &[Detection]) -> Vec<Detection> {
+        detections
+            .iter()
+            .filter(|d| self.accepts(d))
+            .cloned()
+            .collect()
+    }
+
+    /// 각 검출에 겹치는 영역 이름을 태깅
+    pub fn tag(&self, detections: &[Detection]) -> Vec<(Detection, Vec<String>)> {
+        detections
+            .iter()
+            .map(|d| {
+                let zones = self.zones_for(d).into_iter().map(String::from).collect();
+                (d.clone(), zones)
+            })
+            .collect()
+    }
+}
+
+/// 영역을 반투명하게 오버레이
+pub fn draw_zones(image: &mut RgbImage, zones: &[Zone]) {
+    let (width, height) = (image.width() as f32, image.height() as f32);
+
+    for zone in zones {
+        if zone.polygon.len() < 3 {
+            continue;
+        }
+        let color = match zone.mode {
+            ZoneMode::Include => INCLUDE_ZONE_COLOR,
+            ZoneMode::Exclude => EXCLUDE_ZONE_COLOR,
+        };
+
+        let mut points: Vec<Point<i32>> = zone
+            .polygon
+            .iter()
+            .map(|[x, y]| Point::new((x * width) as i32, (y * height) as i32))
+            .collect();
+        // draw_polygon_mut는 닫힌 다각형(첫 점 == 끝 점)을 허용하지 않음
+        points.dedup();
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        if points.len() < 3 {
+            continue;
+        }
+
+        // 마스크에 다각형을 채운 뒤 알파 블렌딩
+        let mut mask = image::GrayImage::new(image.width(), image.height());
+        draw_polygon_mut(&mut mask, &points, image::Luma([255]));
+        for (pixel, mask_pixel) in image.pixels_mut().zip(mask.pixels()) {
+            if mask_pixel[0] > 0 {
+                for (p, c) in pixel.0.iter_mut().zip(color.0.iter()) {
+                    *p = (*p as f32 * (1.0 - ZONE_FILL_ALPHA) + *c as f32 * ZONE_FILL_ALPHA) as u8;
+                }
+            }
+        }
+
+        // 외곽선
+        let outline: Vec<Point<f32>> = points
+            .iter()
+            .map(|p| Point::new(p.x as f32, p.y as f32))
+            .collect();
+        draw_hollow_polygon_mut(image, &outline, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-5;
+
+    fn detection(bbox: [f32; 4]) -> Detection {
+        Detection {
+            bbox,
+            confidence: 0.9,
+            class_id: 1,
+            class_name: "person".to_string(),
+            mask: None,
+            keypoints: None,
+            obb: None,
+            secondary: Vec::new(),
+            original_class_id: None,
+        }
+    }
+
+    fn zone(name: &str, polygon: &[[f32; 2]], mode: ZoneMode) -> Zone {
+        Zone {
+            name: name.to_string(),
+            polygon: polygon.to_vec(),
+            mode,
+        }
+    }
+
+    /// 정규화 좌표 사각형 영역
+    fn rect_zone(name: &str, [x1, y1, x2, y2]: [f32; 4], mode: ZoneMode) -> Zone {
+        zone(name, &[[x1, y1], [x2, y1], [x2, y2], [x1, y2]], mode)
+    }
+
+    /// 오른쪽 위 0.6 x 0.6이 빠진 L자 (오목) 다각형, 넓이 0.64
+    fn l_shape() -> Zone {
+        zone(
+            "l",
+            &[
+                [0.0, 0.0],
+                [0.4, 0.0],
+                [0.4, 0.6],
+                [1.0, 0.6],
+                [1.0, 1.0],
+                [0.0, 1.0],
+            ],
+            ZoneMode::Include,
+        )
+    }
+
+    #[test]
+    fn contains_points_in_convex_and_concave_polygons() {
+        let square = rect_zone("square", [0.2, 0.2, 0.8, 0.8], ZoneMode::Include);
+        assert!(square.contains_point(0.5, 0.5));
+        assert!(!square.contains_point(0.1, 0.5));
+        assert!(!square.contains_point(0.5, 0.9));
+
+        let l = l_shape();
+        assert!(l.contains_point(0.2, 0.2));
+        assert!(l.contains_point(0.8, 0.8));
+        // 빠진 부분은 다각형의 볼록 껍질 안이지만 밖
+        assert!(!l.contains_point(0.7, 0.3));
+
+        let line = zone("line", &[[0.0, 0.0], [1.0, 1.0]], ZoneMode::Include);
+        assert!(!line.contains_point(0.5, 0.5));
+    }
+
+    #[test]
+    fn boundary_points_belong_to_exactly_one_adjacent_zone() {
+        let left = rect_zone("left", [0.0, 0.0, 0.5, 1.0], ZoneMode::Include);
+        let right = rect_zone("right", [0.5, 0.0, 1.0, 1.0], ZoneMode::Include);
+        let below = rect_zone("below", [0.0, 1.0, 0.5, 2.0], ZoneMode::Include);
+
+        // 세로 공유 변
+        for y in [0.25, 0.5, 0.75] {
+            assert_ne!(left.contains_point(0.5, y), right.contains_point(0.5, y));
+        }
+        // 가로 공유 변
+        for x in [0.1, 0.25, 0.4] {
+            assert_ne!(left.contains_point(x, 1.0), below.contains_point(x, 1.0));
+        }
+    }
+
+    #[test]
+    fn ioa_of_boxes_inside_half_inside_and_outside() {
+        let square = rect_zone("square", [0.0, 0.0, 0.5, 1.0], ZoneMode::Include);
+        assert!((square.ioa(&[0.1, 0.1, 0.3, 0.3]) - 1.0).abs() < EPSILON);
+        assert!((square.ioa(&[0.25, 0.25, 0.75, 0.75]) - 0.5).abs() < EPSILON);
+        assert_eq!(square.ioa(&[0.6, 0.6, 0.9, 0.9]), 0.0);
+        // 넓이가 없는 박스
+        assert_eq!(square.ioa(&[0.2, 0.2, 0.2, 0.4]), 0.0);
+
+        // 오목 다각형도 사각형 창으로 클리핑
+        let l = l_shape();
+        assert!((l.ioa(&[0.0, 0.0, 1.0, 1.0]) - 0.64).abs() < EPSILON);
+        assert!((l.ioa(&[0.2, 0.4, 0.6, 0.8]) - 0.75).abs() < EPSILON);
+    }
+
+    #[test]
+    fn clips_polygon_to_rect() {
+        let square = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let clipped = clip_polygon_to_rect(&square, &[0.5, 0.5, 2.0, 2.0]);
+        assert!((polygon_area(&clipped) - 0.25).abs() < EPSILON);
+        assert!(clipped.iter().all(|[x, y]| *x >= 0.5 && *y >= 0.5));
+
+        assert!(clip_polygon_to_rect(&square, &[2.0, 2.0, 3.0, 3.0]).is_empty());
+    }
+
+    #[test]
+    fn accepts_include_and_rejects_exclude() {
+        let filter = RoiFilter {
+            zones: vec![
+                rect_zone("left", [0.0, 0.0, 0.5, 1.0], ZoneMode::Include),
+                rect_zone("door", [0.0, 0.0, 0.2, 0.2], ZoneMode::Exclude),
+            ],
+            criterion: OverlapCriterion::CenterInPolygon,
+        };
+        let kept = detection([0.2, 0.4, 0.4, 0.6]);
+        let in_exclude = detection([0.05, 0.05, 0.15, 0.15]);
+        let outside_include = detection([0.6, 0.4, 0.8, 0.6]);
+        assert!(filter.accepts(&kept));
+        assert!(!filter.accepts(&in_exclude));
+        assert!(!filter.accepts(&outside_include));
+        assert_eq!(
+            filter.filter(&[kept.clone(), in_exclude.clone(), outside_include.clone()]),
+            vec![kept.clone()]
+        );
+        assert_eq!(filter.zones_for(&in_exclude), vec!["left", "door"]);
+
+        // include 영역이 없으면 exclude만 적용
+        let exclude_only = RoiFilter {
+            zones: vec![rect_zone("door", [0.0, 0.0, 0.2, 0.2], ZoneMode::Exclude)],
+            criterion: OverlapCriterion::CenterInPolygon,
+        };
+        assert!(exclude_only.accepts(&outside_include));
+        assert!(!exclude_only.accepts(&in_exclude));
+    }
+
+    #[test]
+    fn ioa_criterion_uses_threshold() {
+        let zones = vec![rect_zone("left", [0.0, 0.0, 0.5, 1.0], ZoneMode::Include)];
+        let half_inside = detection([0.25, 0.25, 0.75, 0.75]);
+        let loose = RoiFilter {
+            zones: zones.clone(),
+            criterion: OverlapCriterion::Ioa { threshold: 0.5 },
+        };
+        let strict = RoiFilter {
+            zones,
+            criterion: OverlapCriterion::Ioa { threshold: 0.6 },
+        };
+        assert!(loose.accepts(&half_inside));
+        assert!(!strict.accepts(&half_inside));
+    }
+
+    #[test]
+    fn parses_json_with_defaults() {
+        let filter = RoiFilter::from_json_str(
+            r#"{"zones": [{"name": "a", "polygon": [[0, 0], [1, 0], [1, 1]]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(filter.zones[0].mode, ZoneMode::Include);
+        assert_eq!(filter.criterion, OverlapCriterion::CenterInPolygon);
+
+        let filter = RoiFilter::from_json_str(
+            r#"{"zones": [], "criterion": {"type": "ioa", "threshold": 0.3}}"#,
+        )
+        .unwrap();
+        assert_eq!(filter.criterion, OverlapCriterion::Ioa { threshold: 0.3 });
+    }
+}
@@ -0,0 +1,129 @@
+use image::codecs::jpeg::JpegEncoder;
+use image::{Rgb, RgbImage};
+use rf_detr_onnx_test_lib::{decode_image, decode_image_with_orientation, OrientationMode};
+
+// 상수 정의
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 32;
+const RED: Rgb<u8> = Rgb([255, 0, 0]);
+const BLUE: Rgb<u8> = Rgb([0, 0, 255]);
+/// JPEG 손실 압축을 감안한 채널별 허용 오차
+const TOLERANCE: i32 = 48;
+
+/// 왼쪽 절반은 빨강, 오른쪽 절반은 파랑인 가로 이미지 (회전/반전 여부를 색으로 판별)
+fn sample_image() -> RgbImage {
+    RgbImage::from_fn(WIDTH, HEIGHT, |x, _| if x < WIDTH / 2 { RED } else { BLUE })
+}
+
+/// 리틀 엔디언 TIFF IFD 하나에 방향 태그(0x0112)만 담은 EXIF APP1 세그먼트
+fn exif_segment(orientation: u16) -> Vec<u8> {
+    let mut tiff = Vec::new();
+    tiff.extend_from_slice(b"II*\0");
+    tiff.extend_from_slice(&8u32.to_le_bytes()); // 첫 IFD 위치
+    tiff.extend_from_slice(&1u16.to_le_bytes()); // 항목 수
+    tiff.extend_from_slice(&0x0112u16.to_le_bytes()); // Orientation
+    tiff.extend_from_slice(&3u16.to_le_bytes()); // SHORT
+    tiff.extend_from_slice(&1u32.to_le_bytes()); // 개수
+    tiff.extend_from_slice(&orientation.to_le_bytes());
+    tiff.extend_from_slice(&[0, 0]); // 값 패딩
+    tiff.extend_from_slice(&0u32.to_le_bytes()); // 다음 IFD 없음
+
+    let payload_len = 6 + tiff.len();
+    let mut segment = vec![0xFF, 0xE1];
+    segment.extend_from_slice(&((payload_len + 2) as u16).to_be_bytes());
+    segment.extend_from_slice(b"Exif\0\0");
+    segment.extend_from_slice(&tiff);
+    segment
+}
+
+/// 샘플 이미지를 JPEG로 인코딩하고, 방향이 주어지면 SOI 바로 뒤에 EXIF 세그먼트 삽입
+fn sample_jpeg(orientation: Option<u16>) -> Vec<u8> {
+    let mut jpeg = Vec::new();
+    JpegEncoder::new_with_quality(&mut jpeg, 95)
+        .encode_image(&sample_image())
+        .expect("encode sample jpeg");
+    if let Some(orientation) = orientation {
+        jpeg.splice(2..2, exif_segment(orientation));
+    }
+    jpeg
+}
+
+fn assert_color(image: &RgbImage, x: u32, y: u32, expected: Rgb<u8>) {
+    let actual = *image.get_pixel(x, y);
+    let close = actual
+        .0
+        .iter()
+        .zip(expected.0.iter())
+        .all(|(&a, &e)| (a as i32 - e as i32).abs() <= TOLERANCE);
+    assert!(
+        close,
+        "pixel ({x}, {y}) = {actual:?}, expected about {expected:?}"
+    );
+}
+
+#[test]
+fn jpeg_without_exif_is_unchanged() {
+    let image = decode_image(&sample_jpeg(None)).unwrap();
+    assert_eq!(image.dimensions(), (WIDTH, HEIGHT));
+    assert_color(&image, 8, 16, RED);
+    assert_color(&image, 56, 16, BLUE);
+}
+
+#[test]
+fn rotate_90_tag_is_applied() {
+    let image = decode_image(&sample_jpeg(Some(6))).unwrap();
+    // 시계 방향 90도: 왼쪽(빨강)이 위로 감
+    assert_eq!(image.dimensions(), (HEIGHT, WIDTH));
+    assert_color(&image, 16, 8, RED);
+    assert_color(&image, 16, 56, BLUE);
+}
+
+#[test]
+fn rotate_180_tag_is_applied() {
+    let image = decode_image(&sample_jpeg(Some(3))).unwrap();
+    assert_eq!(image.dimensions(), (WIDTH, HEIGHT));
+    assert_color(&image, 8, 16, BLUE);
+    assert_color(&image, 56, 16, RED);
+}
+
+#[test]
+fn rotate_270_tag_is_applied() {
+    let image = decode_image(&sample_jpeg(Some(8))).unwrap();
+    // 시계 방향 270도: 왼쪽(빨강)이 아래로 감
+    assert_eq!(image.dimensions(), (HEIGHT, WIDTH));
+    assert_color(&image, 16, 8, BLUE);
+    assert_color(&image, 16, 56, RED);
+}
+
+#[test]
+fn mirrored_tag_is_applied() {
+    let image = decode_image(&sample_jpeg(Some(2))).unwrap();
+    assert_eq!(image.dimensions(), (WIDTH, HEIGHT));
+    assert_color(&image, 8, 16, BLUE);
+    assert_color(&image, 56, 16, RED);
+}
+
+#[test]
+fn raw_mode_keeps_stored_orientation() {
+    for orientation in [2, 3, 6, 8] {
+        let image =
+            decode_image_with_orientation(&sample_jpeg(Some(orientation)), OrientationMode::Raw)
+                .unwrap();
+        assert_eq!(
+            image.dimensions(),
+            (WIDTH, HEIGHT),
+            "orientation {orientation}"
+        );
+        assert_color(&image, 8, 16, RED);
+        assert_color(&image, 56, 16, BLUE);
+    }
+}
+
+#[test]
+fn decode_image_defaults_to_apply() {
+    let jpeg = sample_jpeg(Some(6));
+    assert_eq!(
+        decode_image(&jpeg).unwrap(),
+        decode_image_with_orientation(&jpeg, OrientationMode::Apply).unwrap()
+    );
+}